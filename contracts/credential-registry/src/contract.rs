@@ -1,25 +1,66 @@
+use ark_bn254::Fr;
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdResult,
+    entry_point, from_json, to_json_binary, Addr, Api, Binary, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage,
 };
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use ripemd::Ripemd160;
 use sha2::{Digest, Sha256};
 
-use citizen_common::credential::{Credential, CredentialType, ZkProof};
+use citizen_common::credential::{
+    AttributeClaim, Credential, CredentialType, Groth16Proof, Groth16VerificationKey, Nullifier,
+    PredicateOp, VerificationResult, ZkProof,
+};
 use citizen_common::errors::ContractError;
+use citizen_common::hooks::CredentialHookMsg;
+use citizen_common::vc::{self, CredentialStatus, VcProof, VerifiableCredential};
+use citizen_common::verification::{
+    VerificationConfig, VerificationRequest, VerificationStatus, VerificationType,
+};
 
+use crate::groth16::{self, PreparedVerificationKey};
+use crate::hooks::*;
+use crate::merkle;
 use crate::msg::*;
+use crate::privacy::*;
+use crate::recovery::*;
+use crate::relayers::*;
+use crate::schemas::*;
 use crate::state::*;
+use crate::status_list;
 
 const CONTRACT_NAME: &str = "crates.io:credential-registry";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Public signal layout of `circuits/citizenship.circom`:
+/// `[valid, commitment, currentTime, minAgeSeconds]`
+const CITIZENSHIP_SIGNALS: usize = 4;
+const SIGNAL_VALID: usize = 0;
+const SIGNAL_COMMITMENT: usize = 1;
+const SIGNAL_CURRENT_TIME: usize = 2;
+const SIGNAL_MIN_AGE: usize = 3;
+/// Oldest currentTime a proof may carry, in seconds before the block time
+const MAX_PROOF_AGE: u64 = 3_600;
+
+/// Most entries a single `BatchIssue` may carry
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Most expired-but-unswept credentials `CountValidCredentials` will discount
+/// before asking for a sweep
+const MAX_UNSWEPT_COUNTED: usize = 100;
+
+/// `credentialStatus.type` and `proof.type` on exported Verifiable Credentials.
+/// The proof is the registry's on-chain record; it carries no signature.
+const VC_STATUS_TYPE: &str = "CitizenLedgerStatusList";
+const VC_PROOF_TYPE: &str = "CitizenLedgerRegistryAttestation";
+
 // ── Instantiate ─────────────────────────────────────────────────────
 
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -29,12 +70,22 @@ pub fn instantiate(
     ADMIN.save(deps.storage, &admin)?;
     CREDENTIAL_COUNT.save(deps.storage, &0u64)?;
     RECOVERY_TIMELOCK.save(deps.storage, &10080u64)?; // ~7 days at 6s blocks
+    ATTESTATION_THRESHOLD.save(deps.storage, &1u32)?;
+    HOOK_GAS_LIMIT.save(deps.storage, &DEFAULT_HOOK_GAS_LIMIT)?;
 
     for issuer_str in &msg.issuers {
         let issuer = deps.api.addr_validate(issuer_str)?;
         ISSUERS.save(deps.storage, &issuer, &true)?;
     }
 
+    let config = msg
+        .verification_config
+        .unwrap_or_else(default_verification_config);
+    save_verification_config(deps.storage, config)?;
+    VOUCHING_CONFIG.save(deps.storage, &default_vouching_config())?;
+    NEXT_STATUS_INDEX.save(deps.storage, &0u64)?;
+    save_revocation_root(deps.storage, 0, env.block.time.seconds())?;
+
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("admin", admin.as_str())
@@ -50,31 +101,115 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // Writes would race the indexes still being built, so only the backfill runs
+    if PENDING_BACKFILL.exists(deps.storage) && !matches!(msg, ExecuteMsg::BackfillIndexes { .. }) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Indexes are still being backfilled after migration",
+        )));
+    }
     match msg {
         ExecuteMsg::IssueCredential {
             holder,
             credential_type,
             commitment,
             expires_at,
+            nullifier,
+            attributes_root,
         } => execute_issue(
             deps,
             env,
             info,
-            holder,
-            credential_type,
-            commitment,
-            expires_at,
+            IssuanceEntry {
+                holder,
+                credential_type,
+                commitment,
+                expires_at,
+                nullifier,
+                attributes_root,
+            },
         ),
+        ExecuteMsg::BatchIssue { entries } => execute_batch_issue(deps, env, info, entries),
+        ExecuteMsg::AnchorIssuanceRoot { root } => execute_anchor_root(deps, env, info, root),
+        ExecuteMsg::ClaimAnchoredCredential {
+            root,
+            entry,
+            position,
+            siblings,
+        } => execute_claim_anchored(deps, env, root, entry, position, siblings),
         ExecuteMsg::VerifyCredential {
             credential_id,
             proof,
-        } => execute_verify(deps, env, info, credential_id, proof),
+            min_age_seconds,
+        } => execute_verify(deps, env, info, credential_id, proof, min_age_seconds),
+        ExecuteMsg::RenewCredential {
+            credential_id,
+            commitment,
+            expires_at,
+            attributes_root,
+        } => execute_renew(
+            deps,
+            env,
+            info,
+            credential_id,
+            commitment,
+            expires_at,
+            attributes_root,
+        ),
         ExecuteMsg::RevokeCredential {
             credential_id,
             reason,
-        } => execute_revoke(deps, env, info, credential_id, reason),
-        ExecuteMsg::AddIssuer { issuer } => execute_add_issuer(deps, info, issuer),
+            fraud,
+        } => execute_revoke(deps, env, info, credential_id, reason, fraud),
+        ExecuteMsg::AddIssuer { issuer, scope } => execute_add_issuer(deps, info, issuer, scope),
         ExecuteMsg::RemoveIssuer { issuer } => execute_remove_issuer(deps, info, issuer),
+        ExecuteMsg::RegisterCredentialSchema { name, schema } => {
+            execute_register_schema(deps, env, info, name, schema)
+        }
+        ExecuteMsg::UpdateCredentialSchema { name, schema } => {
+            execute_update_schema(deps, info, name, schema)
+        }
+        ExecuteMsg::SetIssuerStatus { issuer, status } => {
+            execute_set_issuer_status(deps, info, issuer, status)
+        }
+        ExecuteMsg::RevokeIssuerCredentials {
+            issuer,
+            issued_since,
+            reason,
+            start_after,
+            limit,
+        } => execute_revoke_issuer_credentials(
+            deps,
+            env,
+            info,
+            issuer,
+            issued_since,
+            reason,
+            start_after,
+            limit,
+        ),
+        ExecuteMsg::SweepExpired { limit } => execute_sweep_expired(deps, env, limit),
+        ExecuteMsg::BackfillIndexes { limit } => execute_backfill_indexes(deps, env, info, limit),
+        ExecuteMsg::AddTypeImplication { implication } => {
+            execute_add_type_implication(deps, info, implication)
+        }
+        ExecuteMsg::RemoveTypeImplication { from, to } => {
+            execute_remove_type_implication(deps, info, from, to)
+        }
+        ExecuteMsg::SetCredentialPrivacy {
+            credential_type,
+            private,
+        } => execute_set_credential_privacy(deps, info, credential_type, private),
+        ExecuteMsg::GrantCredentialAccess { contract } => {
+            execute_grant_credential_access(deps, info, contract)
+        }
+        ExecuteMsg::RevokeCredentialAccess { contract } => {
+            execute_revoke_credential_access(deps, info, contract)
+        }
+        ExecuteMsg::AddHook { contract } => execute_add_hook(deps, info, contract),
+        ExecuteMsg::RemoveHook { contract } => execute_remove_hook(deps, info, contract),
+        ExecuteMsg::SetHookGasLimit { gas_limit } => {
+            execute_set_hook_gas_limit(deps, info, gas_limit)
+        }
         ExecuteMsg::TransferAdmin { new_admin } => execute_transfer_admin(deps, info, new_admin),
         ExecuteMsg::SubmitVerificationResult {
             request_id,
@@ -82,6 +217,7 @@ pub fn execute(
             credential_type,
             commitment,
             approved,
+            nullifier,
         } => execute_submit_verification(
             deps,
             env,
@@ -91,6 +227,7 @@ pub fn execute(
             credential_type,
             commitment,
             approved,
+            nullifier,
         ),
         ExecuteMsg::RequestKeyRecovery {
             old_address,
@@ -102,6 +239,81 @@ pub fn execute(
         ExecuteMsg::ContestRecovery { recovery_id } => {
             execute_contest_recovery(deps, info, recovery_id)
         }
+        ExecuteMsg::SetGuardians {
+            guardians,
+            threshold,
+        } => execute_set_guardians(deps, info, guardians, threshold),
+        ExecuteMsg::ApproveRecovery { recovery_id } => {
+            execute_approve_recovery(deps, env, info, recovery_id)
+        }
+        ExecuteMsg::CancelRecovery { recovery_id } => {
+            execute_cancel_recovery(deps, info, recovery_id)
+        }
+        ExecuteMsg::ResolveRecovery {
+            recovery_id,
+            approve,
+        } => execute_resolve_recovery(deps, env, info, recovery_id, approve),
+        ExecuteMsg::RegisterVerificationKey {
+            credential_type,
+            version,
+            verification_key,
+        } => execute_register_vk(
+            deps,
+            env,
+            info,
+            credential_type.to_string(),
+            version,
+            verification_key,
+        ),
+        ExecuteMsg::RegisterPredicateKey {
+            attribute,
+            op,
+            version,
+            verification_key,
+        } => execute_register_vk(
+            deps,
+            env,
+            info,
+            predicate_key_type(&attribute, &op),
+            version,
+            verification_key,
+        ),
+        ExecuteMsg::DeprecateVerificationKey {
+            credential_type,
+            version,
+        } => execute_deprecate_vk(deps, info, credential_type.to_string(), version),
+        ExecuteMsg::DeprecatePredicateKey {
+            attribute,
+            op,
+            version,
+        } => execute_deprecate_vk(deps, info, predicate_key_type(&attribute, &op), version),
+        ExecuteMsg::RequestVerification {
+            verification_type,
+            data_hash,
+        } => execute_request_verification(deps, env, info, verification_type, data_hash),
+        ExecuteMsg::StartVerificationReview { request_id } => {
+            execute_start_review(deps, env, info, request_id)
+        }
+        ExecuteMsg::ExpireVerificationRequest { request_id } => {
+            execute_expire_request(deps, env, request_id)
+        }
+        ExecuteMsg::UpdateVerificationConfig { config } => {
+            execute_update_verification_config(deps, info, config)
+        }
+        ExecuteMsg::RequestSocialVouching {
+            data_hash,
+            commitment,
+            nullifier,
+        } => execute_request_vouching(deps, env, info, data_hash, commitment, nullifier),
+        ExecuteMsg::Vouch { request_id } => execute_vouch(deps, env, info, request_id),
+        ExecuteMsg::UpdateVouchingConfig { config } => {
+            execute_update_vouching_config(deps, info, config)
+        }
+        ExecuteMsg::AddRelayer { relayer } => execute_add_relayer(deps, info, relayer),
+        ExecuteMsg::RemoveRelayer { relayer } => execute_remove_relayer(deps, info, relayer),
+        ExecuteMsg::SetAttestationThreshold { threshold } => {
+            execute_set_threshold(deps, info, threshold)
+        }
     }
 }
 
@@ -109,675 +321,6149 @@ fn execute_issue(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    holder: String,
-    credential_type: CredentialType,
-    commitment: String,
-    expires_at: u64,
+    entry: IssuanceEntry,
 ) -> Result<Response, ContractError> {
-    // Only trusted issuers can issue credentials
-    let is_issuer = ISSUERS
-        .may_load(deps.storage, &info.sender)?
-        .unwrap_or(false);
-    if !is_issuer {
-        return Err(ContractError::IssuerNotAuthorized {
-            issuer: info.sender.to_string(),
-        });
-    }
+    let (cred_id, status_index) = issue_entry(deps.storage, deps.api, &env, &info.sender, &entry)?;
+    let hooks = hook_msgs(
+        deps.storage,
+        &CredentialHookMsg::Issued {
+            credential_id: cred_id.clone(),
+            holder: entry.holder.clone(),
+            credential_type: entry.credential_type.clone(),
+        },
+    )?;
 
-    let holder_addr = deps.api.addr_validate(&holder)?;
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "issue_credential")
+        .add_attribute("credential_id", &cred_id)
+        .add_attribute("holder", entry.holder)
+        .add_attribute("type", entry.credential_type.to_string())
+        .add_attribute("issuer", info.sender.as_str())
+        .add_attribute("status_index", status_index.to_string()))
+}
 
-    // Generate a deterministic credential ID
-    let mut hasher = Sha256::new();
-    hasher.update(holder.as_bytes());
-    hasher.update(credential_type.to_string().as_bytes());
-    hasher.update(commitment.as_bytes());
-    hasher.update(env.block.time.seconds().to_le_bytes());
-    let cred_id = hex::encode(hasher.finalize())[..32].to_string();
+/// Issue one credential from `issuer`, returning its id and status index.
+/// Every check runs before the first write, so a rejected entry leaves no trace.
+fn issue_entry(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    env: &Env,
+    issuer: &Addr,
+    entry: &IssuanceEntry,
+) -> Result<(String, u64), ContractError> {
+    ensure_active_issuer(storage, issuer)?;
+    let holder_addr = api.addr_validate(&entry.holder)?;
+    let cred_id = issuer_credential_id(
+        storage,
+        env,
+        &entry.holder,
+        &entry.credential_type,
+        &entry.commitment,
+    )?;
+    check_nullifier_unspent(storage, &entry.nullifier)?;
+    let attributes_root = entry
+        .attributes_root
+        .as_deref()
+        .map(groth16::canonical_field::<Fr>)
+        .transpose()?;
+    ensure_status_capacity(storage)?;
+    // Last check, as it counts the issuance against the issuer's rate limit
+    let expires_at = authorize_issuance(
+        storage,
+        env,
+        issuer,
+        &entry.credential_type,
+        entry.expires_at,
+    )?;
 
-    // Check for duplicate
-    if CREDENTIALS.has(deps.storage, &cred_id) {
-        return Err(ContractError::CredentialAlreadyExists { id: cred_id });
-    }
+    spend_nullifier(storage, env, &entry.nullifier, &holder_addr, &cred_id)?;
 
     let credential = Credential {
         id: cred_id.clone(),
         holder: holder_addr.clone(),
-        credential_type: credential_type.clone(),
-        commitment,
-        issuer: info.sender.clone(),
+        credential_type: entry.credential_type.clone(),
+        commitment: entry.commitment.clone(),
+        issuer: issuer.clone(),
         issued_at: env.block.time.seconds(),
         expires_at,
         revoked: false,
     };
 
-    CREDENTIALS.save(deps.storage, &cred_id, &credential)?;
-    HOLDER_CREDENTIALS.save(deps.storage, (&holder_addr, &cred_id), &true)?;
+    CREDENTIALS.save(storage, &cred_id, &credential)?;
+    HOLDER_CREDENTIALS.save(storage, (&holder_addr, &cred_id), &true)?;
+    ISSUER_CREDENTIALS.save(storage, (issuer, &cred_id), &true)?;
+    index_expiry(storage, &credential)?;
+    if counted_as_active(storage, &credential)? {
+        count_active(storage, &credential)?;
+    }
+    if let Some(root) = attributes_root {
+        CREDENTIAL_ATTRIBUTES.save(storage, &cred_id, &root)?;
+    }
+    let status_index = assign_status_index(storage, &cred_id)?;
 
-    let count = CREDENTIAL_COUNT.load(deps.storage)?;
-    CREDENTIAL_COUNT.save(deps.storage, &(count + 1))?;
+    let count = CREDENTIAL_COUNT.load(storage)?;
+    CREDENTIAL_COUNT.save(storage, &(count + 1))?;
 
-    Ok(Response::new()
-        .add_attribute("action", "issue_credential")
-        .add_attribute("credential_id", &cred_id)
-        .add_attribute("holder", holder)
-        .add_attribute("type", credential_type.to_string())
-        .add_attribute("issuer", info.sender.as_str()))
+    Ok((cred_id, status_index))
 }
 
-fn execute_verify(
+fn execute_batch_issue(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
-    credential_id: String,
-    proof: ZkProof,
+    info: MessageInfo,
+    entries: Vec<IssuanceEntry>,
 ) -> Result<Response, ContractError> {
-    let cred = CREDENTIALS.may_load(deps.storage, &credential_id)?.ok_or(
-        ContractError::CredentialNotFound {
-            id: credential_id.clone(),
-        },
-    )?;
-
-    if cred.revoked {
-        return Err(ContractError::CredentialRevoked);
-    }
-
-    if cred.expires_at > 0 && env.block.time.seconds() > cred.expires_at {
-        return Err(ContractError::CredentialExpired);
-    }
-
-    // ── ZK Proof verification placeholder ──
-    // In production, this would call a ZK verifier (e.g., Groth16 on-chain verifier).
-    // For now, we do a simplified hash-based check: the proof_data must hash to
-    // the credential's commitment.
-    let mut hasher = Sha256::new();
-    hasher.update(proof.proof_data.as_bytes());
-    for input in &proof.public_inputs {
-        hasher.update(input.as_bytes());
+    if entries.is_empty() || entries.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "A batch must hold between 1 and {} entries",
+            MAX_BATCH_SIZE
+        ))));
     }
-    let computed = hex::encode(hasher.finalize())[..32].to_string();
+    ensure_active_issuer(deps.storage, &info.sender)?;
 
-    if computed != cred.commitment[..computed.len().min(cred.commitment.len())] {
-        return Err(ContractError::InvalidProof {
-            reason: "Proof commitment mismatch".to_string(),
-        });
+    let mut events = Vec::with_capacity(entries.len());
+    let mut hooks = vec![];
+    let mut accepted = 0usize;
+    for (index, entry) in entries.iter().enumerate() {
+        match issue_entry(deps.storage, deps.api, &env, &info.sender, entry) {
+            Ok((cred_id, status_index)) => {
+                accepted += 1;
+                hooks.extend(hook_msgs(
+                    deps.storage,
+                    &CredentialHookMsg::Issued {
+                        credential_id: cred_id.clone(),
+                        holder: entry.holder.clone(),
+                        credential_type: entry.credential_type.clone(),
+                    },
+                )?);
+                events.push(
+                    Event::new("credential_issued")
+                        .add_attribute("credential_id", cred_id)
+                        .add_attribute("holder", &entry.holder)
+                        .add_attribute("status_index", status_index.to_string()),
+                );
+            }
+            Err(err) => events.push(
+                Event::new("batch_entry_rejected")
+                    .add_attribute("index", index.to_string())
+                    .add_attribute("holder", &entry.holder)
+                    .add_attribute("reason", err.to_string()),
+            ),
+        }
     }
 
     Ok(Response::new()
-        .add_attribute("action", "verify_credential")
-        .add_attribute("credential_id", credential_id)
-        .add_attribute("valid", "true"))
+        .add_events(events)
+        .add_submessages(hooks)
+        .add_attribute("action", "batch_issue")
+        .add_attribute("issuer", info.sender.as_str())
+        .add_attribute("accepted", accepted.to_string())
+        .add_attribute("rejected", (entries.len() - accepted).to_string()))
 }
 
-fn execute_revoke(
+fn execute_anchor_root(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    credential_id: String,
-    reason: String,
+    root: String,
 ) -> Result<Response, ContractError> {
-    let mut cred = CREDENTIALS.may_load(deps.storage, &credential_id)?.ok_or(
-        ContractError::CredentialNotFound {
-            id: credential_id.clone(),
-        },
-    )?;
-
-    // Only the original issuer or admin can revoke
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != cred.issuer && info.sender != admin {
-        return Err(ContractError::Unauthorized {
-            reason: "Only issuer or admin can revoke".to_string(),
-        });
+    ensure_active_issuer(deps.storage, &info.sender)?;
+    let root = hex::encode(decode_hash(&root).ok_or_else(|| {
+        ContractError::Std(StdError::generic_err(
+            "Issuance root must be 32 hex-encoded bytes",
+        ))
+    })?);
+    if ISSUANCE_ROOTS.has(deps.storage, &root) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Issuance root already anchored",
+        )));
     }
 
-    cred.revoked = true;
-    CREDENTIALS.save(deps.storage, &credential_id, &cred)?;
+    ISSUANCE_ROOTS.save(
+        deps.storage,
+        &root,
+        &StoredIssuanceRoot {
+            issuer: info.sender.clone(),
+            anchored_at: env.block.time.seconds(),
+            claimed: 0,
+        },
+    )?;
 
     Ok(Response::new()
-        .add_attribute("action", "revoke_credential")
-        .add_attribute("credential_id", credential_id)
-        .add_attribute("reason", reason))
+        .add_attribute("action", "anchor_issuance_root")
+        .add_attribute("root", root)
+        .add_attribute("issuer", info.sender.as_str()))
 }
 
-fn execute_add_issuer(
+fn execute_claim_anchored(
     deps: DepsMut,
-    info: MessageInfo,
-    issuer: String,
+    env: Env,
+    root: String,
+    entry: IssuanceEntry,
+    position: u64,
+    siblings: Vec<String>,
 ) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::Unauthorized {
-            reason: "Only admin can add issuers".to_string(),
+    let root = root.to_lowercase();
+    let mut anchored = ISSUANCE_ROOTS
+        .may_load(deps.storage, &root)?
+        .ok_or_else(|| StdError::not_found("Issuance root"))?;
+
+    let siblings = decode_siblings(&siblings)?;
+    let domain = groth16::canonical_field::<Fr>(&entry.nullifier.domain)?;
+    let value = groth16::canonical_field::<Fr>(&entry.nullifier.value)?;
+    let attributes_root = entry
+        .attributes_root
+        .as_deref()
+        .map(groth16::canonical_field::<Fr>)
+        .transpose()?
+        .unwrap_or_default();
+    let leaf = merkle::issuance_leaf(
+        &entry.holder,
+        &entry.credential_type.to_string(),
+        &entry.commitment,
+        entry.expires_at,
+        &domain,
+        &value,
+        &attributes_root,
+    );
+    if hex::encode(merkle::fold(leaf, position, &siblings)) != root {
+        return Err(ContractError::InvalidProof {
+            reason: "Entry is not included under the issuance root".to_string(),
         });
     }
 
-    let issuer_addr = deps.api.addr_validate(&issuer)?;
-    ISSUERS.save(deps.storage, &issuer_addr, &true)?;
+    let (cred_id, status_index) =
+        issue_entry(deps.storage, deps.api, &env, &anchored.issuer, &entry)?;
+    anchored.claimed += 1;
+    ISSUANCE_ROOTS.save(deps.storage, &root, &anchored)?;
+    let hooks = hook_msgs(
+        deps.storage,
+        &CredentialHookMsg::Issued {
+            credential_id: cred_id.clone(),
+            holder: entry.holder.clone(),
+            credential_type: entry.credential_type.clone(),
+        },
+    )?;
 
     Ok(Response::new()
-        .add_attribute("action", "add_issuer")
-        .add_attribute("issuer", issuer))
+        .add_submessages(hooks)
+        .add_attribute("action", "claim_anchored_credential")
+        .add_attribute("credential_id", &cred_id)
+        .add_attribute("holder", entry.holder)
+        .add_attribute("type", entry.credential_type.to_string())
+        .add_attribute("issuer", anchored.issuer.as_str())
+        .add_attribute("status_index", status_index.to_string()))
 }
 
-fn execute_remove_issuer(
-    deps: DepsMut,
-    info: MessageInfo,
-    issuer: String,
-) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
-        return Err(ContractError::Unauthorized {
-            reason: "Only admin can remove issuers".to_string(),
+fn decode_hash(hex_str: &str) -> Option<merkle::Hash> {
+    let mut node = [0u8; 32];
+    hex::decode_to_slice(hex_str, &mut node).ok().map(|_| node)
+}
+
+fn decode_siblings(siblings: &[String]) -> Result<Vec<merkle::Hash>, ContractError> {
+    siblings
+        .iter()
+        .map(|s| decode_hash(s))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| ContractError::InvalidProof {
+            reason: "Malformed Merkle sibling".to_string(),
+        })
+}
+
+/// Check that `issuer` may issue `credential_type` now and return the
+/// expiry to use, applying the custom schema's default validity.
+fn authorize_issuance(
+    storage: &mut dyn Storage,
+    env: &Env,
+    issuer: &Addr,
+    credential_type: &CredentialType,
+    expires_at: u64,
+) -> Result<u64, ContractError> {
+    ensure_active_issuer(storage, issuer)?;
+    if expires_at != 0 && expires_at <= env.block.time.seconds() {
+        return Err(ContractError::CredentialExpired);
+    }
+    let mut expires_at = expires_at;
+    if let CredentialType::Custom(name) = credential_type {
+        let schema = load_schema(storage, name)?;
+        if !schema.allowed_issuers.is_empty() && !schema.allowed_issuers.contains(issuer) {
+            return Err(ContractError::IssuerNotAuthorized {
+                issuer: issuer.to_string(),
+            });
+        }
+        if expires_at == 0 && schema.default_validity_secs > 0 {
+            expires_at = env.block.time.seconds() + schema.default_validity_secs;
+        }
+    }
+    check_issuer_scope(
+        storage,
+        issuer,
+        credential_type,
+        expires_at,
+        env.block.time.seconds(),
+    )?;
+    Ok(expires_at)
+}
+
+/// Only trusted, active issuers can issue credentials.
+fn ensure_active_issuer(storage: &dyn Storage, issuer: &Addr) -> Result<(), ContractError> {
+    let is_issuer = ISSUERS.may_load(storage, issuer)?.unwrap_or(false);
+    let active =
+        ISSUER_STATUS.may_load(storage, issuer)?.unwrap_or_default() == IssuerStatus::Active;
+    if !is_issuer || !active {
+        return Err(ContractError::IssuerNotAuthorized {
+            issuer: issuer.to_string(),
         });
     }
+    Ok(())
+}
 
-    let issuer_addr = deps.api.addr_validate(&issuer)?;
-    ISSUERS.remove(deps.storage, &issuer_addr);
+/// Deterministic id of a new credential, whether an issuer or the verification
+/// flow issues it; errors if already taken.
+pub(crate) fn issuer_credential_id(
+    storage: &dyn Storage,
+    env: &Env,
+    holder: &str,
+    credential_type: &CredentialType,
+    commitment: &str,
+) -> Result<String, ContractError> {
+    let mut hasher = Sha256::new();
+    hasher.update(holder.as_bytes());
+    hasher.update(credential_type.to_string().as_bytes());
+    hasher.update(commitment.as_bytes());
+    hasher.update(env.block.time.seconds().to_le_bytes());
+    let cred_id = hex::encode(hasher.finalize())[..32].to_string();
 
-    Ok(Response::new()
-        .add_attribute("action", "remove_issuer")
-        .add_attribute("issuer", issuer))
+    if CREDENTIALS.has(storage, &cred_id) {
+        return Err(ContractError::CredentialAlreadyExists { id: cred_id });
+    }
+    Ok(cred_id)
 }
 
-fn execute_transfer_admin(
+#[allow(clippy::too_many_arguments)]
+fn execute_renew(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    new_admin: String,
+    credential_id: String,
+    commitment: String,
+    expires_at: u64,
+    attributes_root: Option<String>,
 ) -> Result<Response, ContractError> {
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
+    let old = CREDENTIALS.may_load(deps.storage, &credential_id)?.ok_or(
+        ContractError::CredentialNotFound {
+            id: credential_id.clone(),
+        },
+    )?;
+    if info.sender != old.issuer {
         return Err(ContractError::Unauthorized {
-            reason: "Only admin can transfer admin role".to_string(),
+            reason: "Only the original issuer can renew a credential".to_string(),
         });
     }
+    if old.revoked || issued_while_compromised(deps.storage, &old)? {
+        return Err(ContractError::CredentialRevoked);
+    }
+    let mut old_lineage = load_lineage(deps.storage, &credential_id)?;
+    if let Some(successor) = old_lineage.superseded_by {
+        return Err(ContractError::CredentialSuperseded { successor });
+    }
 
-    let new_admin_addr = deps.api.addr_validate(&new_admin)?;
-    ADMIN.save(deps.storage, &new_admin_addr)?;
+    // Renewal is a fresh issuance as far as the issuer's permissions go
+    ensure_status_capacity(deps.storage)?;
+    let expires_at = authorize_issuance(
+        deps.storage,
+        &env,
+        &info.sender,
+        &old.credential_type,
+        expires_at,
+    )?;
+    let cred_id = issuer_credential_id(
+        deps.storage,
+        &env,
+        old.holder.as_str(),
+        &old.credential_type,
+        &commitment,
+    )?;
+    let now = env.block.time.seconds();
+
+    let credential = Credential {
+        id: cred_id.clone(),
+        holder: old.holder.clone(),
+        credential_type: old.credential_type.clone(),
+        commitment,
+        issuer: info.sender.clone(),
+        issued_at: now,
+        expires_at,
+        revoked: false,
+    };
+    CREDENTIALS.save(deps.storage, &cred_id, &credential)?;
+    HOLDER_CREDENTIALS.save(deps.storage, (&old.holder, &cred_id), &true)?;
+    ISSUER_CREDENTIALS.save(deps.storage, (&info.sender, &cred_id), &true)?;
+    index_expiry(deps.storage, &credential)?;
+    if counted_as_active(deps.storage, &credential)? {
+        count_active(deps.storage, &credential)?;
+    }
+    if let Some(root) = attributes_root {
+        let root = groth16::canonical_field::<Fr>(&root)?;
+        CREDENTIAL_ATTRIBUTES.save(deps.storage, &cred_id, &root)?;
+    }
+    let status_index = assign_status_index(deps.storage, &cred_id)?;
+    let count = CREDENTIAL_COUNT.load(deps.storage)?;
+    CREDENTIAL_COUNT.save(deps.storage, &(count + 1))?;
+
+    // The identity behind the credential is unchanged, so its nullifier moves over
+    if let Some(n) = CREDENTIAL_NULLIFIERS.may_load(deps.storage, &credential_id)? {
+        CREDENTIAL_NULLIFIERS.remove(deps.storage, &credential_id);
+        CREDENTIAL_NULLIFIERS.save(deps.storage, &cred_id, &n)?;
+        NULLIFIERS.update(deps.storage, (&n.domain, &n.value), |r| {
+            r.map(|mut r| {
+                r.credential_id = cred_id.clone();
+                r
+            })
+            .ok_or_else(|| StdError::not_found("nullifier"))
+        })?;
+    }
+
+    if counted_as_active(deps.storage, &old)? {
+        uncount_active(deps.storage, &old)?;
+    }
+    CREDENTIAL_LINEAGE.save(
+        deps.storage,
+        &cred_id,
+        &StoredLineage {
+            lineage_id: old_lineage.lineage_id.clone(),
+            previous_id: Some(credential_id.clone()),
+            superseded_by: None,
+        },
+    )?;
+    old_lineage.superseded_by = Some(cred_id.clone());
+    CREDENTIAL_LINEAGE.save(deps.storage, &credential_id, &old_lineage)?;
+    CREDENTIAL_EXPIRY.remove(deps.storage, (old.expires_at, &credential_id));
+    // Proofs of non-revocation must stop working for the superseded credential
+    flag_in_status_list(deps.storage, &credential_id, now)?;
+    let hooks = hook_msgs(
+        deps.storage,
+        &CredentialHookMsg::Issued {
+            credential_id: cred_id.clone(),
+            holder: old.holder.to_string(),
+            credential_type: old.credential_type.clone(),
+        },
+    )?;
 
     Ok(Response::new()
-        .add_attribute("action", "transfer_admin")
-        .add_attribute("new_admin", new_admin))
+        .add_submessages(hooks)
+        .add_attribute("action", "renew_credential")
+        .add_attribute("credential_id", &cred_id)
+        .add_attribute("previous_id", credential_id)
+        .add_attribute("lineage_id", old_lineage.lineage_id)
+        .add_attribute("status_index", status_index.to_string()))
 }
 
-// ── Query ───────────────────────────────────────────────────────────
+/// Add a credential to the expiry index if it expires at all.
+fn index_expiry(storage: &mut dyn Storage, cred: &Credential) -> StdResult<()> {
+    if cred.expires_at > 0 {
+        CREDENTIAL_EXPIRY.save(storage, (cred.expires_at, &cred.id), &true)?;
+    }
+    Ok(())
+}
 
-#[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetCredential { credential_id } => {
-            to_json_binary(&query_credential(deps, credential_id)?)
+/// Count a credential towards its holder, counting the holder on their first one.
+pub(crate) fn count_active(storage: &mut dyn Storage, cred: &Credential) -> StdResult<()> {
+    let type_str = cred.credential_type.to_string();
+    let key = (type_str.as_str(), &cred.holder);
+    let held = ACTIVE_HOLDER_CREDENTIALS
+        .may_load(storage, key)?
+        .unwrap_or(0);
+    ACTIVE_HOLDER_CREDENTIALS.save(storage, key, &(held + 1))?;
+    if held == 0 {
+        ACTIVE_CREDENTIALS.update(storage, &type_str, |c| {
+            Ok::<_, StdError>(c.unwrap_or(0) + 1)
+        })?;
+    }
+    Ok(())
+}
+
+/// Stop counting a credential, dropping its holder once none of theirs is left.
+pub(crate) fn uncount_active(storage: &mut dyn Storage, cred: &Credential) -> StdResult<()> {
+    let type_str = cred.credential_type.to_string();
+    let key = (type_str.as_str(), &cred.holder);
+    match ACTIVE_HOLDER_CREDENTIALS
+        .may_load(storage, key)?
+        .unwrap_or(0)
+    {
+        0 => {}
+        1 => {
+            ACTIVE_HOLDER_CREDENTIALS.remove(storage, key);
+            ACTIVE_CREDENTIALS.update(storage, &type_str, |c| {
+                Ok::<_, StdError>(c.unwrap_or(0).saturating_sub(1))
+            })?;
         }
-        QueryMsg::HasValidCredential {
-            holder,
-            credential_type,
-        } => to_json_binary(&query_has_valid(deps, env, holder, credential_type)?),
-        QueryMsg::ListCredentials {
-            holder,
-            start_after,
-            limit,
-        } => to_json_binary(&query_list(deps, holder, start_after, limit)?),
-        QueryMsg::ListIssuers {} => to_json_binary(&query_issuers(deps)?),
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::GetRecovery { recovery_id } => {
-            to_json_binary(&query_recovery(deps, recovery_id)?)
+        held => ACTIVE_HOLDER_CREDENTIALS.save(storage, key, &(held - 1))?,
+    }
+    Ok(())
+}
+
+/// Whether a credential is still in `ACTIVE_CREDENTIALS`: it leaves on
+/// revocation, on renewal, when its issuer key is marked compromised, or when
+/// the expiry sweep passes it.
+pub(crate) fn counted_as_active(storage: &dyn Storage, cred: &Credential) -> StdResult<bool> {
+    if cred.revoked || superseded_by(storage, &cred.id)?.is_some() {
+        return Ok(false);
+    }
+    Ok(!SWEPT_CREDENTIALS.has(storage, &cred.id) && !issued_while_compromised(storage, cred)?)
+}
+
+fn load_lineage(storage: &dyn Storage, credential_id: &str) -> StdResult<StoredLineage> {
+    Ok(CREDENTIAL_LINEAGE
+        .may_load(storage, credential_id)?
+        .unwrap_or_else(|| StoredLineage {
+            lineage_id: credential_id.to_string(),
+            previous_id: None,
+            superseded_by: None,
+        }))
+}
+
+fn superseded_by(storage: &dyn Storage, credential_id: &str) -> StdResult<Option<String>> {
+    Ok(CREDENTIAL_LINEAGE
+        .may_load(storage, credential_id)?
+        .and_then(|l| l.superseded_by))
+}
+
+/// Fail if the revocation status list has no bit left for a new credential.
+pub(crate) fn ensure_status_capacity(storage: &dyn Storage) -> StdResult<()> {
+    if NEXT_STATUS_INDEX.load(storage)? >= status_list::CAPACITY {
+        return Err(StdError::generic_err("Revocation status list is full"));
+    }
+    Ok(())
+}
+
+/// Give a new credential the next free bit in the revocation status list.
+pub(crate) fn assign_status_index(
+    storage: &mut dyn Storage,
+    credential_id: &str,
+) -> StdResult<u64> {
+    ensure_status_capacity(storage)?;
+    let index = NEXT_STATUS_INDEX.load(storage)?;
+    NEXT_STATUS_INDEX.save(storage, &(index + 1))?;
+    CREDENTIAL_STATUS_INDEX.save(storage, credential_id, &index)?;
+    Ok(index)
+}
+
+/// Publish the current status list root as a new epoch.
+fn save_revocation_root(storage: &mut dyn Storage, epoch: u64, now: u64) -> StdResult<()> {
+    let root = status_list::root(storage)?;
+    REVOCATION_EPOCH.save(storage, &epoch)?;
+    REVOCATION_ROOTS.save(
+        storage,
+        epoch,
+        &RevocationRootRecord {
+            root: hex::encode(root),
+            updated_at: now,
+        },
+    )
+}
+
+/// Enforce a scoped issuer's credential types, validity cap and issuance rate,
+/// counting this issuance towards the rate limit.
+fn check_issuer_scope(
+    storage: &mut dyn Storage,
+    issuer: &Addr,
+    credential_type: &CredentialType,
+    expires_at: u64,
+    now: u64,
+) -> Result<(), ContractError> {
+    let Some(scope) = ISSUER_SCOPES.may_load(storage, issuer)? else {
+        return Ok(());
+    };
+
+    if !scope.credential_types.contains(credential_type) {
+        return Err(ContractError::Unauthorized {
+            reason: format!(
+                "Issuer is not authorized for {} credentials",
+                credential_type
+            ),
+        });
+    }
+
+    if let Some(max_validity) = scope.max_validity_secs {
+        if expires_at == 0 || expires_at > now + max_validity {
+            return Err(ContractError::Unauthorized {
+                reason: format!(
+                    "Issuer may grant at most {} seconds of validity",
+                    max_validity
+                ),
+            });
+        }
+    }
+
+    if let Some(limit) = scope.issuance_limit {
+        let mut window = ISSUER_USAGE.may_load(storage, issuer)?.unwrap_or_default();
+        if now >= window.period_start + limit.period_secs {
+            window.period_start = now;
+            window.issued_in_period = 0;
         }
+        if window.issued_in_period >= limit.max_per_period {
+            return Err(ContractError::Unauthorized {
+                reason: "Issuance limit for this period reached".to_string(),
+            });
+        }
+        window.issued_in_period += 1;
+        ISSUER_USAGE.save(storage, issuer, &window)?;
     }
+
+    Ok(())
 }
 
-fn query_credential(deps: Deps, credential_id: String) -> StdResult<CredentialResponse> {
-    let cred = CREDENTIALS.load(deps.storage, &credential_id)?;
-    Ok(CredentialResponse {
-        id: cred.id,
-        holder: cred.holder.to_string(),
-        credential_type: cred.credential_type,
-        commitment: cred.commitment,
-        issuer: cred.issuer.to_string(),
-        issued_at: cred.issued_at,
-        expires_at: cred.expires_at,
-        revoked: cred.revoked,
-    })
+/// Record an identity nullifier as spent by a newly issued credential.
+/// One identity can hold one credential per nullifier domain.
+pub(crate) fn spend_nullifier(
+    storage: &mut dyn Storage,
+    env: &Env,
+    nullifier: &Nullifier,
+    holder: &Addr,
+    credential_id: &str,
+) -> Result<(), ContractError> {
+    let Nullifier { domain, value } = check_nullifier_unspent(storage, nullifier)?;
+
+    NULLIFIERS.save(
+        storage,
+        (&domain, &value),
+        &StoredNullifier {
+            holder: holder.clone(),
+            credential_id: credential_id.to_string(),
+            spent_at: env.block.time.seconds(),
+        },
+    )?;
+    CREDENTIAL_NULLIFIERS.save(storage, credential_id, &Nullifier { domain, value })?;
+    Ok(())
 }
 
-fn query_has_valid(
-    deps: Deps,
+/// Canonical form of a nullifier, provided it has not been spent.
+fn check_nullifier_unspent(
+    storage: &dyn Storage,
+    nullifier: &Nullifier,
+) -> Result<Nullifier, ContractError> {
+    let domain = groth16::canonical_field::<Fr>(&nullifier.domain)?;
+    let value = groth16::canonical_field::<Fr>(&nullifier.value)?;
+
+    if NULLIFIERS.has(storage, (&domain, &value)) {
+        return Err(ContractError::NullifierAlreadyUsed { domain });
+    }
+    Ok(Nullifier { domain, value })
+}
+
+fn execute_verify(
+    deps: DepsMut,
     env: Env,
-    holder: String,
-    credential_type: CredentialType,
-) -> StdResult<HasCredentialResponse> {
-    let holder_addr = deps.api.addr_validate(&holder)?;
-    let type_str = credential_type.to_string();
-    let now = env.block.time.seconds();
+    _info: MessageInfo,
+    credential_id: String,
+    proof: ZkProof,
+    min_age_seconds: Option<u64>,
+) -> Result<Response, ContractError> {
+    let cred = CREDENTIALS.may_load(deps.storage, &credential_id)?.ok_or(
+        ContractError::CredentialNotFound {
+            id: credential_id.clone(),
+        },
+    )?;
 
-    // Iterate over holder's credentials to find a valid one of the requested type
-    let creds: Vec<_> = HOLDER_CREDENTIALS
-        .prefix(&holder_addr)
-        .range(deps.storage, None, None, Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?;
+    ensure_usable(deps.storage, &cred, env.block.time.seconds())?;
 
-    for (cred_id, _) in creds {
-        if let Ok(cred) = CREDENTIALS.load(deps.storage, &cred_id) {
-            if cred.credential_type.to_string() == type_str
-                && !cred.revoked
-                && (cred.expires_at == 0 || cred.expires_at > now)
-            {
-                return Ok(HasCredentialResponse {
-                    has_credential: true,
-                    credential_id: Some(cred.id),
+    // ── Groth16 proof verification ──
+    // The proof names the circuit version it was made for; the key must be
+    // registered under the credential's own type and still be current.
+    let type_str = cred.credential_type.to_string();
+    let mut layout = SignalLayout::Citizenship;
+    if let CredentialType::Custom(name) = &cred.credential_type {
+        let schema = load_schema(deps.storage, name)?;
+        if let Some(version) = schema.verification_key {
+            if version != proof.vk_reference {
+                return Err(ContractError::InvalidProof {
+                    reason: format!("{} proofs must use verification key {}", type_str, version),
                 });
             }
         }
+        layout = schema.signal_layout;
     }
+    verify_proof(deps.storage, &type_str, &proof)?;
 
-    Ok(HasCredentialResponse {
-        has_credential: false,
-        credential_id: None,
-    })
+    // A valid proof only speaks for this credential if its public signals match it
+    check_credential_signals(
+        &cred,
+        &layout,
+        &proof.public_inputs,
+        env.block.time.seconds(),
+        min_age_seconds,
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "verify_credential")
+        .add_attribute("credential_id", credential_id)
+        .add_attribute("vk_reference", proof.vk_reference.as_str());
+    if layout == SignalLayout::Citizenship {
+        res = res.add_attribute("min_age_seconds", &proof.public_inputs[SIGNAL_MIN_AGE]);
+    }
+    Ok(res.add_attribute("valid", "true"))
 }
 
-fn query_list(
-    deps: Deps,
-    holder: String,
-    _start_after: Option<String>,
-    limit: Option<u32>,
-) -> StdResult<CredentialsListResponse> {
-    let holder_addr = deps.api.addr_validate(&holder)?;
-    let limit = limit.unwrap_or(30).min(100) as usize;
+/// Reject credentials that are revoked, superseded or expired.
+fn ensure_usable(storage: &dyn Storage, cred: &Credential, now: u64) -> Result<(), ContractError> {
+    if cred.revoked || issued_while_compromised(storage, cred)? {
+        return Err(ContractError::CredentialRevoked);
+    }
+    if let Some(successor) = superseded_by(storage, &cred.id)? {
+        return Err(ContractError::CredentialSuperseded { successor });
+    }
+    if cred.expires_at > 0 && now > cred.expires_at {
+        return Err(ContractError::CredentialExpired);
+    }
+    Ok(())
+}
 
-    let creds: Vec<_> = HOLDER_CREDENTIALS
-        .prefix(&holder_addr)
-        .range(deps.storage, None, None, Order::Ascending)
-        .take(limit)
-        .filter_map(|r| r.ok())
-        .filter_map(|(cred_id, _)| {
-            CREDENTIALS
-                .load(deps.storage, &cred_id)
-                .ok()
-                .map(|c| CredentialResponse {
-                    id: c.id,
-                    holder: c.holder.to_string(),
-                    credential_type: c.credential_type,
-                    commitment: c.commitment,
-                    issuer: c.issuer.to_string(),
-                    issued_at: c.issued_at,
-                    expires_at: c.expires_at,
-                    revoked: c.revoked,
-                })
-        })
-        .collect();
+/// Check a Groth16 proof against the current key it names under `type_str`.
+fn verify_proof(
+    storage: &dyn Storage,
+    type_str: &str,
+    proof: &ZkProof,
+) -> Result<(), ContractError> {
+    let reference = format!("{}/{}", type_str, proof.vk_reference);
+    let stored = VERIFICATION_KEYS
+        .may_load(storage, (type_str, &proof.vk_reference))?
+        .ok_or(ContractError::VerificationKeyNotFound {
+            reference: reference.clone(),
+        })?;
+    if stored.deprecated {
+        return Err(ContractError::VerificationKeyDeprecated { reference });
+    }
+    let groth16_proof: Groth16Proof =
+        from_json(proof.proof_data.as_bytes()).map_err(|e| ContractError::InvalidProof {
+            reason: format!("Malformed proof data: {}", e),
+        })?;
+    groth16::verify(&stored.key, &groth16_proof, &proof.public_inputs)
+}
 
-    Ok(CredentialsListResponse { credentials: creds })
+/// Verification key namespace of the predicate circuit for `attribute op _`.
+fn predicate_key_type(attribute: &str, op: &PredicateOp) -> String {
+    format!("predicate:{}:{}", attribute, op)
 }
 
-fn query_issuers(deps: Deps) -> StdResult<IssuersResponse> {
-    let issuers: Vec<String> = ISSUERS
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|r| r.ok())
-        .map(|(addr, _)| addr.to_string())
-        .collect();
-    Ok(IssuersResponse { issuers })
+/// Public signals of predicate circuits
+const PREDICATE_SIGNALS: usize = 4;
+const PREDICATE_VALID: usize = 0;
+const PREDICATE_ROOT: usize = 1;
+const PREDICATE_CURRENT_TIME: usize = 2;
+const PREDICATE_VALUE: usize = 3;
+
+/// Check each disclosure against the credential's attribute root and
+/// return the claims they establish.
+fn check_disclosures(
+    storage: &dyn Storage,
+    cred: &Credential,
+    disclosures: &[Disclosure],
+    now: u64,
+) -> Result<Vec<AttributeClaim>, ContractError> {
+    ensure_usable(storage, cred, now)?;
+    let root =
+        CREDENTIAL_ATTRIBUTES
+            .may_load(storage, &cred.id)?
+            .ok_or(ContractError::InvalidProof {
+                reason: "Credential does not commit to attributes".to_string(),
+            })?;
+    if disclosures.is_empty() {
+        return Err(ContractError::InvalidProof {
+            reason: "No disclosures given".to_string(),
+        });
+    }
+
+    let mut claims = Vec::with_capacity(disclosures.len());
+    for disclosure in disclosures {
+        match disclosure {
+            Disclosure::Reveal {
+                attribute,
+                value,
+                salt,
+                position,
+                siblings,
+            } => {
+                let siblings = decode_siblings(siblings)?;
+                let leaf = merkle::attribute_leaf(attribute, value, salt);
+                if merkle::to_field(&merkle::fold(leaf, *position, &siblings)) != root {
+                    return Err(ContractError::InvalidProof {
+                        reason: format!("Attribute {} does not open the commitment", attribute),
+                    });
+                }
+                claims.push(AttributeClaim {
+                    attribute: attribute.clone(),
+                    op: PredicateOp::Eq,
+                    value: value.clone(),
+                });
+            }
+            Disclosure::Predicate { claim, proof } => {
+                verify_proof(
+                    storage,
+                    &predicate_key_type(&claim.attribute, &claim.op),
+                    proof,
+                )?;
+                check_predicate_signals(&root, &claim.value, &proof.public_inputs, now)?;
+                claims.push(claim.clone());
+            }
+        }
+    }
+    Ok(claims)
 }
 
-fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
-    let admin = ADMIN.load(deps.storage)?;
-    let count = CREDENTIAL_COUNT.load(deps.storage)?;
-    Ok(ConfigResponse {
-        admin: admin.to_string(),
-        total_credentials: count,
-    })
+fn check_predicate_signals(
+    root: &str,
+    value: &str,
+    signals: &[String],
+    now: u64,
+) -> Result<(), ContractError> {
+    if signals.len() != PREDICATE_SIGNALS {
+        return Err(ContractError::InvalidProof {
+            reason: format!(
+                "Expected {} predicate signals, got {}",
+                PREDICATE_SIGNALS,
+                signals.len()
+            ),
+        });
+    }
+    if groth16::parse_field::<Fr>(&signals[PREDICATE_VALID])? != Fr::from(1u64) {
+        return Err(ContractError::InvalidProof {
+            reason: "Circuit output is not valid".to_string(),
+        });
+    }
+    if groth16::canonical_field::<Fr>(&signals[PREDICATE_ROOT])? != root {
+        return Err(ContractError::InvalidProof {
+            reason: "Proof attribute root mismatch".to_string(),
+        });
+    }
+    if groth16::canonical_field::<Fr>(&signals[PREDICATE_VALUE])?
+        != groth16::canonical_field::<Fr>(value)?
+    {
+        return Err(ContractError::InvalidProof {
+            reason: "Proof does not match the claimed value".to_string(),
+        });
+    }
+    let proven_at: u64 =
+        signals[PREDICATE_CURRENT_TIME]
+            .parse()
+            .map_err(|_| ContractError::InvalidProof {
+                reason: "Invalid currentTime signal".to_string(),
+            })?;
+    if proven_at > now {
+        return Err(ContractError::InvalidProof {
+            reason: "Proof currentTime is in the future".to_string(),
+        });
+    }
+    Ok(())
 }
 
-fn query_recovery(deps: Deps, recovery_id: String) -> StdResult<RecoveryResponse> {
-    let r = RECOVERIES.load(deps.storage, &recovery_id)?;
-    Ok(RecoveryResponse {
-        recovery_id: r.recovery_id,
-        old_address: r.old_address.to_string(),
-        new_address: r.new_address.to_string(),
-        status: format!("{:?}", r.status),
-        requested_at: r.requested_at,
-        execute_after: r.execute_after,
-    })
+/// Bind the public signals of a verified credential proof to a credential.
+fn check_credential_signals(
+    cred: &Credential,
+    layout: &SignalLayout,
+    signals: &[String],
+    now: u64,
+    min_age_seconds: Option<u64>,
+) -> Result<(), ContractError> {
+    let expected = match layout {
+        SignalLayout::Citizenship => signals.len() == CITIZENSHIP_SIGNALS,
+        SignalLayout::Commitment => signals.len() > SIGNAL_COMMITMENT,
+    };
+    if !expected {
+        return Err(ContractError::InvalidProof {
+            reason: format!(
+                "Unexpected {} public signals for {:?} layout",
+                signals.len(),
+                layout
+            ),
+        });
+    }
+
+    if groth16::parse_field::<Fr>(&signals[SIGNAL_VALID])? != Fr::from(1u64) {
+        return Err(ContractError::InvalidProof {
+            reason: "Circuit output is not valid".to_string(),
+        });
+    }
+
+    let commitment =
+        groth16::parse_field::<Fr>(&cred.commitment).map_err(|_| ContractError::InvalidProof {
+            reason: "Credential commitment is not a field element".to_string(),
+        })?;
+    if groth16::parse_field::<Fr>(&signals[SIGNAL_COMMITMENT])? != commitment {
+        return Err(ContractError::InvalidProof {
+            reason: "Proof commitment mismatch".to_string(),
+        });
+    }
+    if *layout == SignalLayout::Commitment {
+        if min_age_seconds.is_some() {
+            return Err(ContractError::InvalidProof {
+                reason: "Proofs of this type carry no minAge signal".to_string(),
+            });
+        }
+        return Ok(());
+    }
+
+    // The age check is relative to currentTime, so it must be recent and not
+    // in the future
+    let proven_at = parse_signal(&signals[SIGNAL_CURRENT_TIME], "currentTime")?;
+    if proven_at > now {
+        return Err(ContractError::InvalidProof {
+            reason: "Proof currentTime is in the future".to_string(),
+        });
+    }
+    if now - proven_at > MAX_PROOF_AGE {
+        return Err(ContractError::InvalidProof {
+            reason: format!(
+                "Proof currentTime is more than {} seconds old",
+                MAX_PROOF_AGE
+            ),
+        });
+    }
+
+    let proven_age = parse_signal(&signals[SIGNAL_MIN_AGE], "minAge")?;
+    if let Some(required) = min_age_seconds {
+        if proven_age < required {
+            return Err(ContractError::InvalidProof {
+                reason: format!(
+                    "Proof minAge {} is below the required {}",
+                    proven_age, required
+                ),
+            });
+        }
+    }
+
+    Ok(())
 }
 
-// ── Verification Relay ──────────────────────────────────────────────
+/// Read a public signal that carries seconds.
+fn parse_signal(signal: &str, name: &str) -> Result<u64, ContractError> {
+    signal.parse().map_err(|_| ContractError::InvalidProof {
+        reason: format!("Invalid {} signal", name),
+    })
+}
 
-#[allow(clippy::too_many_arguments)]
-fn execute_submit_verification(
+fn execute_revoke(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    request_id: String,
-    applicant: String,
-    credential_type: CredentialType,
-    commitment: String,
-    approved: bool,
+    credential_id: String,
+    reason: String,
+    fraud: bool,
 ) -> Result<Response, ContractError> {
-    // Only admin or authorized relayers can submit verification results
+    let mut cred = CREDENTIALS.may_load(deps.storage, &credential_id)?.ok_or(
+        ContractError::CredentialNotFound {
+            id: credential_id.clone(),
+        },
+    )?;
+
+    // Only the original issuer or admin can revoke
     let admin = ADMIN.load(deps.storage)?;
-    let is_relayer = AUTHORIZED_RELAYERS
-        .may_load(deps.storage, &info.sender)?
-        .unwrap_or(false);
-    if info.sender != admin && !is_relayer {
+    if info.sender != cred.issuer && info.sender != admin {
         return Err(ContractError::Unauthorized {
-            reason: "Only admin or authorized relayer can submit verification results".to_string(),
+            reason: "Only issuer or admin can revoke".to_string(),
+        });
+    }
+
+    // Revoking again must not bump the revocation epoch or re-send hooks
+    if cred.revoked {
+        return Ok(Response::new()
+            .add_attribute("action", "revoke_credential")
+            .add_attribute("credential_id", credential_id)
+            .add_attribute("already_revoked", "true"));
+    }
+
+    mark_revoked(deps.storage, &mut cred, env.block.time.seconds())?;
+
+    // Citizens who vouched for a fraudulent identity lose the right to vouch for a while
+    let mut penalized = 0usize;
+    if fraud {
+        let vouchers = CREDENTIAL_VOUCHERS
+            .may_load(deps.storage, &credential_id)?
+            .unwrap_or_default();
+        let cooldown = VOUCHING_CONFIG.load(deps.storage)?.fraud_cooldown_secs;
+        for voucher in &vouchers {
+            let mut record = VOUCHERS
+                .may_load(deps.storage, voucher)?
+                .unwrap_or_default();
+            record.cooldown_until = env.block.time.seconds() + cooldown;
+            record.fraud_count += 1;
+            VOUCHERS.save(deps.storage, voucher, &record)?;
+        }
+        penalized = vouchers.len();
+    }
+    let hooks = hook_msgs(deps.storage, &revoked_hook(&cred))?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "revoke_credential")
+        .add_attribute("credential_id", credential_id)
+        .add_attribute("reason", reason)
+        .add_attribute("fraud", fraud.to_string())
+        .add_attribute("vouchers_penalized", penalized.to_string()))
+}
+
+fn execute_add_type_implication(
+    deps: DepsMut,
+    info: MessageInfo,
+    implication: TypeImplication,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can manage type implications".to_string(),
+        });
+    }
+
+    let from_key = implication_source_key(&implication.from);
+    let to_key = implication.to.to_string();
+    let mut implications: Vec<TypeImplication> = load_type_implications(deps.storage)?
+        .into_iter()
+        .filter(|i| implication_source_key(&i.from) != from_key || i.to != implication.to)
+        .collect();
+    implications.push(implication.clone());
+
+    // With the rule in place, `to` must not lead back to the source: to any
+    // source for a wildcard rule, since every other type then implies `to`
+    let reachable = reachable_types(&implications, &implication.to, |_| Ok(true))?;
+    let cycle = match &implication.from {
+        Some(from) => from == &implication.to || reachable.contains(from),
+        None => reachable.len() > 1,
+    };
+    if cycle {
+        return Err(ContractError::ImplicationCycle {
+            from: from_key,
+            to: to_key,
+        });
+    }
+
+    TYPE_IMPLICATIONS.save(deps.storage, (&from_key, &to_key), &implication)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_type_implication")
+        .add_attribute("from", from_key)
+        .add_attribute("to", to_key)
+        .add_attribute(
+            "requires_attestation",
+            implication.requires_attestation.to_string(),
+        ))
+}
+
+fn execute_remove_type_implication(
+    deps: DepsMut,
+    info: MessageInfo,
+    from: Option<CredentialType>,
+    to: CredentialType,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can manage type implications".to_string(),
+        });
+    }
+
+    let from_key = implication_source_key(&from);
+    let to_key = to.to_string();
+    TYPE_IMPLICATIONS.remove(deps.storage, (&from_key, &to_key));
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_type_implication")
+        .add_attribute("from", from_key)
+        .add_attribute("to", to_key))
+}
+
+/// Storage key of an implication's source; `*` stands for any type.
+fn implication_source_key(from: &Option<CredentialType>) -> String {
+    match from {
+        Some(t) => t.to_string(),
+        None => "*".to_string(),
+    }
+}
+
+fn load_type_implications(storage: &dyn Storage) -> StdResult<Vec<TypeImplication>> {
+    TYPE_IMPLICATIONS
+        .range(storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, i)| i))
+        .collect()
+}
+
+/// Every type reachable from `start`, itself included, through the rules
+/// `follow` accepts.
+fn reachable_types(
+    implications: &[TypeImplication],
+    start: &CredentialType,
+    mut follow: impl FnMut(&TypeImplication) -> StdResult<bool>,
+) -> StdResult<Vec<CredentialType>> {
+    let mut seen = vec![start.clone()];
+    let mut queue = vec![start.clone()];
+    while let Some(current) = queue.pop() {
+        for rule in implications.iter().filter(|i| match &i.from {
+            Some(from) => from == &current,
+            None => i.to != current,
+        }) {
+            if !seen.contains(&rule.to) && follow(rule)? {
+                seen.push(rule.to.clone());
+                queue.push(rule.to.clone());
+            }
+        }
+    }
+    Ok(seen)
+}
+
+/// Whether an issuer is currently trusted to issue `credential_type` itself.
+fn issuer_attests(
+    storage: &dyn Storage,
+    issuer: &Addr,
+    credential_type: &CredentialType,
+) -> StdResult<bool> {
+    if ensure_active_issuer(storage, issuer).is_err() {
+        return Ok(false);
+    }
+    if let Some(scope) = ISSUER_SCOPES.may_load(storage, issuer)? {
+        if !scope.credential_types.contains(credential_type) {
+            return Ok(false);
+        }
+    }
+    if let CredentialType::Custom(name) = credential_type {
+        return Ok(match CREDENTIAL_SCHEMAS.may_load(storage, name)? {
+            Some(schema) => {
+                schema.allowed_issuers.is_empty() || schema.allowed_issuers.contains(issuer)
+            }
+            None => false,
         });
     }
+    Ok(true)
+}
+
+/// Flag a credential as revoked and set its bit in the status list.
+fn mark_revoked(storage: &mut dyn Storage, cred: &mut Credential, now: u64) -> StdResult<()> {
+    if counted_as_active(storage, cred)? {
+        uncount_active(storage, cred)?;
+    }
+    cred.revoked = true;
+    CREDENTIALS.save(storage, &cred.id, cred)?;
+    CREDENTIAL_EXPIRY.remove(storage, (cred.expires_at, &cred.id));
+    flag_in_status_list(storage, &cred.id, now)
+}
+
+fn execute_sweep_expired(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let cursor = EXPIRY_SWEEP_CURSOR.may_load(deps.storage)?;
+    let start = cursor
+        .as_ref()
+        .map(|(expires_at, id)| Bound::exclusive((*expires_at, id.as_str())));
+    // Expired means `expires_at <= now`, i.e. every key below (now + 1, "")
+    let end = Some(Bound::exclusive((now + 1, "")));
+
+    let expired: Vec<(u64, String)> = CREDENTIAL_EXPIRY
+        .keys(deps.storage, start, end, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut events = Vec::with_capacity(expired.len());
+    let mut hooks = vec![];
+    for (expires_at, cred_id) in &expired {
+        let cred = CREDENTIALS.load(deps.storage, cred_id)?;
+        if counted_as_active(deps.storage, &cred)? {
+            uncount_active(deps.storage, &cred)?;
+        }
+        SWEPT_CREDENTIALS.save(deps.storage, cred_id, &true)?;
+        hooks.extend(hook_msgs(
+            deps.storage,
+            &CredentialHookMsg::Expired {
+                credential_id: cred_id.clone(),
+                holder: cred.holder.to_string(),
+                credential_type: cred.credential_type.clone(),
+            },
+        )?);
+        events.push(
+            Event::new("credential_expired")
+                .add_attribute("credential_id", cred_id)
+                .add_attribute("holder", cred.holder.as_str())
+                .add_attribute("credential_type", cred.credential_type.to_string())
+                .add_attribute("expires_at", expires_at.to_string()),
+        );
+    }
+    if let Some(last) = expired.last() {
+        EXPIRY_SWEEP_CURSOR.save(deps.storage, last)?;
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_submessages(hooks)
+        .add_attribute("action", "sweep_expired")
+        .add_attribute("swept", expired.len().to_string()))
+}
+
+/// Set a credential's status list bit, publishing the new root under the next epoch.
+fn flag_in_status_list(storage: &mut dyn Storage, credential_id: &str, now: u64) -> StdResult<()> {
+    if let Some(index) = CREDENTIAL_STATUS_INDEX.may_load(storage, credential_id)? {
+        status_list::set_revoked(storage, index)?;
+        let epoch = REVOCATION_EPOCH.load(storage)? + 1;
+        save_revocation_root(storage, epoch, now)?;
+    }
+    Ok(())
+}
+
+fn execute_set_issuer_status(
+    deps: DepsMut,
+    info: MessageInfo,
+    issuer: String,
+    status: IssuerStatus,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can change issuer status".to_string(),
+        });
+    }
+
+    let issuer_addr = deps.api.addr_validate(&issuer)?;
+    // Credentials issued while the key was compromised leave the count, and
+    // come back if the compromise is lifted or moved later
+    let previous = ISSUER_STATUS
+        .may_load(deps.storage, &issuer_addr)?
+        .unwrap_or_default();
+    let recount = matches!(previous, IssuerStatus::Compromised { .. })
+        || matches!(status, IssuerStatus::Compromised { .. });
+    let mut creds = vec![];
+    if recount {
+        let ids: Vec<String> = ISSUER_CREDENTIALS
+            .prefix(&issuer_addr)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for cred_id in &ids {
+            let cred = CREDENTIALS.load(deps.storage, cred_id)?;
+            let counted = counted_as_active(deps.storage, &cred)?;
+            creds.push((cred, counted));
+        }
+    }
+    if status == IssuerStatus::Active {
+        ISSUER_STATUS.remove(deps.storage, &issuer_addr);
+    } else {
+        ISSUER_STATUS.save(deps.storage, &issuer_addr, &status)?;
+    }
+    for (cred, was_counted) in &creds {
+        match (was_counted, counted_as_active(deps.storage, cred)?) {
+            (true, false) => uncount_active(deps.storage, cred)?,
+            (false, true) => count_active(deps.storage, cred)?,
+            _ => {}
+        }
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "set_issuer_status")
+        .add_attribute("issuer", issuer);
+    res = match status {
+        IssuerStatus::Active => res.add_attribute("status", "active"),
+        IssuerStatus::Suspended => res.add_attribute("status", "suspended"),
+        IssuerStatus::Compromised { since } => res
+            .add_attribute("status", "compromised")
+            .add_attribute("compromised_since", since.to_string()),
+    };
+    Ok(res)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_revoke_issuer_credentials(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    issuer: String,
+    issued_since: Option<u64>,
+    reason: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can bulk revoke credentials".to_string(),
+        });
+    }
+
+    let issuer_addr = deps.api.addr_validate(&issuer)?;
+    let since = match issued_since {
+        Some(since) => since,
+        None => match ISSUER_STATUS.may_load(deps.storage, &issuer_addr)? {
+            Some(IssuerStatus::Compromised { since }) => since,
+            _ => 0,
+        },
+    };
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let page: Vec<String> = ISSUER_CREDENTIALS
+        .prefix(&issuer_addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut revoked = 0usize;
+    let mut hooks = vec![];
+    for cred_id in &page {
+        let mut cred = CREDENTIALS.load(deps.storage, cred_id)?;
+        if !cred.revoked && cred.issued_at >= since {
+            mark_revoked(deps.storage, &mut cred, env.block.time.seconds())?;
+            hooks.extend(hook_msgs(deps.storage, &revoked_hook(&cred))?);
+            revoked += 1;
+        }
+    }
+
+    // A short page means the issuer's index is exhausted
+    let next_start_after = if page.len() == limit {
+        page.last().cloned().unwrap_or_default()
+    } else {
+        String::new()
+    };
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "revoke_issuer_credentials")
+        .add_attribute("issuer", issuer)
+        .add_attribute("reason", reason)
+        .add_attribute("revoked", revoked.to_string())
+        .add_attribute("next_start_after", next_start_after))
+}
+
+fn execute_add_issuer(
+    deps: DepsMut,
+    info: MessageInfo,
+    issuer: String,
+    scope: Option<IssuerScope>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can add issuers".to_string(),
+        });
+    }
+
+    let issuer_addr = deps.api.addr_validate(&issuer)?;
+    match &scope {
+        Some(scope) => {
+            validate_issuer_scope(scope)?;
+            ISSUER_SCOPES.save(deps.storage, &issuer_addr, scope)?;
+        }
+        None => ISSUER_SCOPES.remove(deps.storage, &issuer_addr),
+    }
+    ISSUERS.save(deps.storage, &issuer_addr, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_issuer")
+        .add_attribute("issuer", issuer)
+        .add_attribute("scoped", scope.is_some().to_string()))
+}
+
+fn validate_issuer_scope(scope: &IssuerScope) -> Result<(), ContractError> {
+    if scope.credential_types.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Issuer scope must allow at least one credential type",
+        )));
+    }
+    if scope.max_validity_secs == Some(0) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "max_validity_secs must be positive",
+        )));
+    }
+    if let Some(limit) = &scope.issuance_limit {
+        if limit.max_per_period == 0 || limit.period_secs == 0 {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Issuance limit must allow at least one credential per non-empty period",
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn execute_remove_issuer(
+    deps: DepsMut,
+    info: MessageInfo,
+    issuer: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can remove issuers".to_string(),
+        });
+    }
+
+    let issuer_addr = deps.api.addr_validate(&issuer)?;
+    ISSUERS.remove(deps.storage, &issuer_addr);
+    ISSUER_SCOPES.remove(deps.storage, &issuer_addr);
+    ISSUER_USAGE.remove(deps.storage, &issuer_addr);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_issuer")
+        .add_attribute("issuer", issuer))
+}
+
+fn execute_transfer_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_admin: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can transfer admin role".to_string(),
+        });
+    }
+
+    let new_admin_addr = deps.api.addr_validate(&new_admin)?;
+    ADMIN.save(deps.storage, &new_admin_addr)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_admin")
+        .add_attribute("new_admin", new_admin))
+}
+
+// ── Verification Keys ───────────────────────────────────────────────
+
+fn execute_register_vk(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    type_str: String,
+    version: String,
+    verification_key: Groth16VerificationKey,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can register verification keys".to_string(),
+        });
+    }
+
+    // Keys are immutable once registered: a new circuit gets a new version
+    if VERIFICATION_KEYS.has(deps.storage, (&type_str, &version)) {
+        return Err(ContractError::VerificationKeyAlreadyExists {
+            reference: format!("{}/{}", type_str, version),
+        });
+    }
+
+    // Reject malformed keys up front rather than on the first proof
+    PreparedVerificationKey::parse(&verification_key)?;
+
+    VERIFICATION_KEYS.save(
+        deps.storage,
+        (&type_str, &version),
+        &StoredVerificationKey {
+            key: verification_key,
+            deprecated: false,
+            registered_at: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_verification_key")
+        .add_attribute("credential_type", type_str)
+        .add_attribute("version", version))
+}
+
+fn execute_deprecate_vk(
+    deps: DepsMut,
+    info: MessageInfo,
+    type_str: String,
+    version: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can deprecate verification keys".to_string(),
+        });
+    }
+
+    let mut stored = VERIFICATION_KEYS
+        .may_load(deps.storage, (&type_str, &version))?
+        .ok_or(ContractError::VerificationKeyNotFound {
+            reference: format!("{}/{}", type_str, version),
+        })?;
+    stored.deprecated = true;
+    VERIFICATION_KEYS.save(deps.storage, (&type_str, &version), &stored)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deprecate_verification_key")
+        .add_attribute("credential_type", type_str)
+        .add_attribute("version", version))
+}
+
+// ── Query ───────────────────────────────────────────────────────────
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetCredential {
+            credential_id,
+            view_token,
+        } => to_json_binary(&query_credential(deps, env, credential_id, view_token)?),
+        QueryMsg::GetVerifiableCredential {
+            credential_id,
+            view_token,
+        } => to_json_binary(&query_verifiable_credential(
+            deps,
+            env,
+            credential_id,
+            view_token,
+        )?),
+        QueryMsg::VerifyPresentation { presentation } => {
+            to_json_binary(&query_verify_presentation(deps, env, presentation)?)
+        }
+        QueryMsg::GetIssuanceRoot { root } => to_json_binary(&query_issuance_root(deps, root)?),
+        QueryMsg::GetLineage { credential_id } => {
+            to_json_binary(&query_lineage(deps, credential_id)?)
+        }
+        QueryMsg::HasValidCredential {
+            holder,
+            credential_type,
+            requester,
+        } => to_json_binary(&query_has_valid(
+            deps,
+            env,
+            holder,
+            credential_type,
+            requester,
+        )?),
+        QueryMsg::CountValidCredentials { credential_type } => {
+            to_json_binary(&query_count_valid(deps, env, credential_type)?)
+        }
+        QueryMsg::ListCredentials {
+            holder,
+            start_after,
+            limit,
+            view_token,
+        } => to_json_binary(&query_list(
+            deps,
+            env,
+            holder,
+            start_after,
+            limit,
+            view_token,
+        )?),
+        QueryMsg::ListCredentialAccess { holder } => {
+            to_json_binary(&query_credential_access(deps, holder)?)
+        }
+        QueryMsg::ListCredentialsByIssuer {
+            issuer,
+            start_after,
+            limit,
+        } => to_json_binary(&query_list_by_issuer(deps, issuer, start_after, limit)?),
+        QueryMsg::ListExpiringCredentials {
+            before,
+            start_after,
+            limit,
+        } => to_json_binary(&query_expiring(deps, env, before, start_after, limit)?),
+        QueryMsg::ListExpiredCredentials { start_after, limit } => {
+            to_json_binary(&query_expired(deps, env, start_after, limit)?)
+        }
+        QueryMsg::RevocationRoot { epoch } => to_json_binary(&query_revocation_root(deps, epoch)?),
+        QueryMsg::RevocationWitness { status_index } => {
+            to_json_binary(&query_revocation_witness(deps, status_index)?)
+        }
+        QueryMsg::VerifyDisclosure {
+            credential_id,
+            disclosures,
+        } => to_json_binary(&query_verify_disclosure(
+            deps,
+            env,
+            credential_id,
+            disclosures,
+        )?),
+        QueryMsg::GetCredentialSchema { name } => to_json_binary(&query_schema(deps, name)?),
+        QueryMsg::ListCredentialSchemas { start_after, limit } => {
+            to_json_binary(&query_schemas(deps, start_after, limit)?)
+        }
+        QueryMsg::ListIssuers {} => to_json_binary(&query_issuers(deps)?),
+        QueryMsg::IssuerStatus { issuer } => to_json_binary(&query_issuer_status(deps, issuer)?),
+        QueryMsg::ListTypeImplications {} => to_json_binary(&query_type_implications(deps)?),
+        QueryMsg::ListHooks {} => to_json_binary(&query_hooks(deps)?),
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetRecovery { recovery_id } => {
+            to_json_binary(&query_recovery(deps, recovery_id)?)
+        }
+        QueryMsg::ListPendingRecoveries {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query_pending_recoveries(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetGuardians { holder } => to_json_binary(&query_guardians(deps, holder)?),
+        QueryMsg::GetVerificationRequest { request_id } => {
+            to_json_binary(&query_verification_request(deps, env, request_id)?)
+        }
+        QueryMsg::ListVerificationRequestsByApplicant {
+            applicant,
+            start_after,
+            limit,
+        } => to_json_binary(&query_requests_by_applicant(
+            deps,
+            env,
+            applicant,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListVerificationRequestsByStatus {
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_requests_by_status(
+            deps,
+            env,
+            status,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::VerificationConfig {} => to_json_binary(&query_verification_config(deps)?),
+        QueryMsg::GetVouches { request_id } => to_json_binary(&query_vouches(deps, request_id)?),
+        QueryMsg::GetVoucher { voucher } => to_json_binary(&query_voucher(deps, env, voucher)?),
+        QueryMsg::VouchingConfig {} => to_json_binary(&VOUCHING_CONFIG.load(deps.storage)?),
+        QueryMsg::ListRelayers {} => to_json_binary(&query_relayers(deps)?),
+        QueryMsg::GetAttestations { request_id } => {
+            to_json_binary(&query_attestations(deps, request_id)?)
+        }
+        QueryMsg::IsNullifierSpent { nullifier } => {
+            to_json_binary(&query_nullifier(deps, nullifier)?)
+        }
+        QueryMsg::GetVerificationKey {
+            credential_type,
+            version,
+        } => to_json_binary(&query_verification_key(deps, credential_type, version)?),
+        QueryMsg::ListVerificationKeys { credential_type } => {
+            to_json_binary(&query_verification_keys(deps, credential_type)?)
+        }
+    }
+}
+
+fn query_credential(
+    deps: Deps,
+    env: Env,
+    credential_id: String,
+    view_token: Option<ViewToken>,
+) -> StdResult<CredentialResponse> {
+    let cred = load_visible(deps, &env, &credential_id, view_token)?;
+    Ok(to_credential_response(deps.storage, cred))
+}
+
+fn query_verifiable_credential(
+    deps: Deps,
+    env: Env,
+    credential_id: String,
+    view_token: Option<ViewToken>,
+) -> StdResult<VerifiableCredential> {
+    let cred = load_visible(deps, &env, &credential_id, view_token)?;
+    let registry_did = vc::address_to_did(&env.contract.address);
+    let status_index = CREDENTIAL_STATUS_INDEX.may_load(deps.storage, &credential_id)?;
+    let mut credential = vc::to_verifiable_credential(&cred);
+    credential.credential_status = Some(CredentialStatus {
+        id: match status_index {
+            Some(index) => format!("{}#status-{}", registry_did, index),
+            None => registry_did.clone(),
+        },
+        status_type: VC_STATUS_TYPE.to_string(),
+        status_list_index: status_index.map(|i| i.to_string()),
+        revoked: cred.revoked,
+    });
+    credential.proof = Some(VcProof {
+        proof_type: VC_PROOF_TYPE.to_string(),
+        created: credential.issuance_date.clone(),
+        proof_purpose: "assertionMethod".to_string(),
+        verification_method: registry_did,
+        proof_value: None,
+    });
+    Ok(credential)
+}
+
+fn query_verify_disclosure(
+    deps: Deps,
+    env: Env,
+    credential_id: String,
+    disclosures: Vec<Disclosure>,
+) -> StdResult<VerificationResult> {
+    let cred = CREDENTIALS.load(deps.storage, &credential_id)?;
+    let credential_type = cred.credential_type.clone();
+    Ok(
+        match check_disclosures(deps.storage, &cred, &disclosures, env.block.time.seconds()) {
+            Ok(claims) => VerificationResult {
+                valid: true,
+                credential_type,
+                message: format!("{} attribute claims proven", claims.len()),
+                claims,
+            },
+            Err(e) => VerificationResult {
+                valid: false,
+                credential_type,
+                message: e.to_string(),
+                claims: vec![],
+            },
+        },
+    )
+}
+
+fn query_verify_presentation(
+    deps: Deps,
+    env: Env,
+    presentation: Presentation,
+) -> StdResult<VerificationResult> {
+    let cred = CREDENTIALS.load(deps.storage, &presentation.credential_id)?;
+    let credential_type = cred.credential_type.clone();
+    Ok(match check_presentation(deps, &env, &cred, &presentation) {
+        Ok(()) => VerificationResult {
+            valid: true,
+            credential_type,
+            message: format!("Presented to {}", presentation.audience),
+            claims: vec![],
+        },
+        Err(e) => VerificationResult {
+            valid: false,
+            credential_type,
+            message: e.to_string(),
+            claims: vec![],
+        },
+    })
+}
+
+fn check_presentation(
+    deps: Deps,
+    env: &Env,
+    cred: &Credential,
+    presentation: &Presentation,
+) -> Result<(), ContractError> {
+    let now = env.block.time.seconds();
+    ensure_usable(deps.storage, cred, now)?;
+    if now >= presentation.expires_at {
+        return Err(ContractError::InvalidPresentation {
+            reason: "Presentation expired".to_string(),
+        });
+    }
+
+    let digest = presentation_digest(env, presentation);
+    check_holder_signature(
+        deps.api,
+        &cred.holder,
+        &presentation.pubkey,
+        &digest,
+        &presentation.signature,
+    )
+    .map_err(|reason| ContractError::InvalidPresentation { reason })
+}
+
+/// Check that `pubkey` belongs to `holder` and signed `digest`, returning
+/// the reason when it did not.
+fn check_holder_signature(
+    api: &dyn Api,
+    holder: &Addr,
+    pubkey: &[u8],
+    digest: &[u8],
+    signature: &[u8],
+) -> Result<(), String> {
+    // Account addresses are ripemd160(sha256(pubkey)) of the compressed key
+    let holder = api
+        .addr_canonicalize(holder.as_str())
+        .map_err(|e| e.to_string())?;
+    if pubkey.len() != 33
+        || Ripemd160::digest(Sha256::digest(pubkey)).as_slice() != holder.as_slice()
+    {
+        return Err("Public key does not belong to the holder".to_string());
+    }
+
+    let valid = api
+        .secp256k1_verify(digest, signature, pubkey)
+        .map_err(|e| e.to_string())?;
+    if !valid {
+        return Err("Signature does not match".to_string());
+    }
+    Ok(())
+}
+
+/// The message a holder signs for a presentation; see `msg::Presentation`.
+fn presentation_digest(env: &Env, presentation: &Presentation) -> [u8; 32] {
+    holder_signed_digest(
+        b"citizen-ledger/presentation/v1",
+        env,
+        &[
+            &presentation.credential_id,
+            &presentation.audience,
+            &presentation.nonce,
+        ],
+        presentation.expires_at,
+    )
+}
+
+/// The message a holder signs for a view token; see `msg::ViewToken`.
+fn view_token_digest(env: &Env, holder: &Addr, token: &ViewToken) -> [u8; 32] {
+    holder_signed_digest(
+        b"citizen-ledger/view/v1",
+        env,
+        &[holder.as_str()],
+        token.expires_at,
+    )
+}
+
+/// sha256 of `domain`, the chain id, the registry address and `fields` (each
+/// length-prefixed), then `expires_at`.
+fn holder_signed_digest(domain: &[u8], env: &Env, fields: &[&str], expires_at: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    for field in [env.block.chain_id.as_str(), env.contract.address.as_str()]
+        .iter()
+        .chain(fields)
+    {
+        hasher.update((field.len() as u32).to_be_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.update(expires_at.to_be_bytes());
+    hasher.finalize().into()
+}
+
+fn query_lineage(deps: Deps, credential_id: String) -> StdResult<LineageResponse> {
+    CREDENTIALS.load(deps.storage, &credential_id)?;
+    let lineage = load_lineage(deps.storage, &credential_id)?;
+
+    // Walk forward from the first credential of the lineage
+    let mut credential_ids = vec![lineage.lineage_id.clone()];
+    let mut next = load_lineage(deps.storage, &lineage.lineage_id)?.superseded_by;
+    while let Some(id) = next {
+        next = load_lineage(deps.storage, &id)?.superseded_by;
+        credential_ids.push(id);
+    }
+
+    Ok(LineageResponse {
+        lineage_id: lineage.lineage_id,
+        current: credential_ids.last().cloned().unwrap_or_default(),
+        credential_ids,
+    })
+}
+
+fn query_has_valid(
+    deps: Deps,
+    env: Env,
+    holder: String,
+    credential_type: CredentialType,
+    requester: Option<String>,
+) -> StdResult<HasCredentialResponse> {
+    let holder_addr = deps.api.addr_validate(&holder)?;
+    let now = env.block.time.seconds();
+    let cred = match find_valid_credential(deps.storage, &holder_addr, &credential_type, now)? {
+        Some(cred) => Some(cred),
+        None => find_implying_credential(deps.storage, &holder_addr, &credential_type, now)?,
+    };
+    let Some(cred) = cred else {
+        return Ok(HasCredentialResponse {
+            has_credential: false,
+            credential_id: None,
+            lineage_id: None,
+            credential_type: None,
+        });
+    };
+
+    // Queries carry no sender, so `requester` is a hint anyone can give; it
+    // only decides whether a private credential's id is handed out
+    let disclose_id = !is_private(deps.storage, &holder_addr, &cred.credential_type)?
+        || match requester {
+            Some(requester) => {
+                let requester = deps.api.addr_validate(&requester)?;
+                HOOKS.has(deps.storage, &requester)
+                    || CREDENTIAL_ACCESS.has(deps.storage, (&holder_addr, &requester))
+            }
+            None => false,
+        };
+    Ok(HasCredentialResponse {
+        has_credential: true,
+        lineage_id: Some(load_lineage(deps.storage, &cred.id)?.lineage_id),
+        credential_type: Some(cred.credential_type.clone()),
+        credential_id: disclose_id.then_some(cred.id),
+    })
+}
+
+/// Whether a credential was issued at or after its issuer's compromise cutoff.
+fn issued_while_compromised(storage: &dyn Storage, cred: &Credential) -> StdResult<bool> {
+    Ok(matches!(
+        ISSUER_STATUS.may_load(storage, &cred.issuer)?,
+        Some(IssuerStatus::Compromised { since }) if cred.issued_at >= since
+    ))
+}
+
+/// First unrevoked, unexpired credential of a type held by an address.
+fn find_valid_credential(
+    storage: &dyn Storage,
+    holder: &Addr,
+    credential_type: &CredentialType,
+    now: u64,
+) -> StdResult<Option<Credential>> {
+    let type_str = credential_type.to_string();
+
+    // Iterate over holder's credentials to find a valid one of the requested type
+    let creds: Vec<_> = HOLDER_CREDENTIALS
+        .prefix(holder)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (cred_id, _) in creds {
+        if let Ok(cred) = CREDENTIALS.load(storage, &cred_id) {
+            if cred.credential_type.to_string() == type_str && is_current(storage, &cred, now)? {
+                return Ok(Some(cred));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// First valid credential held by an address whose type implies `credential_type`.
+fn find_implying_credential(
+    storage: &dyn Storage,
+    holder: &Addr,
+    credential_type: &CredentialType,
+    now: u64,
+) -> StdResult<Option<Credential>> {
+    let implications = load_type_implications(storage)?;
+    if implications.is_empty() {
+        return Ok(None);
+    }
+
+    let creds: Vec<_> = HOLDER_CREDENTIALS
+        .prefix(holder)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (cred_id, _) in creds {
+        let Ok(cred) = CREDENTIALS.load(storage, &cred_id) else {
+            continue;
+        };
+        if !is_current(storage, &cred, now)? {
+            continue;
+        }
+        let implied = reachable_types(&implications, &cred.credential_type, |rule| {
+            if rule.requires_attestation {
+                issuer_attests(storage, &cred.issuer, &rule.to)
+            } else {
+                Ok(true)
+            }
+        })?;
+        if implied.contains(credential_type) {
+            return Ok(Some(cred));
+        }
+    }
+
+    Ok(None)
+}
+
+fn query_count_valid(
+    deps: Deps,
+    env: Env,
+    credential_type: CredentialType,
+) -> StdResult<CredentialCountResponse> {
+    let type_str = credential_type.to_string();
+    let active = ACTIVE_CREDENTIALS
+        .may_load(deps.storage, &type_str)?
+        .unwrap_or(0);
+
+    // Credentials that expired since the last sweep are still counted; drop
+    // the holders left with nothing else
+    let cursor = EXPIRY_SWEEP_CURSOR.may_load(deps.storage)?;
+    let start = cursor
+        .as_ref()
+        .map(|(expires_at, id)| Bound::exclusive((*expires_at, id.as_str())));
+    let end = Some(Bound::exclusive((env.block.time.seconds() + 1, "")));
+    let unswept = CREDENTIAL_EXPIRY
+        .keys(deps.storage, start, end, Order::Ascending)
+        .take(MAX_UNSWEPT_COUNTED + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    if unswept.len() > MAX_UNSWEPT_COUNTED {
+        return Err(StdError::generic_err(
+            "Too many expired credentials await SweepExpired to count valid ones",
+        ));
+    }
+    let mut expired_by_holder: Vec<(Addr, u32)> = vec![];
+    for (_, cred_id) in &unswept {
+        let cred = CREDENTIALS.load(deps.storage, cred_id)?;
+        if cred.credential_type != credential_type || !counted_as_active(deps.storage, &cred)? {
+            continue;
+        }
+        match expired_by_holder
+            .iter_mut()
+            .find(|(h, _)| *h == cred.holder)
+        {
+            Some((_, n)) => *n += 1,
+            None => expired_by_holder.push((cred.holder, 1)),
+        }
+    }
+    let mut lapsed = 0u64;
+    for (holder, expired) in &expired_by_holder {
+        let held = ACTIVE_HOLDER_CREDENTIALS
+            .may_load(deps.storage, (type_str.as_str(), holder))?
+            .unwrap_or(0);
+        if *expired >= held {
+            lapsed += 1;
+        }
+    }
+
+    Ok(CredentialCountResponse {
+        credential_type,
+        count: active.saturating_sub(lapsed),
+    })
+}
+
+/// Unrevoked, unexpired, not superseded, and not from a compromised issuer key.
+fn is_current(storage: &dyn Storage, cred: &Credential, now: u64) -> StdResult<bool> {
+    Ok(!cred.revoked
+        && (cred.expires_at == 0 || cred.expires_at > now)
+        && !issued_while_compromised(storage, cred)?
+        && superseded_by(storage, &cred.id)?.is_none())
+}
+
+fn query_list(
+    deps: Deps,
+    env: Env,
+    holder: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    view_token: Option<ViewToken>,
+) -> StdResult<CredentialsListResponse> {
+    let holder_addr = deps.api.addr_validate(&holder)?;
+    let include_private = match view_token {
+        Some(token) => {
+            check_view_token(deps, &env, &holder_addr, &token)?;
+            true
+        }
+        None => false,
+    };
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let ids =
+        HOLDER_CREDENTIALS
+            .prefix(&holder_addr)
+            .keys(deps.storage, start, None, Order::Ascending);
+    list_visible(deps.storage, ids, limit, include_private)
+}
+
+/// Fail unless `token` is the holder's unexpired permission to see their private credentials.
+fn check_view_token(deps: Deps, env: &Env, holder: &Addr, token: &ViewToken) -> StdResult<()> {
+    if env.block.time.seconds() >= token.expires_at {
+        return Err(StdError::generic_err("Invalid view token: expired"));
+    }
+    let digest = view_token_digest(env, holder, token);
+    check_holder_signature(deps.api, holder, &token.pubkey, &digest, &token.signature)
+        .map_err(|reason| StdError::generic_err(format!("Invalid view token: {}", reason)))
+}
+
+/// Load a credential, withholding private ones unless their holder's view token is given.
+fn load_visible(
+    deps: Deps,
+    env: &Env,
+    credential_id: &str,
+    view_token: Option<ViewToken>,
+) -> StdResult<Credential> {
+    let cred = CREDENTIALS.load(deps.storage, credential_id)?;
+    match view_token {
+        Some(token) => check_view_token(deps, env, &cred.holder, &token)?,
+        None if is_private(deps.storage, &cred.holder, &cred.credential_type)? => {
+            return Err(StdError::generic_err(
+                "Credential is private; a view token from its holder is required",
+            ))
+        }
+        None => {}
+    }
+    Ok(cred)
+}
+
+/// The first `limit` listed credentials, counting rather than returning private
+/// ones unless `include_private`. Private entries use up the page like any
+/// other, so a page may come back short; continue from `next_start_after`.
+fn list_visible(
+    storage: &dyn Storage,
+    ids: impl Iterator<Item = StdResult<String>>,
+    limit: Option<u32>,
+    include_private: bool,
+) -> StdResult<CredentialsListResponse> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let mut credentials = vec![];
+    let mut private_count = 0u32;
+    let mut examined = 0usize;
+    let mut last_id = None;
+    for cred_id in ids.take(limit) {
+        let cred_id = cred_id?;
+        examined += 1;
+        if let Some(cred) = CREDENTIALS.may_load(storage, &cred_id)? {
+            if !include_private && is_private(storage, &cred.holder, &cred.credential_type)? {
+                private_count += 1;
+            } else {
+                credentials.push(to_credential_response(storage, cred));
+            }
+        }
+        last_id = Some(cred_id);
+    }
+
+    Ok(CredentialsListResponse {
+        credentials,
+        private_count,
+        next_start_after: if examined == limit { last_id } else { None },
+    })
+}
+
+fn query_list_by_issuer(
+    deps: Deps,
+    issuer: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CredentialsListResponse> {
+    let issuer_addr = deps.api.addr_validate(&issuer)?;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let ids =
+        ISSUER_CREDENTIALS
+            .prefix(&issuer_addr)
+            .keys(deps.storage, start, None, Order::Ascending);
+    list_visible(deps.storage, ids, limit, false)
+}
+
+fn query_issuance_root(deps: Deps, root: String) -> StdResult<IssuanceRootResponse> {
+    let root = root.to_lowercase();
+    let anchored = ISSUANCE_ROOTS.load(deps.storage, &root)?;
+    Ok(IssuanceRootResponse {
+        root,
+        issuer: anchored.issuer.to_string(),
+        anchored_at: anchored.anchored_at,
+        claimed: anchored.claimed,
+    })
+}
+
+fn query_expiring(
+    deps: Deps,
+    env: Env,
+    before: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CredentialsListResponse> {
+    // Still valid means `expires_at > now`
+    let now = env.block.time.seconds();
+    let after = expiry_key(deps.storage, start_after)?;
+    let start = match &after {
+        Some((expires_at, id)) if *expires_at > now => Bound::exclusive((*expires_at, id.as_str())),
+        _ => Bound::inclusive((now + 1, "")),
+    };
+    list_by_expiry(deps.storage, start, before, limit)
+}
+
+fn query_expired(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CredentialsListResponse> {
+    let after = expiry_key(deps.storage, start_after)?;
+    let start = after
+        .as_ref()
+        .map(|(expires_at, id)| Bound::exclusive((*expires_at, id.as_str())))
+        .unwrap_or(Bound::inclusive((0, "")));
+    list_by_expiry(deps.storage, start, env.block.time.seconds() + 1, limit)
+}
+
+/// Expiry index key of a pagination cursor credential.
+fn expiry_key(
+    storage: &dyn Storage,
+    credential_id: Option<String>,
+) -> StdResult<Option<(u64, String)>> {
+    credential_id
+        .map(|id| Ok((CREDENTIALS.load(storage, &id)?.expires_at, id)))
+        .transpose()
+}
+
+/// Credentials in the expiry index from `start` up to (excluding) `expires_before`.
+fn list_by_expiry<'a>(
+    storage: &dyn Storage,
+    start: Bound<'a, (u64, &'a str)>,
+    expires_before: u64,
+    limit: Option<u32>,
+) -> StdResult<CredentialsListResponse> {
+    let ids = CREDENTIAL_EXPIRY
+        .keys(
+            storage,
+            Some(start),
+            Some(Bound::exclusive((expires_before, ""))),
+            Order::Ascending,
+        )
+        .map(|r| r.map(|(_, cred_id)| cred_id));
+    list_visible(storage, ids, limit, false)
+}
+
+fn to_credential_response(storage: &dyn Storage, c: Credential) -> CredentialResponse {
+    let lineage = CREDENTIAL_LINEAGE.may_load(storage, &c.id).ok().flatten();
+    let expired = SWEPT_CREDENTIALS.has(storage, &c.id);
+    CredentialResponse {
+        expired,
+        lineage_id: lineage
+            .as_ref()
+            .map(|l| l.lineage_id.clone())
+            .unwrap_or_else(|| c.id.clone()),
+        previous_id: lineage.as_ref().and_then(|l| l.previous_id.clone()),
+        superseded_by: lineage.and_then(|l| l.superseded_by),
+        status_index: CREDENTIAL_STATUS_INDEX
+            .may_load(storage, &c.id)
+            .ok()
+            .flatten(),
+        attributes_root: CREDENTIAL_ATTRIBUTES
+            .may_load(storage, &c.id)
+            .ok()
+            .flatten(),
+        id: c.id,
+        holder: c.holder.to_string(),
+        credential_type: c.credential_type,
+        commitment: c.commitment,
+        issuer: c.issuer.to_string(),
+        issued_at: c.issued_at,
+        expires_at: c.expires_at,
+        revoked: c.revoked,
+    }
+}
+
+fn query_type_implications(deps: Deps) -> StdResult<TypeImplicationsResponse> {
+    Ok(TypeImplicationsResponse {
+        implications: load_type_implications(deps.storage)?,
+    })
+}
+
+fn query_issuers(deps: Deps) -> StdResult<IssuersResponse> {
+    let issuers = ISSUERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|addr| {
+            let addr = addr?;
+            Ok(IssuerInfo {
+                issuer: addr.to_string(),
+                scope: ISSUER_SCOPES.may_load(deps.storage, &addr)?,
+                status: ISSUER_STATUS
+                    .may_load(deps.storage, &addr)?
+                    .unwrap_or_default(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(IssuersResponse { issuers })
+}
+
+fn query_revocation_root(deps: Deps, epoch: Option<u64>) -> StdResult<RevocationRootResponse> {
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => REVOCATION_EPOCH.load(deps.storage)?,
+    };
+    let record = REVOCATION_ROOTS.load(deps.storage, epoch)?;
+    let mut root = [0u8; 32];
+    hex::decode_to_slice(&record.root, &mut root)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(RevocationRootResponse {
+        epoch,
+        root_field: merkle::to_field(&root),
+        root: record.root,
+        updated_at: record.updated_at,
+        depth: status_list::DEPTH as u32,
+    })
+}
+
+fn query_revocation_witness(deps: Deps, status_index: u64) -> StdResult<RevocationWitnessResponse> {
+    let (chunk, siblings) = status_list::witness(deps.storage, status_index)?;
+    Ok(RevocationWitnessResponse {
+        status_index,
+        epoch: REVOCATION_EPOCH.load(deps.storage)?,
+        root: hex::encode(status_list::root(deps.storage)?),
+        chunk: hex::encode(chunk),
+        siblings: siblings.iter().map(hex::encode).collect(),
+    })
+}
+
+fn query_issuer_status(deps: Deps, issuer: String) -> StdResult<IssuerStatusResponse> {
+    let issuer_addr = deps.api.addr_validate(&issuer)?;
+    let status = ISSUER_STATUS
+        .may_load(deps.storage, &issuer_addr)?
+        .unwrap_or_default();
+    Ok(IssuerStatusResponse { issuer, status })
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let admin = ADMIN.load(deps.storage)?;
+    let count = CREDENTIAL_COUNT.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: admin.to_string(),
+        total_credentials: count,
+    })
+}
+
+fn query_nullifier(deps: Deps, nullifier: Nullifier) -> StdResult<NullifierResponse> {
+    let canonical = |v: &str| {
+        groth16::canonical_field::<Fr>(v).map_err(|e| StdError::generic_err(e.to_string()))
+    };
+    let record = NULLIFIERS.may_load(
+        deps.storage,
+        (
+            &canonical(&nullifier.domain)?,
+            &canonical(&nullifier.value)?,
+        ),
+    )?;
+    Ok(NullifierResponse {
+        spent: record.is_some(),
+        spent_at: record.map(|r| r.spent_at),
+    })
+}
+
+fn vk_response(
+    credential_type: String,
+    version: String,
+    stored: StoredVerificationKey,
+) -> VerificationKeyResponse {
+    VerificationKeyResponse {
+        credential_type,
+        version,
+        verification_key: stored.key,
+        deprecated: stored.deprecated,
+        registered_at: stored.registered_at,
+    }
+}
+
+fn query_verification_key(
+    deps: Deps,
+    credential_type: CredentialType,
+    version: String,
+) -> StdResult<VerificationKeyResponse> {
+    let type_str = credential_type.to_string();
+    let stored = VERIFICATION_KEYS.load(deps.storage, (&type_str, &version))?;
+    Ok(vk_response(type_str, version, stored))
+}
+
+fn query_verification_keys(
+    deps: Deps,
+    credential_type: Option<CredentialType>,
+) -> StdResult<VerificationKeysResponse> {
+    let keys = match credential_type {
+        Some(ct) => {
+            let type_str = ct.to_string();
+            VERIFICATION_KEYS
+                .prefix(&type_str)
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|r| r.map(|(version, stored)| vk_response(type_str.clone(), version, stored)))
+                .collect::<StdResult<Vec<_>>>()?
+        }
+        None => VERIFICATION_KEYS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|r| r.map(|((ct, version), stored)| vk_response(ct, version, stored)))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+    Ok(VerificationKeysResponse { keys })
+}
+
+// ── Verification Requests ───────────────────────────────────────────
+
+fn execute_request_verification(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    verification_type: VerificationType,
+    data_hash: String,
+) -> Result<Response, ContractError> {
+    if verification_type == VerificationType::SocialVouching {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Use RequestSocialVouching for social verification",
+        )));
+    }
+
+    let request_id = open_verification_request(deps, &env, &info, verification_type, data_hash)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_verification")
+        .add_attribute("request_id", request_id)
+        .add_attribute("applicant", info.sender.as_str()))
+}
+
+/// Store a new pending verification request for the sender.
+fn open_verification_request(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    verification_type: VerificationType,
+    data_hash: String,
+) -> Result<String, ContractError> {
+    if data_hash.is_empty() {
+        return Err(ContractError::InvalidVerificationRequest {
+            reason: "data_hash must not be empty".to_string(),
+        });
+    }
+
+    let request_id_raw = format!(
+        "verification:{}:{}:{}",
+        info.sender, data_hash, env.block.height
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(request_id_raw.as_bytes());
+    let request_id = hex::encode(hasher.finalize());
+
+    if VERIFICATION_REQUESTS.has(deps.storage, &request_id) {
+        return Err(ContractError::VerificationRequestAlreadyExists { request_id });
+    }
+
+    let request = VerificationRequest {
+        request_id: request_id.clone(),
+        applicant: info.sender.to_string(),
+        verification_type,
+        data_hash,
+        status: VerificationStatus::Pending,
+        requested_at: env.block.time.seconds(),
+        completed_at: 0,
+    };
+    save_request(deps.storage, &request)?;
+    APPLICANT_REQUESTS.save(deps.storage, (&info.sender, &request_id), &true)?;
+
+    Ok(request_id)
+}
+
+fn execute_start_review(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    request_id: String,
+) -> Result<Response, ContractError> {
+    let is_relayer = AUTHORIZED_RELAYERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or(false);
+    if !is_relayer {
+        return Err(ContractError::Unauthorized {
+            reason: "Only authorized relayers can review verification requests".to_string(),
+        });
+    }
+
+    let mut request = load_relayer_request(deps.as_ref(), &env, &request_id)?;
+    request.status = VerificationStatus::InReview;
+    save_request(deps.storage, &request)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_verification_review")
+        .add_attribute("request_id", request_id)
+        .add_attribute("relayer", info.sender.as_str()))
+}
+
+fn execute_expire_request(
+    deps: DepsMut,
+    env: Env,
+    request_id: String,
+) -> Result<Response, ContractError> {
+    let mut request = VERIFICATION_REQUESTS
+        .may_load(deps.storage, &request_id)?
+        .ok_or(ContractError::VerificationRequestNotFound {
+            request_id: request_id.clone(),
+        })?;
+
+    let config = VERIFICATION_CONFIG.load(deps.storage)?;
+    if effective_status(&request, &config, env.block.time.seconds()) != VerificationStatus::Expired
+        || request.status == VerificationStatus::Expired
+    {
+        return Err(ContractError::VerificationRequestNotExpired { request_id });
+    }
+
+    request.status = VerificationStatus::Expired;
+    request.completed_at = env.block.time.seconds();
+    save_request(deps.storage, &request)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "expire_verification_request")
+        .add_attribute("request_id", request_id))
+}
+
+fn execute_update_verification_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: VerificationConfig,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can update the verification config".to_string(),
+        });
+    }
+
+    save_verification_config(deps.storage, config)?;
+
+    Ok(Response::new().add_attribute("action", "update_verification_config"))
+}
+
+/// Store the verification config. The relayer set lives in
+/// `AUTHORIZED_RELAYERS` and only changes through `AddRelayer`/`RemoveRelayer`,
+/// so a listed `authorized_relayers` is dropped rather than authorized.
+fn save_verification_config(
+    storage: &mut dyn Storage,
+    mut config: VerificationConfig,
+) -> StdResult<()> {
+    config.authorized_relayers.clear();
+    VERIFICATION_CONFIG.save(storage, &config)
+}
+
+/// Save a request, moving it to its recorded status in `REQUESTS_BY_STATUS`.
+pub(crate) fn save_request(
+    storage: &mut dyn Storage,
+    request: &VerificationRequest,
+) -> StdResult<()> {
+    let id = request.request_id.as_str();
+    if let Some(previous) = VERIFICATION_REQUESTS.may_load(storage, id)? {
+        REQUESTS_BY_STATUS.remove(storage, (status_key(&previous.status), id));
+    }
+    REQUESTS_BY_STATUS.save(storage, (status_key(&request.status), id), &true)?;
+    VERIFICATION_REQUESTS.save(storage, id, request)
+}
+
+/// Stable key of a status in `REQUESTS_BY_STATUS`
+fn status_key(status: &VerificationStatus) -> &'static str {
+    match status {
+        VerificationStatus::Pending => "pending",
+        VerificationStatus::InReview => "in_review",
+        VerificationStatus::Approved => "approved",
+        VerificationStatus::Rejected => "rejected",
+        VerificationStatus::Expired => "expired",
+    }
+}
+
+fn default_verification_config() -> VerificationConfig {
+    VerificationConfig {
+        api_endpoint: String::new(),
+        authorized_relayers: vec![],
+        min_social_vouches: 3,
+        result_validity_secs: 604_800, // 7 days
+        require_biometric: false,
+    }
+}
+
+/// Status of a request as of `now`: open requests older than the validity
+/// period count as expired even before anyone records it.
+fn effective_status(
+    request: &VerificationRequest,
+    config: &VerificationConfig,
+    now: u64,
+) -> VerificationStatus {
+    let open = matches!(
+        request.status,
+        VerificationStatus::Pending | VerificationStatus::InReview
+    );
+    if open && now > request.requested_at + config.result_validity_secs {
+        VerificationStatus::Expired
+    } else {
+        request.status.clone()
+    }
+}
+
+/// Load a request that relayers can still act on.
+fn load_open_request(
+    deps: Deps,
+    env: &Env,
+    request_id: &str,
+) -> Result<VerificationRequest, ContractError> {
+    let request = VERIFICATION_REQUESTS
+        .may_load(deps.storage, request_id)?
+        .ok_or(ContractError::VerificationRequestNotFound {
+            request_id: request_id.to_string(),
+        })?;
+
+    let config = VERIFICATION_CONFIG.load(deps.storage)?;
+    match effective_status(&request, &config, env.block.time.seconds()) {
+        VerificationStatus::Pending | VerificationStatus::InReview => Ok(request),
+        VerificationStatus::Expired => Err(ContractError::VerificationRequestExpired {
+            request_id: request_id.to_string(),
+        }),
+        _ => Err(ContractError::VerificationRequestClosed {
+            request_id: request_id.to_string(),
+        }),
+    }
+}
+
+/// Load an open request that relayers may review; social vouching requests
+/// are settled by vouchers alone.
+pub(crate) fn load_relayer_request(
+    deps: Deps,
+    env: &Env,
+    request_id: &str,
+) -> Result<VerificationRequest, ContractError> {
+    let request = load_open_request(deps, env, request_id)?;
+    if request.verification_type == VerificationType::SocialVouching {
+        return Err(ContractError::Unauthorized {
+            reason: "Social vouching requests are settled by vouchers, not relayers".to_string(),
+        });
+    }
+    Ok(request)
+}
+
+fn query_verification_request(
+    deps: Deps,
+    env: Env,
+    request_id: String,
+) -> StdResult<VerificationRequest> {
+    let config = VERIFICATION_CONFIG.load(deps.storage)?;
+    let mut request = VERIFICATION_REQUESTS.load(deps.storage, &request_id)?;
+    request.status = effective_status(&request, &config, env.block.time.seconds());
+    Ok(request)
+}
+
+fn query_requests_by_applicant(
+    deps: Deps,
+    env: Env,
+    applicant: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VerificationRequestsResponse> {
+    let applicant_addr = deps.api.addr_validate(&applicant)?;
+    let config = VERIFICATION_CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let requests = APPLICANT_REQUESTS
+        .prefix(&applicant_addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| {
+            let mut request = VERIFICATION_REQUESTS.load(deps.storage, &id?)?;
+            request.status = effective_status(&request, &config, now);
+            Ok(request)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(VerificationRequestsResponse { requests })
+}
+
+fn query_requests_by_status(
+    deps: Deps,
+    env: Env,
+    status: VerificationStatus,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<VerificationRequestsResponse> {
+    let config = VERIFICATION_CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let requests = REQUESTS_BY_STATUS
+        .prefix(status_key(&status))
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| {
+            let mut request = VERIFICATION_REQUESTS.load(deps.storage, &id?)?;
+            request.status = effective_status(&request, &config, now);
+            Ok(request)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(VerificationRequestsResponse { requests })
+}
+
+fn query_verification_config(deps: Deps) -> StdResult<VerificationConfig> {
+    let mut config = VERIFICATION_CONFIG.load(deps.storage)?;
+    config.authorized_relayers = AUTHORIZED_RELAYERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(config)
+}
+
+// ── Social Vouching ─────────────────────────────────────────────────
+
+fn execute_request_vouching(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    data_hash: String,
+    commitment: String,
+    nullifier: Nullifier,
+) -> Result<Response, ContractError> {
+    // Fail early on a nullifier that could never be spent
+    let nullifier = Nullifier {
+        domain: groth16::canonical_field::<Fr>(&nullifier.domain)?,
+        value: groth16::canonical_field::<Fr>(&nullifier.value)?,
+    };
+    if NULLIFIERS.has(deps.storage, (&nullifier.domain, &nullifier.value)) {
+        return Err(ContractError::NullifierAlreadyUsed {
+            domain: nullifier.domain,
+        });
+    }
+
+    let request_id = open_verification_request(
+        deps.branch(),
+        &env,
+        &info,
+        VerificationType::SocialVouching,
+        data_hash,
+    )?;
+    VOUCHING_CLAIMS.save(
+        deps.storage,
+        &request_id,
+        &VouchingClaim {
+            commitment,
+            nullifier,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_social_vouching")
+        .add_attribute("request_id", request_id)
+        .add_attribute("applicant", info.sender.as_str()))
+}
+
+fn execute_vouch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    request_id: String,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    let mut request = load_open_request(deps.as_ref(), &env, &request_id)?;
+    let claim = VOUCHING_CLAIMS.may_load(deps.storage, &request_id)?.ok_or(
+        ContractError::VerificationRequestNotFound {
+            request_id: request_id.clone(),
+        },
+    )?;
+
+    if request.applicant == info.sender.as_str() {
+        return Err(ContractError::Unauthorized {
+            reason: "Cannot vouch for yourself".to_string(),
+        });
+    }
+    if find_valid_credential(
+        deps.storage,
+        &info.sender,
+        &CredentialType::Citizenship,
+        now,
+    )?
+    .is_none()
+    {
+        return Err(ContractError::NoVerifiedCredential);
+    }
+    if VOUCHES.has(deps.storage, (&request_id, &info.sender)) {
+        return Err(ContractError::AlreadyAttested { request_id });
+    }
+
+    // Rate limit: a voucher gets a fixed budget of vouches per period
+    let config = VOUCHING_CONFIG.load(deps.storage)?;
+    let mut record = VOUCHERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if now < record.cooldown_until {
+        return Err(ContractError::Unauthorized {
+            reason: format!("Vouching suspended until {}", record.cooldown_until),
+        });
+    }
+    if now >= record.period_start + config.period_secs {
+        record.period_start = now;
+        record.vouches_in_period = 0;
+    }
+    if record.vouches_in_period >= config.max_vouches_per_period {
+        return Err(ContractError::Unauthorized {
+            reason: "Vouch limit for this period reached".to_string(),
+        });
+    }
+    record.vouches_in_period += 1;
+    VOUCHERS.save(deps.storage, &info.sender, &record)?;
+    VOUCHES.save(deps.storage, (&request_id, &info.sender), &now)?;
+
+    if request.status == VerificationStatus::Pending {
+        request.status = VerificationStatus::InReview;
+        save_request(deps.storage, &request)?;
+    }
+
+    let vouchers: Vec<Addr> = VOUCHES
+        .prefix(&request_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let required = VERIFICATION_CONFIG.load(deps.storage)?.min_social_vouches;
+    if (vouchers.len() as u32) < required {
+        return Ok(Response::new()
+            .add_attribute("action", "vouch")
+            .add_attribute("request_id", request_id)
+            .add_attribute("voucher", info.sender.as_str())
+            .add_attribute("vouches", vouchers.len().to_string())
+            .add_attribute("required", required.to_string()));
+    }
+
+    // Enough citizens vouched: the registry itself issues the credential
+    let applicant = deps.api.addr_validate(&request.applicant)?;
+    let cred_id = issue_verified_credential(
+        deps.storage,
+        &env,
+        &applicant,
+        CredentialType::Citizenship,
+        claim.commitment,
+        &env.contract.address,
+        &claim.nullifier,
+    )?;
+    CREDENTIAL_VOUCHERS.save(deps.storage, &cred_id, &vouchers)?;
+
+    request.status = VerificationStatus::Approved;
+    request.completed_at = now;
+    save_request(deps.storage, &request)?;
+    let hooks = hook_msgs(
+        deps.storage,
+        &CredentialHookMsg::Issued {
+            credential_id: cred_id.clone(),
+            holder: applicant.to_string(),
+            credential_type: CredentialType::Citizenship,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "vouching_approved")
+        .add_attribute("request_id", request_id)
+        .add_attribute("credential_id", cred_id)
+        .add_attribute("applicant", request.applicant))
+}
+
+fn execute_update_vouching_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: VouchingConfig,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can update the vouching config".to_string(),
+        });
+    }
+
+    VOUCHING_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_vouching_config"))
+}
+
+fn default_vouching_config() -> VouchingConfig {
+    VouchingConfig {
+        max_vouches_per_period: 3,
+        period_secs: 2_592_000,          // 30 days
+        fraud_cooldown_secs: 15_552_000, // 180 days
+    }
+}
+
+fn query_vouches(deps: Deps, request_id: String) -> StdResult<VouchesResponse> {
+    let vouchers = VOUCHES
+        .prefix(&request_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(VouchesResponse {
+        request_id,
+        vouchers,
+        required: VERIFICATION_CONFIG.load(deps.storage)?.min_social_vouches,
+    })
+}
+
+fn query_voucher(deps: Deps, env: Env, voucher: String) -> StdResult<VoucherResponse> {
+    let voucher_addr = deps.api.addr_validate(&voucher)?;
+    let config = VOUCHING_CONFIG.load(deps.storage)?;
+    let record = VOUCHERS
+        .may_load(deps.storage, &voucher_addr)?
+        .unwrap_or_default();
+
+    let period_ends_at = record.period_start + config.period_secs;
+    let used = if env.block.time.seconds() >= period_ends_at {
+        0
+    } else {
+        record.vouches_in_period
+    };
+    Ok(VoucherResponse {
+        voucher,
+        vouches_remaining: config.max_vouches_per_period.saturating_sub(used),
+        period_ends_at,
+        cooldown_until: record.cooldown_until,
+        fraud_count: record.fraud_count,
+    })
+}
+
+// ── Reply ───────────────────────────────────────────────────────────
+
+/// Only failed hooks reply; record the failure and let the triggering action stand.
+#[entry_point]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != HOOK_REPLY_ID {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Unknown reply id {}",
+            msg.id
+        ))));
+    }
+    let hook: String = from_json(&msg.payload)?;
+    let error = msg.result.into_result().err().unwrap_or_default();
+
+    Ok(Response::new()
+        .add_attribute("action", "credential_hook_failed")
+        .add_attribute("hook", hook)
+        .add_attribute("error", error))
+}
+
+// ── Migrate ─────────────────────────────────────────────────────────
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let version = get_contract_version(deps.storage)?;
+    if version.contract != CONTRACT_NAME {
+        return Err(ContractError::Unauthorized {
+            reason: format!("Cannot migrate from {}", version.contract),
+        });
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // Deployments from before k-of-n attestation behave as 1-of-n
+    if !ATTESTATION_THRESHOLD.exists(deps.storage) {
+        ATTESTATION_THRESHOLD.save(deps.storage, &1u32)?;
+    }
+    if !VERIFICATION_CONFIG.exists(deps.storage) {
+        VERIFICATION_CONFIG.save(deps.storage, &default_verification_config())?;
+    }
+    if !VOUCHING_CONFIG.exists(deps.storage) {
+        VOUCHING_CONFIG.save(deps.storage, &default_vouching_config())?;
+    }
+    if !HOOK_GAS_LIMIT.exists(deps.storage) {
+        HOOK_GAS_LIMIT.save(deps.storage, &DEFAULT_HOOK_GAS_LIMIT)?;
+    }
+    // Leave the index backfill to `BackfillIndexes`, one bounded page per call.
+    // A backfill still pending from an earlier migration keeps its progress.
+    let mut steps = vec![];
+    if !PENDING_BACKFILL.exists(deps.storage) {
+        if !NEXT_STATUS_INDEX.exists(deps.storage) {
+            NEXT_STATUS_INDEX.save(deps.storage, &0u64)?;
+            steps.push(BackfillStep::StatusList);
+        }
+        if CREDENTIAL_EXPIRY.is_empty(deps.storage) {
+            steps.push(BackfillStep::Expiry);
+        }
+        if EXPIRY_SWEEP_CURSOR.exists(deps.storage) && SWEPT_CREDENTIALS.is_empty(deps.storage) {
+            steps.push(BackfillStep::SweptFlags);
+        }
+        if ISSUER_CREDENTIALS.is_empty(deps.storage) {
+            steps.push(BackfillStep::IssuerIndex);
+        }
+        // Counts from before holders were counted once are rebuilt from scratch
+        if ACTIVE_HOLDER_CREDENTIALS.is_empty(deps.storage) {
+            ACTIVE_CREDENTIALS.clear(deps.storage);
+            steps.push(BackfillStep::ActiveCounts);
+        }
+        if CREDENTIALS.is_empty(deps.storage) {
+            if steps.contains(&BackfillStep::StatusList) {
+                save_revocation_root(deps.storage, 0, env.block.time.seconds())?;
+            }
+            steps.clear();
+        }
+        if REQUESTS_BY_STATUS.is_empty(deps.storage)
+            && !VERIFICATION_REQUESTS.is_empty(deps.storage)
+        {
+            steps.push(BackfillStep::RequestStatus);
+        }
+        if !steps.is_empty() {
+            PENDING_BACKFILL.save(
+                deps.storage,
+                &Backfill {
+                    steps: steps.clone(),
+                    cursor: None,
+                },
+            )?;
+        }
+    }
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", version.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("backfill_steps", steps.len().to_string()))
+}
+
+fn execute_backfill_indexes(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can backfill indexes".to_string(),
+        });
+    }
+    let mut backfill = PENDING_BACKFILL
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No index backfill pending"))?;
+    let limit = limit.unwrap_or(30).clamp(1, 100) as usize;
+    let start = backfill.cursor.as_deref().map(Bound::exclusive);
+    let credential_pass = backfill
+        .steps
+        .iter()
+        .any(|step| *step != BackfillStep::RequestStatus);
+
+    let (processed, last) = if credential_pass {
+        let creds: Vec<Credential> = CREDENTIALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|r| r.map(|(_, c)| c))
+            .collect::<StdResult<Vec<_>>>()?;
+        for cred in &creds {
+            backfill_credential(deps.storage, &backfill.steps, cred)?;
+        }
+        let last = creds.last().map(|c| c.id.clone());
+        (creds.len(), last)
+    } else {
+        let requests: Vec<(String, VerificationStatus)> = VERIFICATION_REQUESTS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|r| r.map(|(id, request)| (id, request.status)))
+            .collect::<StdResult<Vec<_>>>()?;
+        for (request_id, status) in &requests {
+            REQUESTS_BY_STATUS.save(deps.storage, (status_key(status), request_id), &true)?;
+        }
+        let last = requests.last().map(|(id, _)| id.clone());
+        (requests.len(), last)
+    };
+
+    if processed == limit {
+        // A full page may have more entries after it
+        backfill.cursor = last;
+    } else if credential_pass {
+        if backfill.steps.contains(&BackfillStep::StatusList) {
+            save_revocation_root(deps.storage, 0, env.block.time.seconds())?;
+        }
+        backfill
+            .steps
+            .retain(|step| *step == BackfillStep::RequestStatus);
+        backfill.cursor = None;
+    } else {
+        backfill.steps.clear();
+    }
+    let done = backfill.steps.is_empty();
+    if done {
+        PENDING_BACKFILL.remove(deps.storage);
+    } else {
+        PENDING_BACKFILL.save(deps.storage, &backfill)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "backfill_indexes")
+        .add_attribute("processed", processed.to_string())
+        .add_attribute("done", done.to_string()))
+}
+
+/// Add one pre-existing credential to each index the backfill is building.
+fn backfill_credential(
+    storage: &mut dyn Storage,
+    steps: &[BackfillStep],
+    cred: &Credential,
+) -> StdResult<()> {
+    if steps.contains(&BackfillStep::StatusList) {
+        let index = assign_status_index(storage, &cred.id)?;
+        if cred.revoked {
+            status_list::set_revoked(storage, index)?;
+        }
+    }
+    if steps.contains(&BackfillStep::Expiry)
+        && !cred.revoked
+        && superseded_by(storage, &cred.id)?.is_none()
+    {
+        index_expiry(storage, cred)?;
+    }
+    if steps.contains(&BackfillStep::SweptFlags) {
+        if let Some((expires_at, id)) = EXPIRY_SWEEP_CURSOR.may_load(storage)? {
+            let key = (cred.expires_at, cred.id.as_str());
+            if CREDENTIAL_EXPIRY.has(storage, key) && key <= (expires_at, id.as_str()) {
+                SWEPT_CREDENTIALS.save(storage, &cred.id, &true)?;
+            }
+        }
+    }
+    if steps.contains(&BackfillStep::IssuerIndex) {
+        ISSUER_CREDENTIALS.save(storage, (&cred.issuer, &cred.id), &true)?;
+    }
+    if steps.contains(&BackfillStep::ActiveCounts) && counted_as_active(storage, cred)? {
+        count_active(storage, cred)?;
+    }
+    Ok(())
+}
+
+// ── Tests ───────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::{ReplyOn, SubMsgResult, Timestamp};
+
+    const VK: &str = include_str!("../testdata/citizenship_v1/verification_key.json");
+    const PROOF: &str = include_str!("../testdata/citizenship_v1/proof.json");
+    const PUBLIC: &str = include_str!("../testdata/citizenship_v1/public.json");
+    /// Commitment the fixed proof vector was generated for
+    const VECTOR_COMMITMENT: &str = "4044120335783968401";
+
+    fn vector_proof() -> ZkProof {
+        ZkProof {
+            proof_data: PROOF.to_string(),
+            public_inputs: from_json(PUBLIC).unwrap(),
+            vk_reference: "citizenship_v1".to_string(),
+        }
+    }
+
+    fn nullifier(value: &str) -> Nullifier {
+        Nullifier {
+            domain: "1".to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn add_relayers(mut deps: DepsMut, admin: &Addr, relayers: &[&Addr]) {
+        for relayer in relayers {
+            let msg = ExecuteMsg::AddRelayer {
+                relayer: relayer.to_string(),
+            };
+            execute(deps.branch(), mock_env(), message_info(admin, &[]), msg).unwrap();
+        }
+    }
+
+    fn open_request(deps: DepsMut, applicant: &Addr, data_hash: &str) -> String {
+        let msg = ExecuteMsg::RequestVerification {
+            verification_type: VerificationType::GovernmentId,
+            data_hash: data_hash.to_string(),
+        };
+        let res = execute(deps, mock_env(), message_info(applicant, &[]), msg).unwrap();
+        res.attributes[1].value.clone()
+    }
+
+    fn issue_citizenship(deps: DepsMut, holder: &Addr, nullifier_value: &str) -> String {
+        let issuer1 = MockApi::default().addr_make("issuer1");
+        let msg = ExecuteMsg::IssueCredential {
+            holder: holder.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier(nullifier_value),
+            attributes_root: None,
+        };
+        let res = execute(deps, mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        res.attributes[1].value.clone()
+    }
+
+    fn request_vouching(deps: DepsMut, applicant: &Addr, nullifier_value: &str) -> String {
+        let msg = ExecuteMsg::RequestSocialVouching {
+            data_hash: format!("hash-{}", nullifier_value),
+            commitment: "abc123".to_string(),
+            nullifier: nullifier(nullifier_value),
+        };
+        let res = execute(deps, mock_env(), message_info(applicant, &[]), msg).unwrap();
+        res.attributes[1].value.clone()
+    }
+
+    fn issue_vector_credential(deps: DepsMut, commitment: &str) -> String {
+        let api = MockApi::default();
+        let info = message_info(&api.addr_make("issuer1"), &[]);
+        let msg = ExecuteMsg::IssueCredential {
+            holder: api.addr_make("citizen1").to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: commitment.to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: None,
+        };
+        let res = execute(deps, mock_env(), info, msg).unwrap();
+        res.attributes
+            .iter()
+            .find(|a| a.key == "credential_id")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    fn setup_contract(mut deps: DepsMut) {
+        let api = MockApi::default();
+        let admin = api.addr_make("admin");
+        let issuer1 = api.addr_make("issuer1");
+        let creator = api.addr_make("creator");
+        let msg = InstantiateMsg {
+            admin: admin.to_string(),
+            issuers: vec![issuer1.to_string()],
+            verification_config: None,
+        };
+        let info = message_info(&creator, &[]);
+        instantiate(deps.branch(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::RegisterVerificationKey {
+            credential_type: CredentialType::Citizenship,
+            version: "citizenship_v1".to_string(),
+            verification_key: from_json(VK).unwrap(),
+        };
+        execute(deps, mock_env(), message_info(&admin, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut());
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.admin, admin.to_string());
+        assert_eq!(config.total_credentials, 0);
+    }
+
+    #[test]
+    fn test_issue_credential() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let citizen1 = deps.api.addr_make("citizen1");
+        setup_contract(deps.as_mut());
+
+        let info = message_info(&issuer1, &[]);
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(res.attributes[0].value, "issue_credential");
+
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.total_credentials, 1);
+    }
+
+    #[test]
+    fn test_unauthorized_issuer() {
+        let mut deps = mock_dependencies();
+        let random = deps.api.addr_make("random");
+        let citizen1 = deps.api.addr_make("citizen1");
+        setup_contract(deps.as_mut());
+
+        let info = message_info(&random, &[]);
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::IssuerNotAuthorized { .. }));
+    }
+
+    #[test]
+    fn test_revocation_notifies_hooks() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let citizen1 = deps.api.addr_make("citizen1");
+        let grants = deps.api.addr_make("grants");
+        setup_contract(deps.as_mut());
+
+        let msg = ExecuteMsg::AddHook {
+            contract: grants.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let msg = ExecuteMsg::SetHookGasLimit { gas_limit: 0 };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        let msg = ExecuteMsg::SetHookGasLimit { gas_limit: 300_000 };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&issuer1, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert_eq!(query_hooks(deps.as_ref()).unwrap().gas_limit, 300_000);
+        let cred_id = issue_citizenship(deps.as_mut(), &citizen1, "1");
+
+        let msg = ExecuteMsg::RevokeCredential {
+            credential_id: cred_id.clone(),
+            reason: "test revocation".to_string(),
+            fraud: false,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+
+        // One gas-capped sub-message per hook, replying only on failure
+        let expected = CredentialHookMsg::Revoked {
+            credential_id: cred_id,
+            holder: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+        };
+        assert_eq!(res.messages.len(), 1);
+        let sub = &res.messages[0];
+        assert_eq!(sub.id, HOOK_REPLY_ID);
+        assert_eq!(sub.reply_on, ReplyOn::Error);
+        assert_eq!(sub.gas_limit, Some(300_000));
+        assert_eq!(sub.msg, expected.into_cosmos_msg(grants.as_str()).unwrap());
+        assert_eq!(sub.payload, to_json_binary(&grants).unwrap());
+    }
+
+    #[test]
+    fn test_reply_records_hook_failure() {
+        let mut deps = mock_dependencies();
+        let grants = deps.api.addr_make("grants");
+        setup_contract(deps.as_mut());
+
+        let reply_msg = |id| Reply {
+            id,
+            payload: to_json_binary(&grants).unwrap(),
+            gas_used: 0,
+            result: SubMsgResult::Err("grant contract paused".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg(HOOK_REPLY_ID)).unwrap();
+        assert_eq!(res.attributes[0].value, "credential_hook_failed");
+        assert_eq!(res.attributes[1].value, grants.to_string());
+        assert_eq!(res.attributes[2].value, "grant contract paused");
+
+        assert!(reply(deps.as_mut(), mock_env(), reply_msg(HOOK_REPLY_ID + 1)).is_err());
+    }
+
+    #[test]
+    fn test_revoke_credential() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let citizen1 = deps.api.addr_make("citizen1");
+        setup_contract(deps.as_mut());
+
+        // Issue first
+        let info = message_info(&issuer1, &[]);
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let cred_id = res
+            .attributes
+            .iter()
+            .find(|a| a.key == "credential_id")
+            .unwrap()
+            .value
+            .clone();
+
+        // Revoke
+        let revoke_msg = ExecuteMsg::RevokeCredential {
+            credential_id: cred_id.clone(),
+            reason: "test revocation".to_string(),
+            fraud: false,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), revoke_msg.clone()).unwrap();
+
+        let cred = query_credential(deps.as_ref(), mock_env(), cred_id, None).unwrap();
+        assert!(cred.revoked);
+
+        // Revoking again changes nothing
+        let epoch = query_revocation_root(deps.as_ref(), None).unwrap().epoch;
+        let res = execute(deps.as_mut(), mock_env(), info, revoke_msg).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            query_revocation_root(deps.as_ref(), None).unwrap().epoch,
+            epoch
+        );
+    }
+
+    #[test]
+    fn test_add_remove_issuer() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer2 = deps.api.addr_make("issuer2");
+        setup_contract(deps.as_mut());
+
+        let admin_info = message_info(&admin, &[]);
+
+        // Add issuer
+        let msg = ExecuteMsg::AddIssuer {
+            issuer: issuer2.to_string(),
+            scope: None,
+        };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+
+        let issuers = query_issuers(deps.as_ref()).unwrap();
+        assert_eq!(issuers.issuers.len(), 2);
+
+        // Remove issuer
+        let msg = ExecuteMsg::RemoveIssuer {
+            issuer: issuer2.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap();
+
+        let issuers = query_issuers(deps.as_ref()).unwrap();
+        assert_eq!(issuers.issuers.len(), 1);
+    }
+
+    #[test]
+    fn test_scoped_issuer_limits() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer2 = deps.api.addr_make("issuer2");
+        let holder = deps.api.addr_make("holder");
+        setup_contract(deps.as_mut());
+
+        // The unscoped form from before scopes existed still parses
+        let legacy: ExecuteMsg =
+            from_json(format!(r#"{{"add_issuer":{{"issuer":"{}"}}}}"#, issuer2)).unwrap();
+        assert!(matches!(legacy, ExecuteMsg::AddIssuer { scope: None, .. }));
+
+        let scope = IssuerScope {
+            credential_types: vec![CredentialType::AgeOver18],
+            max_validity_secs: Some(1_000),
+            issuance_limit: Some(IssuanceLimit {
+                max_per_period: 2,
+                period_secs: 86_400,
+            }),
+        };
+        let msg = ExecuteMsg::AddIssuer {
+            issuer: issuer2.to_string(),
+            scope: Some(scope.clone()),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        let issuers = query_issuers(deps.as_ref()).unwrap().issuers;
+        let listed = issuers
+            .iter()
+            .find(|i| i.issuer == issuer2.to_string())
+            .unwrap();
+        assert_eq!(listed.scope, Some(scope));
+
+        let now = mock_env().block.time.seconds();
+        let issue = |deps: DepsMut,
+                     env: Env,
+                     credential_type: CredentialType,
+                     expires_at: u64,
+                     nullifier_value: &str| {
+            let msg = ExecuteMsg::IssueCredential {
+                holder: holder.to_string(),
+                credential_type,
+                commitment: format!("commit-{}", nullifier_value),
+                expires_at,
+                nullifier: nullifier(nullifier_value),
+                attributes_root: None,
+            };
+            execute(deps, env, message_info(&issuer2, &[]), msg)
+        };
+
+        // Trusted for age only, and only for short-lived credentials
+        let err = issue(
+            deps.as_mut(),
+            mock_env(),
+            CredentialType::Citizenship,
+            now + 500,
+            "1",
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        for expires_at in [0, now + 1_001] {
+            let err = issue(
+                deps.as_mut(),
+                mock_env(),
+                CredentialType::AgeOver18,
+                expires_at,
+                "1",
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::Unauthorized { .. }));
+        }
+
+        issue(
+            deps.as_mut(),
+            mock_env(),
+            CredentialType::AgeOver18,
+            now + 1_000,
+            "1",
+        )
+        .unwrap();
+        issue(
+            deps.as_mut(),
+            mock_env(),
+            CredentialType::AgeOver18,
+            now + 500,
+            "2",
+        )
+        .unwrap();
+        let err = issue(
+            deps.as_mut(),
+            mock_env(),
+            CredentialType::AgeOver18,
+            now + 500,
+            "3",
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // The allowance resets with the next period
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(86_400);
+        let later = env.block.time.seconds();
+        issue(
+            deps.as_mut(),
+            env,
+            CredentialType::AgeOver18,
+            later + 500,
+            "3",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_compromised_issuer_cutoff_and_bulk_revoke() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let early = deps.api.addr_make("early");
+        let late = deps.api.addr_make("late");
+        let later = deps.api.addr_make("later");
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &early, "1");
+
+        let now = mock_env().block.time.seconds();
+        for (i, holder) in [&late, &later].into_iter().enumerate() {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(200 + i as u64);
+            let msg = ExecuteMsg::IssueCredential {
+                holder: holder.to_string(),
+                credential_type: CredentialType::Citizenship,
+                commitment: "abc123".to_string(),
+                expires_at: 0,
+                nullifier: nullifier(&(i + 2).to_string()),
+                attributes_root: None,
+            };
+            execute(deps.as_mut(), env, message_info(&issuer1, &[]), msg).unwrap();
+        }
+        let by_issuer =
+            query_list_by_issuer(deps.as_ref(), issuer1.to_string(), None, None).unwrap();
+        assert_eq!(by_issuer.credentials.len(), 3);
+
+        // Suspension only stops new issuance
+        let msg = ExecuteMsg::SetIssuerStatus {
+            issuer: issuer1.to_string(),
+            status: IssuerStatus::Suspended,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let msg = ExecuteMsg::IssueCredential {
+            holder: admin.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("9"),
+            attributes_root: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::IssuerNotAuthorized { .. }));
+        let has = |deps: Deps, holder: &Addr| {
+            query_has_valid(
+                deps,
+                mock_env(),
+                holder.to_string(),
+                CredentialType::Citizenship,
+                None,
+            )
+            .unwrap()
+            .has_credential
+        };
+        assert!(has(deps.as_ref(), &late));
+
+        // Credentials from the compromised window stop counting without being touched
+        let msg = ExecuteMsg::SetIssuerStatus {
+            issuer: issuer1.to_string(),
+            status: IssuerStatus::Compromised { since: now + 100 },
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert_eq!(res.attributes[2].value, "compromised");
+        assert_eq!(res.attributes[3].value, (now + 100).to_string());
+        assert!(has(deps.as_ref(), &early));
+        assert!(!has(deps.as_ref(), &late));
+        assert!(!has(deps.as_ref(), &later));
+        assert_eq!(
+            query_issuer_status(deps.as_ref(), issuer1.to_string())
+                .unwrap()
+                .status,
+            IssuerStatus::Compromised { since: now + 100 }
+        );
+
+        // Bulk revocation pages through the issuer index from the cutoff
+        let mut start_after = None;
+        let mut revoked = 0;
+        loop {
+            let msg = ExecuteMsg::RevokeIssuerCredentials {
+                issuer: issuer1.to_string(),
+                issued_since: None,
+                reason: "issuer key compromised".to_string(),
+                start_after,
+                limit: Some(1),
+            };
+            let res = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+            let attr = |key: &str| {
+                res.attributes
+                    .iter()
+                    .find(|a| a.key == key)
+                    .unwrap()
+                    .value
+                    .clone()
+            };
+            revoked += attr("revoked").parse::<u32>().unwrap();
+            let next = attr("next_start_after");
+            if next.is_empty() {
+                break;
+            }
+            start_after = Some(next);
+        }
+        assert_eq!(revoked, 2);
+        let creds = query_list_by_issuer(deps.as_ref(), issuer1.to_string(), None, None)
+            .unwrap()
+            .credentials;
+        assert_eq!(creds.iter().filter(|c| c.revoked).count(), 2);
+        assert!(has(deps.as_ref(), &early));
+    }
+
+    #[test]
+    fn test_revocation_updates_status_root() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        setup_contract(deps.as_mut());
+        let alice_cred = issue_citizenship(deps.as_mut(), &alice, "1");
+        let bob_cred = issue_citizenship(deps.as_mut(), &bob, "2");
+
+        let alice_index = query_credential(deps.as_ref(), mock_env(), alice_cred.clone(), None)
+            .unwrap()
+            .status_index
+            .unwrap();
+        let bob_index = query_credential(deps.as_ref(), mock_env(), bob_cred, None)
+            .unwrap()
+            .status_index
+            .unwrap();
+        assert_ne!(alice_index, bob_index);
+
+        let before = query_revocation_root(deps.as_ref(), None).unwrap();
+        assert_eq!(before.epoch, 0);
+
+        let msg = ExecuteMsg::RevokeCredential {
+            credential_id: alice_cred,
+            reason: "test".to_string(),
+            fraud: false,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+
+        let after = query_revocation_root(deps.as_ref(), None).unwrap();
+        assert_eq!(after.epoch, 1);
+        assert_ne!(after.root, before.root);
+        // Earlier roots stay queryable for proofs made against them
+        assert_eq!(
+            query_revocation_root(deps.as_ref(), Some(0)).unwrap().root,
+            before.root
+        );
+
+        // Witnesses are looked up by status index, never by credential id
+        let status_bit = |witness: &RevocationWitnessResponse, index: u64| {
+            let chunk = hex::decode(&witness.chunk).unwrap();
+            let bit = index % status_list::CHUNK_BITS;
+            (chunk[(bit / 8) as usize] >> (bit % 8)) & 1
+        };
+        let witness = query_revocation_witness(deps.as_ref(), bob_index).unwrap();
+        assert_eq!(witness.root, after.root);
+        assert_eq!(witness.siblings.len(), status_list::DEPTH as usize);
+        assert_eq!(status_bit(&witness, bob_index), 0);
+        let witness = query_revocation_witness(deps.as_ref(), alice_index).unwrap();
+        assert_eq!(status_bit(&witness, alice_index), 1);
+    }
+
+    #[test]
+    fn test_custom_types_require_registered_schema() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let issuer2 = deps.api.addr_make("issuer2");
+        let holder = deps.api.addr_make("holder");
+        setup_contract(deps.as_mut());
+        let msg = ExecuteMsg::AddIssuer {
+            issuer: issuer2.to_string(),
+            scope: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        let issue = |deps: DepsMut, issuer: &Addr, name: &str| {
+            let msg = ExecuteMsg::IssueCredential {
+                holder: holder.to_string(),
+                credential_type: CredentialType::Custom(name.to_string()),
+                commitment: "abc123".to_string(),
+                expires_at: 0,
+                nullifier: nullifier("5"),
+                attributes_root: None,
+            };
+            execute(deps, mock_env(), message_info(issuer, &[]), msg)
+        };
+
+        let err = issue(deps.as_mut(), &issuer1, "library_card").unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::CredentialSchemaNotFound { .. }
+        ));
+
+        let mut schema = CredentialSchema {
+            description: "Municipal library membership".to_string(),
+            attributes: vec!["branch".to_string()],
+            allowed_issuers: vec![issuer1.to_string()],
+            default_validity_secs: 31_536_000,
+            verification_key: Some("library_v1".to_string()),
+            private: false,
+            signal_layout: SignalLayout::Citizenship,
+        };
+        // The pinned verification key has to exist first
+        let msg = ExecuteMsg::RegisterCredentialSchema {
+            name: "library_card".to_string(),
+            schema: schema.clone(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::VerificationKeyNotFound { .. }));
+
+        schema.verification_key = None;
+        let msg = ExecuteMsg::RegisterCredentialSchema {
+            name: "library_card".to_string(),
+            schema: schema.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&issuer1, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::CredentialSchemaAlreadyExists { .. }
+        ));
+
+        // Only the schema's issuers, and typos stay unregistered
+        let err = issue(deps.as_mut(), &issuer2, "library_card").unwrap_err();
+        assert!(matches!(err, ContractError::IssuerNotAuthorized { .. }));
+        let err = issue(deps.as_mut(), &issuer1, "libary_card").unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::CredentialSchemaNotFound { .. }
+        ));
+
+        let res = issue(deps.as_mut(), &issuer1, "library_card").unwrap();
+        let cred = query_credential(
+            deps.as_ref(),
+            mock_env(),
+            res.attributes[1].value.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            cred.expires_at,
+            mock_env().block.time.seconds() + 31_536_000
+        );
+
+        let listed = query_schemas(deps.as_ref(), None, None).unwrap().schemas;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].schema, schema);
+        assert_eq!(
+            query_schema(deps.as_ref(), "library_card".to_string())
+                .unwrap()
+                .schema
+                .description,
+            "Municipal library membership"
+        );
+
+        // Updates go through their own message and report what they replaced
+        let mut updated = schema.clone();
+        updated.allowed_issuers.push(issuer2.to_string());
+        let msg = ExecuteMsg::UpdateCredentialSchema {
+            name: "library_card".to_string(),
+            schema: updated.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let previous: CredentialSchema = from_json(&res.attributes[2].value).unwrap();
+        assert_eq!(previous, schema);
+        assert_eq!(
+            query_schema(deps.as_ref(), "library_card".to_string())
+                .unwrap()
+                .schema,
+            updated
+        );
+    }
+
+    fn issue_with_attributes(deps: DepsMut, holder: &Addr, root: &str) -> String {
+        let issuer1 = MockApi::default().addr_make("issuer1");
+        let msg = ExecuteMsg::IssueCredential {
+            holder: holder.to_string(),
+            credential_type: CredentialType::Residency,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: Some(root.to_string()),
+        };
+        let res = execute(deps, mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        res.attributes[1].value.clone()
+    }
+
+    #[test]
+    fn test_disclose_revealed_attribute() {
+        let mut deps = mock_dependencies();
+        let holder = deps.api.addr_make("holder");
+        setup_contract(deps.as_mut());
+
+        let region = merkle::attribute_leaf("region", "north", "salt-1");
+        let birth_year = merkle::attribute_leaf("birth_year", "1990", "salt-2");
+        let root = merkle::node_hash(&region, &birth_year);
+        let cred_id = issue_with_attributes(deps.as_mut(), &holder, &merkle::to_field(&root));
+
+        let reveal = |value: &str| Disclosure::Reveal {
+            attribute: "region".to_string(),
+            value: value.to_string(),
+            salt: "salt-1".to_string(),
+            position: 0,
+            siblings: vec![hex::encode(birth_year)],
+        };
+        let result = query_verify_disclosure(
+            deps.as_ref(),
+            mock_env(),
+            cred_id.clone(),
+            vec![reveal("north")],
+        )
+        .unwrap();
+        assert!(result.valid, "{}", result.message);
+        assert_eq!(
+            result.claims,
+            vec![AttributeClaim {
+                attribute: "region".to_string(),
+                op: PredicateOp::Eq,
+                value: "north".to_string(),
+            }]
+        );
+
+        let result =
+            query_verify_disclosure(deps.as_ref(), mock_env(), cred_id, vec![reveal("south")])
+                .unwrap();
+        assert!(!result.valid);
+        assert!(result.claims.is_empty());
+    }
+
+    #[test]
+    fn test_disclose_predicate_with_proof() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let holder = deps.api.addr_make("holder");
+        setup_contract(deps.as_mut());
+
+        // The fixed vector's signals have the predicate layout
+        // [valid, attributes_root, current_time, value] for `age gte 18 years`
+        let msg = ExecuteMsg::RegisterPredicateKey {
+            attribute: "age".to_string(),
+            op: PredicateOp::Gte,
+            version: "citizenship_v1".to_string(),
+            verification_key: from_json(VK).unwrap(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let cred_id = issue_with_attributes(deps.as_mut(), &holder, VECTOR_COMMITMENT);
+
+        let claim = |value: &str| AttributeClaim {
+            attribute: "age".to_string(),
+            op: PredicateOp::Gte,
+            value: value.to_string(),
+        };
+        let disclose = |claim: AttributeClaim| {
+            vec![Disclosure::Predicate {
+                claim,
+                proof: vector_proof(),
+            }]
+        };
+
+        let result = query_verify_disclosure(
+            deps.as_ref(),
+            mock_env(),
+            cred_id.clone(),
+            disclose(claim("567648000")),
+        )
+        .unwrap();
+        assert!(result.valid, "{}", result.message);
+        assert_eq!(result.claims, vec![claim("567648000")]);
+
+        // The proof says nothing about a stronger claim or a different op
+        let result = query_verify_disclosure(
+            deps.as_ref(),
+            mock_env(),
+            cred_id.clone(),
+            disclose(claim("662256000")),
+        )
+        .unwrap();
+        assert!(!result.valid);
+        let mut lte = claim("567648000");
+        lte.op = PredicateOp::Lte;
+        let result =
+            query_verify_disclosure(deps.as_ref(), mock_env(), cred_id.clone(), disclose(lte))
+                .unwrap();
+        assert!(!result.valid);
+
+        // A deprecated predicate circuit stops backing claims
+        let msg = ExecuteMsg::DeprecatePredicateKey {
+            attribute: "age".to_string(),
+            op: PredicateOp::Gte,
+            version: "citizenship_v1".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let result = query_verify_disclosure(
+            deps.as_ref(),
+            mock_env(),
+            cred_id,
+            disclose(claim("567648000")),
+        )
+        .unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn test_shared_registry_queries() {
+        use citizen_common::registry::RegistryQueryMsg;
+        let mut deps = mock_dependencies();
+        let holder = deps.api.addr_make("citizen1");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_citizenship(deps.as_mut(), &holder, "1");
+
+        // Other contracts' typed queries are understood as registry queries
+        let shared = RegistryQueryMsg::HasValidCredential {
+            holder: holder.to_string(),
+            credential_type: CredentialType::Citizenship,
+            requester: None,
+        };
+        let msg: QueryMsg = from_json(to_json_binary(&shared).unwrap()).unwrap();
+        let res: HasCredentialResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.credential_id, Some(cred_id));
+        let shared = RegistryQueryMsg::CountValidCredentials {
+            credential_type: CredentialType::Citizenship,
+        };
+        let msg: QueryMsg = from_json(to_json_binary(&shared).unwrap()).unwrap();
+        let res: CredentialCountResponse =
+            from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.count, 1);
+    }
+
+    #[test]
+    fn test_count_valid_credentials() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        setup_contract(deps.as_mut());
+        let now = mock_env().block.time.seconds();
+        let mut ids = vec![];
+        for (i, expires_at) in [0, now + 100, 0].into_iter().enumerate() {
+            let msg = ExecuteMsg::IssueCredential {
+                holder: deps.api.addr_make(&format!("holder{}", i)).to_string(),
+                credential_type: CredentialType::Citizenship,
+                commitment: "abc123".to_string(),
+                expires_at,
+                nullifier: nullifier(&(i + 1).to_string()),
+                attributes_root: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+            ids.push(res.attributes[1].value.clone());
+        }
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(150);
+        let count = |deps: Deps| {
+            query_count_valid(deps, later.clone(), CredentialType::Citizenship)
+                .unwrap()
+                .count
+        };
+        assert_eq!(
+            query_count_valid(deps.as_ref(), mock_env(), CredentialType::Citizenship)
+                .unwrap()
+                .count,
+            3
+        );
+        assert_eq!(
+            query_count_valid(deps.as_ref(), mock_env(), CredentialType::Residency)
+                .unwrap()
+                .count,
+            0
+        );
+
+        // An expired credential stops counting before the sweep passes it
+        assert_eq!(count(deps.as_ref()), 2);
+        let sweep = ExecuteMsg::SweepExpired { limit: None };
+        execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&admin, &[]),
+            sweep,
+        )
+        .unwrap();
+        assert_eq!(count(deps.as_ref()), 2);
+
+        // Revoking it afterwards doesn't take it out twice
+        for id in &ids[..2] {
+            let msg = ExecuteMsg::RevokeCredential {
+                credential_id: id.clone(),
+                reason: "test".to_string(),
+                fraud: false,
+            };
+            execute(deps.as_mut(), later.clone(), message_info(&admin, &[]), msg).unwrap();
+        }
+        assert_eq!(count(deps.as_ref()), 1);
+
+        // A renewal replaces its predecessor in the count
+        let msg = ExecuteMsg::RenewCredential {
+            credential_id: ids[2].clone(),
+            commitment: "def456".to_string(),
+            expires_at: 0,
+            attributes_root: None,
+        };
+        execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&issuer1, &[]),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(count(deps.as_ref()), 1);
+
+        // Credentials from a compromised key leave the count, and come back if
+        // the compromise is lifted
+        let set_status = |deps: DepsMut, status: IssuerStatus| {
+            let msg = ExecuteMsg::SetIssuerStatus {
+                issuer: issuer1.to_string(),
+                status,
+            };
+            execute(deps, later.clone(), message_info(&admin, &[]), msg).unwrap();
+        };
+        set_status(deps.as_mut(), IssuerStatus::Compromised { since: now });
+        assert_eq!(count(deps.as_ref()), 0);
+        set_status(deps.as_mut(), IssuerStatus::Active);
+        assert_eq!(count(deps.as_ref()), 1);
+
+        // Expiries that have already passed are refused
+        let msg = ExecuteMsg::IssueCredential {
+            holder: deps.api.addr_make("holder9").to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: later.block.time.seconds(),
+            nullifier: nullifier("9"),
+            attributes_root: None,
+        };
+        let err = execute(deps.as_mut(), later, message_info(&issuer1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::CredentialExpired));
+    }
+
+    #[test]
+    fn test_count_valid_counts_holders_once() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let holder = deps.api.addr_make("holder0");
+        let other = deps.api.addr_make("holder1");
+        let new_wallet = deps.api.addr_make("new_wallet");
+        setup_contract(deps.as_mut());
+        let now = mock_env().block.time.seconds();
+        for (i, (holder, expires_at)) in [(&holder, 0), (&holder, now + 100), (&other, now + 100)]
+            .into_iter()
+            .enumerate()
+        {
+            let msg = ExecuteMsg::IssueCredential {
+                holder: holder.to_string(),
+                credential_type: CredentialType::Citizenship,
+                commitment: format!("abc{}", i),
+                expires_at,
+                nullifier: nullifier(&(i + 1).to_string()),
+                attributes_root: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        }
+        let count = |deps: Deps, env: Env| {
+            query_count_valid(deps, env, CredentialType::Citizenship)
+                .unwrap()
+                .count
+        };
+        // Two credentials of one holder make one citizen
+        assert_eq!(count(deps.as_ref(), mock_env()), 2);
+
+        // Unswept expiries only drop holders left without a valid credential
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(150);
+        assert_eq!(count(deps.as_ref(), later.clone()), 1);
+
+        // A recovered holder still counts once
+        migrate_holder(deps.as_mut().storage, &holder, &new_wallet).unwrap();
+        assert_eq!(count(deps.as_ref(), mock_env()), 2);
+        assert_eq!(count(deps.as_ref(), later.clone()), 1);
+        let sweep = ExecuteMsg::SweepExpired { limit: None };
+        execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&issuer1, &[]),
+            sweep,
+        )
+        .unwrap();
+        assert_eq!(count(deps.as_ref(), later), 1);
+    }
+
+    #[test]
+    fn test_migrate_backfills_indexes_in_pages() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let applicant = deps.api.addr_make("applicant");
+        setup_contract(deps.as_mut());
+        let now = mock_env().block.time.seconds();
+        for (i, expires_at) in [0, now + 100, 0].into_iter().enumerate() {
+            let msg = ExecuteMsg::IssueCredential {
+                holder: deps.api.addr_make(&format!("holder{}", i)).to_string(),
+                credential_type: CredentialType::Citizenship,
+                commitment: "abc123".to_string(),
+                expires_at,
+                nullifier: nullifier(&(i + 1).to_string()),
+                attributes_root: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        }
+        open_request(deps.as_mut(), &applicant, "hash1");
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(150);
+        let sweep = ExecuteMsg::SweepExpired { limit: None };
+        execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&admin, &[]),
+            sweep,
+        )
+        .unwrap();
+
+        // Drop the indexes a deployment from before them would lack
+        ACTIVE_CREDENTIALS.clear(deps.as_mut().storage);
+        ACTIVE_HOLDER_CREDENTIALS.clear(deps.as_mut().storage);
+        ISSUER_CREDENTIALS.clear(deps.as_mut().storage);
+        SWEPT_CREDENTIALS.clear(deps.as_mut().storage);
+        REQUESTS_BY_STATUS.clear(deps.as_mut().storage);
+        let res = migrate(deps.as_mut(), later.clone(), MigrateMsg {}).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "backfill_steps" && a.value == "4"));
+
+        // Nothing else runs until the backfill is done
+        let issue = ExecuteMsg::IssueCredential {
+            holder: deps.api.addr_make("holder9").to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("9"),
+            attributes_root: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&issuer1, &[]),
+            issue.clone(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("backfilled"));
+        let backfill = ExecuteMsg::BackfillIndexes { limit: Some(2) };
+        let err = execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&issuer1, &[]),
+            backfill.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // Three credentials take two pages, then the request pass finishes
+        let mut done = vec![];
+        for _ in 0..3 {
+            let res = execute(
+                deps.as_mut(),
+                later.clone(),
+                message_info(&admin, &[]),
+                backfill.clone(),
+            )
+            .unwrap();
+            let attr = res.attributes.iter().find(|a| a.key == "done").unwrap();
+            done.push(attr.value == "true");
+        }
+        assert_eq!(done, vec![false, false, true]);
+        assert!(!PENDING_BACKFILL.exists(deps.as_ref().storage));
+
+        let count =
+            query_count_valid(deps.as_ref(), later.clone(), CredentialType::Citizenship).unwrap();
+        assert_eq!(count.count, 2);
+        let by_issuer =
+            query_list_by_issuer(deps.as_ref(), issuer1.to_string(), None, None).unwrap();
+        assert_eq!(by_issuer.credentials.len(), 3);
+        let pending = query_requests_by_status(
+            deps.as_ref(),
+            later.clone(),
+            VerificationStatus::Pending,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(pending.requests.len(), 1);
+        execute(deps.as_mut(), later, message_info(&issuer1, &[]), issue).unwrap();
+    }
+
+    #[test]
+    fn test_renewal_preserves_lineage() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let issuer2 = deps.api.addr_make("issuer2");
+        let holder = deps.api.addr_make("holder");
+        setup_contract(deps.as_mut());
+        let msg = ExecuteMsg::AddIssuer {
+            issuer: issuer2.to_string(),
+            scope: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let first = issue_citizenship(deps.as_mut(), &holder, "1");
+
+        let renew = |credential_id: &str, commitment: &str| ExecuteMsg::RenewCredential {
+            credential_id: credential_id.to_string(),
+            commitment: commitment.to_string(),
+            expires_at: 0,
+            attributes_root: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&issuer2, &[]),
+            renew(&first, "def456"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&issuer1, &[]),
+            renew(&first, "def456"),
+        )
+        .unwrap();
+        let second = res.attributes[1].value.clone();
+
+        // The old credential is superseded, not revoked, and can't be renewed twice
+        let old = query_credential(deps.as_ref(), mock_env(), first.clone(), None).unwrap();
+        assert!(!old.revoked);
+        assert_eq!(old.superseded_by, Some(second.clone()));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&issuer1, &[]),
+            renew(&first, "ghi789"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::CredentialSuperseded { .. }));
+        assert_eq!(query_revocation_root(deps.as_ref(), None).unwrap().epoch, 1);
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&issuer1, &[]),
+            renew(&second, "ghi789"),
+        )
+        .unwrap();
+        let third = res.attributes[1].value.clone();
+
+        let has = query_has_valid(
+            deps.as_ref(),
+            mock_env(),
+            holder.to_string(),
+            CredentialType::Citizenship,
+            None,
+        )
+        .unwrap();
+        assert_eq!(has.credential_id, Some(third.clone()));
+        assert_eq!(has.lineage_id, Some(first.clone()));
+
+        let lineage = query_lineage(deps.as_ref(), second.clone()).unwrap();
+        assert_eq!(lineage.lineage_id, first);
+        assert_eq!(lineage.credential_ids, vec![first, second, third.clone()]);
+        assert_eq!(lineage.current, third);
+
+        // The identity's nullifier follows the lineage
+        let record = NULLIFIERS.load(&deps.storage, ("1", "1")).unwrap();
+        assert_eq!(record.credential_id, third);
+    }
+
+    #[test]
+    fn test_expiry_queries_and_sweep() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let now = mock_env().block.time.seconds();
+
+        let mut ids = vec![];
+        for (i, expires_at) in [now + 100, now + 200, now + 1000, 0]
+            .into_iter()
+            .enumerate()
+        {
+            let msg = ExecuteMsg::IssueCredential {
+                holder: deps.api.addr_make(&format!("holder{}", i)).to_string(),
+                credential_type: CredentialType::Citizenship,
+                commitment: "abc123".to_string(),
+                expires_at,
+                nullifier: nullifier(&(i + 1).to_string()),
+                attributes_root: None,
+            };
+            let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+            ids.push(res.attributes[1].value.clone());
+        }
+        let listed = |resp: CredentialsListResponse| -> Vec<String> {
+            resp.credentials.into_iter().map(|c| c.id).collect()
+        };
+
+        let expiring = query_expiring(deps.as_ref(), mock_env(), now + 500, None, None).unwrap();
+        assert_eq!(listed(expiring), ids[..2].to_vec());
+        let page = query_expiring(
+            deps.as_ref(),
+            mock_env(),
+            now + 500,
+            Some(ids[0].clone()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(listed(page), ids[1..2].to_vec());
+
+        // Once the first lapses it moves from expiring to expired
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(150);
+        let expiring = query_expiring(deps.as_ref(), later.clone(), now + 500, None, None).unwrap();
+        assert_eq!(listed(expiring), ids[1..2].to_vec());
+        let expired = query_expired(deps.as_ref(), later.clone(), None, None).unwrap();
+        assert_eq!(listed(expired), ids[..1].to_vec());
+
+        let sweep = ExecuteMsg::SweepExpired { limit: None };
+        let res = execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&anyone, &[]),
+            sweep.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events[0].ty, "credential_expired");
+        assert_eq!(res.events[0].attributes[0].value, ids[0]);
+        assert!(
+            query_credential(deps.as_ref(), mock_env(), ids[0].clone(), None)
+                .unwrap()
+                .expired
+        );
+        assert!(
+            !query_credential(deps.as_ref(), mock_env(), ids[1].clone(), None)
+                .unwrap()
+                .expired
+        );
+
+        // Sweeping again only picks up what expired since
+        let res = execute(
+            deps.as_mut(),
+            later.clone(),
+            message_info(&anyone, &[]),
+            sweep.clone(),
+        )
+        .unwrap();
+        assert!(res.events.is_empty());
+
+        // Revoked credentials leave the index
+        let msg = ExecuteMsg::RevokeCredential {
+            credential_id: ids[2].clone(),
+            reason: "test".to_string(),
+            fraud: false,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let mut much_later = mock_env();
+        much_later.block.time = much_later.block.time.plus_seconds(5000);
+        let expired = query_expired(deps.as_ref(), much_later.clone(), None, None).unwrap();
+        assert_eq!(listed(expired), ids[..2].to_vec());
+        let res = execute(deps.as_mut(), much_later, message_info(&anyone, &[]), sweep).unwrap();
+        assert_eq!(res.events.len(), 1);
+        assert_eq!(res.events[0].attributes[0].value, ids[1]);
+    }
+
+    #[test]
+    fn test_full_status_list_rejects_before_writes() {
+        let mut deps = mock_dependencies();
+        let holder = deps.api.addr_make("citizen1");
+        setup_contract(deps.as_mut());
+        NEXT_STATUS_INDEX
+            .save(deps.as_mut().storage, &status_list::CAPACITY)
+            .unwrap();
+
+        let issuer1 = deps.api.addr_make("issuer1");
+        let msg = ExecuteMsg::IssueCredential {
+            holder: holder.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap_err();
+        assert!(
+            !query_nullifier(deps.as_ref(), nullifier("1"))
+                .unwrap()
+                .spent
+        );
+        assert_eq!(query_config(deps.as_ref()).unwrap().total_credentials, 0);
+    }
+
+    fn issuance_entry(holder: &Addr, nullifier_value: &str) -> IssuanceEntry {
+        IssuanceEntry {
+            holder: holder.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier(nullifier_value),
+            attributes_root: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_issue_skips_rejected_entries() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let outsider = deps.api.addr_make("outsider");
+        let holders: Vec<Addr> = (0..3)
+            .map(|i| deps.api.addr_make(&format!("holder{}", i)))
+            .collect();
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &holders[0], "9");
+
+        let mut invalid_holder = issuance_entry(&holders[2], "4");
+        invalid_holder.holder = "not-an-address".to_string();
+        let entries = vec![
+            issuance_entry(&holders[1], "1"),
+            // Nullifier already spent by an earlier credential
+            issuance_entry(&holders[2], "9"),
+            // Same nullifier as an earlier entry of this batch
+            issuance_entry(&holders[2], "1"),
+            invalid_holder,
+            issuance_entry(&holders[2], "2"),
+        ];
+        let msg = ExecuteMsg::BatchIssue {
+            entries: entries.clone(),
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), message_info(&outsider, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::IssuerNotAuthorized { .. }));
+
+        let msg = ExecuteMsg::BatchIssue { entries };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        let attr = |key: &str| {
+            res.attributes
+                .iter()
+                .find(|a| a.key == key)
+                .unwrap()
+                .value
+                .clone()
+        };
+        assert_eq!(attr("accepted"), "2");
+        assert_eq!(attr("rejected"), "3");
+        let rejected: Vec<&str> = res
+            .events
+            .iter()
+            .filter(|e| e.ty == "batch_entry_rejected")
+            .map(|e| e.attributes[0].value.as_str())
+            .collect();
+        assert_eq!(rejected, vec!["1", "2", "3"]);
+
+        assert_eq!(CREDENTIAL_COUNT.load(&deps.storage).unwrap(), 3);
+        assert!(NULLIFIERS.has(&deps.storage, ("1", "2")));
+        assert!(!NULLIFIERS.has(&deps.storage, ("1", "4")));
+    }
+
+    #[test]
+    fn test_claim_anchored_credential() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let anyone = deps.api.addr_make("anyone");
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        setup_contract(deps.as_mut());
+
+        let leaf = |entry: &IssuanceEntry| {
+            merkle::issuance_leaf(
+                &entry.holder,
+                &entry.credential_type.to_string(),
+                &entry.commitment,
+                entry.expires_at,
+                &entry.nullifier.domain,
+                &entry.nullifier.value,
+                "",
+            )
+        };
+        let alice_entry = issuance_entry(&alice, "1");
+        let bob_entry = issuance_entry(&bob, "2");
+        let (alice_leaf, bob_leaf) = (leaf(&alice_entry), leaf(&bob_entry));
+        let root = hex::encode(merkle::node_hash(&alice_leaf, &bob_leaf));
+
+        let msg = ExecuteMsg::AnchorIssuanceRoot { root: root.clone() };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&anyone, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::IssuerNotAuthorized { .. }));
+        execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+
+        let claim = |entry: &IssuanceEntry, position: u64, sibling: &merkle::Hash| {
+            ExecuteMsg::ClaimAnchoredCredential {
+                root: root.clone(),
+                entry: entry.clone(),
+                position,
+                siblings: vec![hex::encode(sibling)],
+            }
+        };
+        // Bob's entry does not sit at Alice's position
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&bob, &[]),
+            claim(&bob_entry, 0, &alice_leaf),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof { .. }));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&bob, &[]),
+            claim(&bob_entry, 1, &alice_leaf),
+        )
+        .unwrap();
+        let cred = query_credential(
+            deps.as_ref(),
+            mock_env(),
+            res.attributes[1].value.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(cred.holder, bob.to_string());
+        assert_eq!(cred.issuer, issuer1.to_string());
+
+        // A claimed entry cannot be claimed again
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&bob, &[]),
+            claim(&bob_entry, 1, &alice_leaf),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::CredentialAlreadyExists { .. }));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&alice, &[]),
+            claim(&alice_entry, 0, &bob_leaf),
+        )
+        .unwrap();
+        let anchored = query_issuance_root(deps.as_ref(), root).unwrap();
+        assert_eq!(anchored.claimed, 2);
+        assert_eq!(anchored.issuer, issuer1.to_string());
+    }
+
+    /// Compressed public key of the secp256k1 secret key 0x0101…01 (32 bytes)
+    const PRESENTATION_PUBKEY: &str =
+        "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f";
+    /// That key's signature over `presentation_digest` of `test_presentation()`
+    /// under `mock_env()`
+    const PRESENTATION_SIGNATURE: &str = "edbd592e074e2a2192c1ca763d93140a0b69066276632bfbef3867158793a9075a64106f80ab80a4f1b10d2bc51e5232dfd0ab7b9f846825787228d07f2ed08d";
+
+    fn test_presentation(credential_id: &str) -> Presentation {
+        Presentation {
+            credential_id: credential_id.to_string(),
+            audience: "vote.example.org".to_string(),
+            nonce: "7f3a9c".to_string(),
+            expires_at: mock_env().block.time.seconds() + 300,
+            pubkey: Binary::from(hex::decode(PRESENTATION_PUBKEY).unwrap()),
+            signature: Binary::from(hex::decode(PRESENTATION_SIGNATURE).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_verify_presentation() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let pubkey = hex::decode(PRESENTATION_PUBKEY).unwrap();
+        let holder = deps
+            .api
+            .addr_humanize(&Ripemd160::digest(Sha256::digest(&pubkey)).to_vec().into())
+            .unwrap();
+        setup_contract(deps.as_mut());
+        let cred_id = issue_citizenship(deps.as_mut(), &holder, "1");
+        let verify = |deps: Deps, env: Env, presentation: Presentation| {
+            query_verify_presentation(deps, env, presentation).unwrap()
+        };
+
+        let result = verify(deps.as_ref(), mock_env(), test_presentation(&cred_id));
+        assert!(result.valid, "{}", result.message);
+        assert_eq!(result.credential_type, CredentialType::Citizenship);
+
+        // The signature covers the audience
+        let mut presentation = test_presentation(&cred_id);
+        presentation.audience = "evil.example.org".to_string();
+        assert!(!verify(deps.as_ref(), mock_env(), presentation).valid);
+
+        // A key that does not derive the holder's address is refused
+        let mut presentation = test_presentation(&cred_id);
+        let mut other_key = pubkey.clone();
+        other_key[0] = 0x02;
+        presentation.pubkey = Binary::from(other_key);
+        let result = verify(deps.as_ref(), mock_env(), presentation);
+        assert!(result.message.contains("does not belong to the holder"));
+
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(300);
+        let result = verify(deps.as_ref(), later, test_presentation(&cred_id));
+        assert!(result.message.contains("Presentation expired"));
+
+        let msg = ExecuteMsg::RevokeCredential {
+            credential_id: cred_id.clone(),
+            reason: "test".to_string(),
+            fraud: false,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let result = verify(deps.as_ref(), mock_env(), test_presentation(&cred_id));
+        assert!(!result.valid);
+        assert_eq!(result.message, "Credential revoked");
+    }
+
+    #[test]
+    fn test_verify_groth16_proof() {
+        let mut deps = mock_dependencies();
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: cred_id,
+            proof: vector_proof(),
+            min_age_seconds: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&anyone, &[]), msg).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "valid" && a.value == "true"));
+    }
+
+    #[test]
+    fn test_verify_enforces_required_min_age() {
+        let mut deps = mock_dependencies();
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        // The vector proves 18 years; a verifier asking for 21 is refused
+        let verify = |min_age_seconds| ExecuteMsg::VerifyCredential {
+            credential_id: cred_id.clone(),
+            proof: vector_proof(),
+            min_age_seconds,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&anyone, &[]),
+            verify(Some(662_256_000)),
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidProof { reason } => {
+                assert_eq!(
+                    reason,
+                    "Proof minAge 567648000 is below the required 662256000"
+                )
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&anyone, &[]),
+            verify(Some(567_648_000)),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_stale_proof() {
+        let mut deps = mock_dependencies();
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        // The vector's currentTime signal
+        let proven_at = Timestamp::from_seconds(1_571_797_000);
+        let verify_at = |time: Timestamp| {
+            let mut env = mock_env();
+            env.block.time = time;
+            let msg = ExecuteMsg::VerifyCredential {
+                credential_id: cred_id.clone(),
+                proof: vector_proof(),
+                min_age_seconds: None,
+            };
+            (env, msg)
+        };
+        let (env, msg) = verify_at(proven_at.plus_seconds(MAX_PROOF_AGE));
+        execute(deps.as_mut(), env, message_info(&anyone, &[]), msg).unwrap();
+
+        let (env, msg) = verify_at(proven_at.plus_seconds(MAX_PROOF_AGE + 1));
+        let err = execute(deps.as_mut(), env, message_info(&anyone, &[]), msg).unwrap_err();
+        match err {
+            ContractError::InvalidProof { reason } => {
+                assert_eq!(reason, "Proof currentTime is more than 3600 seconds old")
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_uses_schema_signal_layout() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let citizenship_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        let library = CredentialType::Custom("library_card".to_string());
+        let msg = ExecuteMsg::RegisterVerificationKey {
+            credential_type: library.clone(),
+            version: "library_v1".to_string(),
+            verification_key: from_json(VK).unwrap(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let msg = ExecuteMsg::RegisterCredentialSchema {
+            name: "library_card".to_string(),
+            schema: CredentialSchema {
+                description: "Municipal library membership".to_string(),
+                attributes: vec![],
+                allowed_issuers: vec![issuer1.to_string()],
+                default_validity_secs: 0,
+                verification_key: Some("library_v1".to_string()),
+                private: false,
+                signal_layout: SignalLayout::Commitment,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let msg = ExecuteMsg::IssueCredential {
+            holder: anyone.to_string(),
+            credential_type: library,
+            commitment: VECTOR_COMMITMENT.to_string(),
+            expires_at: 0,
+            nullifier: nullifier("2"),
+            attributes_root: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        let library_id = res.attributes[1].value.clone();
+
+        // Before the vector's currentTime signal: only the citizenship layout reads it
+        let mut env = mock_env();
+        env.block.time = env.block.time.minus_seconds(86_400);
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: citizenship_id,
+            proof: vector_proof(),
+            min_age_seconds: None,
+        };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&anyone, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof { .. }));
+
+        let mut proof = vector_proof();
+        proof.vk_reference = "library_v1".to_string();
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: library_id,
+            proof,
+            min_age_seconds: None,
+        };
+        let res = execute(deps.as_mut(), env, message_info(&anyone, &[]), msg).unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|a| a.key == "valid" && a.value == "true"));
+        assert!(!res.attributes.iter().any(|a| a.key == "min_age_seconds"));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_public_input() {
+        let mut deps = mock_dependencies();
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        // Claim a lower age threshold than the proof was made for
+        let mut proof = vector_proof();
+        proof.public_inputs[SIGNAL_MIN_AGE] = "0".to_string();
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: cred_id,
+            proof,
+            min_age_seconds: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&anyone, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof { .. }));
+    }
+
+    #[test]
+    fn test_verify_rejects_proof_for_other_commitment() {
+        let mut deps = mock_dependencies();
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), "12345");
+
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: cred_id,
+            proof: vector_proof(),
+            min_age_seconds: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&anyone, &[]), msg).unwrap_err();
+        match err {
+            ContractError::InvalidProof { reason } => {
+                assert_eq!(reason, "Proof commitment mismatch")
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_placeholder_hash_proof() {
+        let mut deps = mock_dependencies();
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        // The old SHA-256 scheme: any string whose hash prefix matches passed
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: cred_id,
+            proof: ZkProof {
+                proof_data: "deadbeef".to_string(),
+                public_inputs: vec![VECTOR_COMMITMENT.to_string()],
+                vk_reference: "citizenship_v1".to_string(),
+            },
+            min_age_seconds: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&anyone, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof { .. }));
+    }
+
+    #[test]
+    fn test_register_verification_key_admin_only() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut());
+
+        let msg = ExecuteMsg::RegisterVerificationKey {
+            credential_type: CredentialType::Citizenship,
+            version: "citizenship_v2".to_string(),
+            verification_key: from_json(VK).unwrap(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // Re-registering an existing version would swap the key under live proofs
+        let msg = ExecuteMsg::RegisterVerificationKey {
+            credential_type: CredentialType::Citizenship,
+            version: "citizenship_v1".to_string(),
+            verification_key: from_json(VK).unwrap(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::VerificationKeyAlreadyExists { .. }
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_deprecated_key() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+
+        let msg = ExecuteMsg::DeprecateVerificationKey {
+            credential_type: CredentialType::Citizenship,
+            version: "citizenship_v1".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: cred_id,
+            proof: vector_proof(),
+            min_age_seconds: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&anyone, &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::VerificationKeyDeprecated { .. }
+        ));
+
+        let keys = query_verification_keys(deps.as_ref(), None).unwrap();
+        assert_eq!(keys.keys.len(), 1);
+        assert!(keys.keys[0].deprecated);
+    }
+
+    #[test]
+    fn test_verify_rejects_key_of_other_credential_type() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let citizen1 = deps.api.addr_make("citizen1");
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut());
+
+        let msg = ExecuteMsg::RegisterVerificationKey {
+            credential_type: CredentialType::Residency,
+            version: "residency_v1".to_string(),
+            verification_key: from_json(VK).unwrap(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen1.to_string(),
+            credential_type: CredentialType::Residency,
+            commitment: VECTOR_COMMITMENT.to_string(),
+            expires_at: 0,
+            nullifier: nullifier("1"),
+            attributes_root: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        let cred_id = res.attributes[1].value.clone();
+
+        // citizenship_v1 exists, but not for residency credentials
+        let msg = ExecuteMsg::VerifyCredential {
+            credential_id: cred_id,
+            proof: vector_proof(),
+            min_age_seconds: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&anyone, &[]), msg).unwrap_err();
+        match err {
+            ContractError::VerificationKeyNotFound { reference } => {
+                assert_eq!(reference, "residency/citizenship_v1")
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let keys = query_verification_keys(deps.as_ref(), Some(CredentialType::Residency)).unwrap();
+        assert_eq!(keys.keys.len(), 1);
+        assert_eq!(keys.keys[0].version, "residency_v1");
+    }
+
+    #[test]
+    fn test_nullifier_cannot_be_reused() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let citizen1 = deps.api.addr_make("citizen1");
+        let citizen2 = deps.api.addr_make("citizen2");
+        setup_contract(deps.as_mut());
+
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("42"),
+            attributes_root: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+
+        let spent = query_nullifier(deps.as_ref(), nullifier("42")).unwrap();
+        assert!(spent.spent);
+
+        // Same identity, new wallet, with the nullifier re-encoded
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen2.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("0042"),
+            attributes_root: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::NullifierAlreadyUsed { .. }));
+
+        // The same identity in another domain is a separate registration
+        let msg = ExecuteMsg::IssueCredential {
+            holder: citizen2.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: Nullifier {
+                domain: "2".to_string(),
+                value: "42".to_string(),
+            },
+            attributes_root: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn test_verification_result_spends_nullifier() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let relayer = deps.api.addr_make("relayer1");
+        let citizen1 = deps.api.addr_make("citizen1");
+        let citizen2 = deps.api.addr_make("citizen2");
+        setup_contract(deps.as_mut());
+        add_relayers(deps.as_mut(), &admin, &[&relayer]);
+
+        let submit = |request_id: &str, applicant: &Addr, approved: bool| {
+            ExecuteMsg::SubmitVerificationResult {
+                request_id: request_id.to_string(),
+                applicant: applicant.to_string(),
+                credential_type: CredentialType::Citizenship,
+                commitment: "abc123".to_string(),
+                approved,
+                nullifier: nullifier("7"),
+            }
+        };
+
+        // A rejection leaves the nullifier unspent
+        let info = message_info(&relayer, &[]);
+        let req = open_request(deps.as_mut(), &citizen1, "hash1");
+        let msg = submit(&req, &citizen1, false);
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert!(
+            !query_nullifier(deps.as_ref(), nullifier("7"))
+                .unwrap()
+                .spent
+        );
+
+        // The request is closed; a retry needs a new one
+        let msg = submit(&req, &citizen1, true);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::VerificationRequestClosed { .. }
+        ));
+
+        let req = open_request(deps.as_mut(), &citizen1, "hash2");
+        let msg = submit(&req, &citizen1, true);
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let req = open_request(deps.as_mut(), &citizen2, "hash3");
+        let msg = submit(&req, &citizen2, true);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::NullifierAlreadyUsed { .. }));
+    }
+
+    fn request_recovery(
+        deps: DepsMut,
+        old_address: &Addr,
+        new_address: &Addr,
+        reverification_id: Option<String>,
+    ) -> Result<String, ContractError> {
+        let msg = ExecuteMsg::RequestKeyRecovery {
+            old_address: old_address.to_string(),
+            reverification_id,
+        };
+        let res = execute(deps, mock_env(), message_info(new_address, &[]), msg)?;
+        Ok(res.attributes[1].value.clone())
+    }
+
+    fn set_guardians(deps: DepsMut, holder: &Addr, guardians: &[&Addr], threshold: u32) {
+        let msg = ExecuteMsg::SetGuardians {
+            guardians: guardians.iter().map(|g| g.to_string()).collect(),
+            threshold,
+        };
+        execute(deps, mock_env(), message_info(holder, &[]), msg).unwrap();
+    }
+
+    fn approve_reverification(
+        mut deps: DepsMut,
+        applicant: &Addr,
+        nullifier_value: &str,
+    ) -> String {
+        let api = MockApi::default();
+        let admin = api.addr_make("admin");
+        let relayer = api.addr_make("relayer1");
+        add_relayers(deps.branch(), &admin, &[&relayer]);
+        let msg = ExecuteMsg::RequestVerification {
+            verification_type: VerificationType::RecoveryReverification,
+            data_hash: format!("reverify-{}", nullifier_value),
+        };
+        let res = execute(deps.branch(), mock_env(), message_info(applicant, &[]), msg).unwrap();
+        let request_id = res.attributes[1].value.clone();
+        let msg = ExecuteMsg::SubmitVerificationResult {
+            request_id: request_id.clone(),
+            applicant: applicant.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            approved: true,
+            nullifier: nullifier(nullifier_value),
+        };
+        let res = execute(deps, mock_env(), message_info(&relayer, &[]), msg).unwrap();
+        assert_eq!(res.attributes[0].value, "reverification_approved");
+        request_id
+    }
+
+    #[test]
+    fn test_recovery_carries_nullifier_over() {
+        let mut deps = mock_dependencies();
+        let citizen1 = deps.api.addr_make("citizen1");
+        let guardian = deps.api.addr_make("guardian1");
+        let new_wallet = deps.api.addr_make("new_wallet");
+        setup_contract(deps.as_mut());
+        let cred_id = issue_vector_credential(deps.as_mut(), VECTOR_COMMITMENT);
+        set_guardians(deps.as_mut(), &citizen1, &[&guardian], 1);
+
+        let recovery_id = request_recovery(deps.as_mut(), &citizen1, &new_wallet, None).unwrap();
+        let msg = ExecuteMsg::ApproveRecovery {
+            recovery_id: recovery_id.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&guardian, &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.height += 10080;
+        let msg = ExecuteMsg::ExecuteRecovery { recovery_id };
+        execute(deps.as_mut(), env, message_info(&new_wallet, &[]), msg).unwrap();
+
+        let record = NULLIFIERS.load(&deps.storage, ("1", "1")).unwrap();
+        assert_eq!(record.holder, new_wallet);
+        assert_eq!(record.credential_id, cred_id);
+        // Guardians follow the identity to its new address
+        assert_eq!(
+            query_guardians(deps.as_ref(), new_wallet.to_string())
+                .unwrap()
+                .threshold,
+            1
+        );
+    }
+
+    #[test]
+    fn test_recovery_needs_guardian_threshold() {
+        let mut deps = mock_dependencies();
+        let citizen1 = deps.api.addr_make("citizen1");
+        let g1 = deps.api.addr_make("guardian1");
+        let g2 = deps.api.addr_make("guardian2");
+        let g3 = deps.api.addr_make("guardian3");
+        let new_wallet = deps.api.addr_make("new_wallet");
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &citizen1, "1");
+
+        // Without guardians or a re-verification there is nothing to prove ownership
+        let err = request_recovery(deps.as_mut(), &citizen1, &new_wallet, None).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let msg = ExecuteMsg::SetGuardians {
+            guardians: vec![g1.to_string(), g1.to_string()],
+            threshold: 1,
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), message_info(&citizen1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold { .. }));
+        set_guardians(deps.as_mut(), &citizen1, &[&g1, &g2, &g3], 2);
+
+        let recovery_id = request_recovery(deps.as_mut(), &citizen1, &new_wallet, None).unwrap();
+        let approve = |sender: &Addr| {
+            (
+                ExecuteMsg::ApproveRecovery {
+                    recovery_id: recovery_id.clone(),
+                },
+                message_info(sender, &[]),
+            )
+        };
+
+        let (msg, info) = approve(&new_wallet);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        let msg = ExecuteMsg::ApproveRecovery {
+            recovery_id: "unknown".to_string(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&g1, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::RecoveryNotFound { .. }));
+
+        let (msg, info) = approve(&g1);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let (msg, info) = approve(&g1);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // One approval short: still pending and not executable
+        let mut env = mock_env();
+        env.block.height += 20_000;
+        let msg = ExecuteMsg::ExecuteRecovery {
+            recovery_id: recovery_id.clone(),
+        };
+        let err = execute(deps.as_mut(), env, message_info(&new_wallet, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let (msg, info) = approve(&g3);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let recovery = query_recovery(deps.as_ref(), recovery_id.clone()).unwrap();
+        assert_eq!(recovery.status, "TimelockWaiting");
+        assert_eq!(recovery.approvals.len(), 2);
+        assert_eq!(recovery.execute_after, mock_env().block.height + 10080);
 
-    if !approved {
-        return Ok(Response::new()
-            .add_attribute("action", "verification_rejected")
-            .add_attribute("request_id", request_id)
-            .add_attribute("applicant", applicant));
+        // The timelock still applies after the guardians agree
+        let msg = ExecuteMsg::ExecuteRecovery {
+            recovery_id: recovery_id.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_wallet, &[]),
+            msg,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::TimelockNotExpired { .. }));
+
+        let msg = ExecuteMsg::ContestRecovery { recovery_id };
+        execute(deps.as_mut(), mock_env(), message_info(&citizen1, &[]), msg).unwrap();
     }
 
-    // Auto-issue credential upon successful verification
-    let holder_addr = deps.api.addr_validate(&applicant)?;
-    let count = CREDENTIAL_COUNT.load(deps.storage)?;
-    let new_count = count + 1;
+    #[test]
+    fn test_recovery_by_reverification() {
+        let mut deps = mock_dependencies();
+        let citizen1 = deps.api.addr_make("citizen1");
+        let new_wallet = deps.api.addr_make("new_wallet");
+        let impostor = deps.api.addr_make("impostor");
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &citizen1, "1");
 
-    let cred_id_raw = format!(
-        "{}:{}:{}",
-        applicant,
-        credential_type,
-        env.block.time.seconds()
-    );
-    let mut hasher = Sha256::new();
-    hasher.update(cred_id_raw.as_bytes());
-    let cred_id = hex::encode(hasher.finalize());
+        // Re-verification proves an existing identity; it does not mint a credential
+        let reverify = approve_reverification(deps.as_mut(), &impostor, "9");
+        assert!(
+            !query_has_valid(
+                deps.as_ref(),
+                mock_env(),
+                impostor.to_string(),
+                CredentialType::Citizenship,
+                None
+            )
+            .unwrap()
+            .has_credential
+        );
+        let err =
+            request_recovery(deps.as_mut(), &citizen1, &impostor, Some(reverify)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
 
-    let credential = Credential {
-        id: cred_id.clone(),
-        holder: holder_addr.clone(),
-        credential_type: credential_type.clone(),
-        commitment,
-        issuer: info.sender.clone(),
-        issued_at: env.block.time.seconds(),
-        expires_at: 0, // no expiry for verified credentials
-        revoked: false,
-    };
+        // Someone else's approved re-verification cannot be borrowed
+        let reverify = approve_reverification(deps.as_mut(), &new_wallet, "1");
+        let err = request_recovery(deps.as_mut(), &citizen1, &impostor, Some(reverify.clone()))
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
 
-    CREDENTIALS.save(deps.storage, &cred_id, &credential)?;
-    HOLDER_CREDENTIALS.save(deps.storage, (&holder_addr, &cred_id), &true)?;
-    CREDENTIAL_COUNT.save(deps.storage, &new_count)?;
+        let recovery_id = request_recovery(
+            deps.as_mut(),
+            &citizen1,
+            &new_wallet,
+            Some(reverify.clone()),
+        )
+        .unwrap();
+        let recovery = query_recovery(deps.as_ref(), recovery_id.clone()).unwrap();
+        assert_eq!(recovery.status, "TimelockWaiting");
 
-    Ok(Response::new()
-        .add_attribute("action", "verification_approved")
-        .add_attribute("request_id", request_id)
-        .add_attribute("credential_id", cred_id)
-        .add_attribute("applicant", applicant))
-}
+        // Each re-verification backs a single recovery
+        let mut env = mock_env();
+        env.block.height += 1;
+        let msg = ExecuteMsg::RequestKeyRecovery {
+            old_address: citizen1.to_string(),
+            reverification_id: Some(reverify),
+        };
+        let err = execute(deps.as_mut(), env, message_info(&new_wallet, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
 
-// ── Key Recovery ────────────────────────────────────────────────────
+        let mut env = mock_env();
+        env.block.height += 10080;
+        let msg = ExecuteMsg::ExecuteRecovery { recovery_id };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&new_wallet, &[]),
+            msg,
+        )
+        .unwrap();
+        assert!(
+            query_has_valid(
+                deps.as_ref(),
+                env,
+                new_wallet.to_string(),
+                CredentialType::Citizenship,
+                None
+            )
+            .unwrap()
+            .has_credential
+        );
+    }
 
-fn execute_request_recovery(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    old_address: String,
-    reverification_id: String,
-) -> Result<Response, ContractError> {
-    let old_addr = deps.api.addr_validate(&old_address)?;
-    let timelock = RECOVERY_TIMELOCK.load(deps.storage)?;
+    #[test]
+    fn test_recovery_cancel_and_admin_resolution() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let citizen1 = deps.api.addr_make("citizen1");
+        let guardian = deps.api.addr_make("guardian1");
+        let new_wallet = deps.api.addr_make("new_wallet");
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &citizen1, "1");
+        set_guardians(deps.as_mut(), &citizen1, &[&guardian], 1);
 
-    let recovery_id_raw = format!(
-        "recovery:{}:{}:{}",
-        old_address, info.sender, env.block.height
-    );
-    let mut hasher = Sha256::new();
-    hasher.update(recovery_id_raw.as_bytes());
-    let recovery_id = hex::encode(hasher.finalize());
-
-    let recovery = StoredRecovery {
-        recovery_id: recovery_id.clone(),
-        old_address: old_addr,
-        new_address: info.sender.clone(),
-        reverification_id,
-        status: StoredRecoveryStatus::TimelockWaiting,
-        requested_at: env.block.height,
-        execute_after: env.block.height + timelock,
-    };
+        let first = request_recovery(deps.as_mut(), &citizen1, &new_wallet, None).unwrap();
+        let pending = query_pending_recoveries(deps.as_ref(), citizen1.to_string(), None, None)
+            .unwrap()
+            .recoveries;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].recovery_id, first);
 
-    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+        let msg = ExecuteMsg::CancelRecovery {
+            recovery_id: first.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&citizen1, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_wallet, &[]),
+            msg,
+        )
+        .unwrap();
+        assert!(
+            query_pending_recoveries(deps.as_ref(), new_wallet.to_string(), None, None)
+                .unwrap()
+                .recoveries
+                .is_empty()
+        );
 
-    Ok(Response::new()
-        .add_attribute("action", "request_recovery")
-        .add_attribute("recovery_id", recovery_id)
-        .add_attribute("old_address", old_address)
-        .add_attribute("new_address", info.sender.as_str())
-        .add_attribute("execute_after", recovery.execute_after.to_string()))
-}
+        // A contested recovery waits for the admin to settle it
+        let mut env = mock_env();
+        env.block.height += 1;
+        let msg = ExecuteMsg::RequestKeyRecovery {
+            old_address: citizen1.to_string(),
+            reverification_id: None,
+        };
+        let res = execute(deps.as_mut(), env, message_info(&new_wallet, &[]), msg).unwrap();
+        let second = res.attributes[1].value.clone();
+        let msg = ExecuteMsg::ContestRecovery {
+            recovery_id: second.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&citizen1, &[]), msg).unwrap();
 
-fn execute_execute_recovery(
-    deps: DepsMut,
-    env: Env,
-    _info: MessageInfo,
-    recovery_id: String,
-) -> Result<Response, ContractError> {
-    let mut recovery =
-        RECOVERIES
-            .may_load(deps.storage, &recovery_id)?
-            .ok_or(ContractError::Unauthorized {
-                reason: "Recovery not found".to_string(),
-            })?;
+        let msg = ExecuteMsg::ResolveRecovery {
+            recovery_id: second.clone(),
+            approve: true,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&guardian, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        let res = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert!(res.messages.is_empty());
 
-    if !matches!(recovery.status, StoredRecoveryStatus::TimelockWaiting) {
-        return Err(ContractError::Unauthorized {
-            reason: "Recovery is not in TimelockWaiting state".to_string(),
-        });
-    }
+        // Approval restarts the timelock instead of migrating at once,
+        // and the old address can no longer contest it
+        assert_eq!(
+            query_recovery(deps.as_ref(), second.clone())
+                .unwrap()
+                .status,
+            "TimelockWaiting"
+        );
+        let msg = ExecuteMsg::ContestRecovery {
+            recovery_id: second.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&citizen1, &[]), msg).unwrap_err();
+        let msg = ExecuteMsg::ExecuteRecovery {
+            recovery_id: second.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&new_wallet, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::TimelockNotExpired { .. }));
+        let mut env = mock_env();
+        env.block.height += 10080;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&new_wallet, &[]),
+            msg,
+        )
+        .unwrap();
 
-    if env.block.height < recovery.execute_after {
-        return Err(ContractError::TimelockNotExpired {
-            execute_at: recovery.execute_after,
-        });
+        assert_eq!(
+            query_recovery(deps.as_ref(), second).unwrap().status,
+            "Executed"
+        );
+        assert!(
+            query_has_valid(
+                deps.as_ref(),
+                env,
+                new_wallet.to_string(),
+                CredentialType::Citizenship,
+                None
+            )
+            .unwrap()
+            .has_credential
+        );
     }
 
-    // Migrate all credentials from old address to new address
-    let old_creds: Vec<String> = HOLDER_CREDENTIALS
-        .prefix(&recovery.old_address)
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|r| r.ok())
-        .map(|(cred_id, _)| cred_id)
-        .collect();
+    #[test]
+    fn test_relayer_management_admin_only() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let relayer = deps.api.addr_make("relayer1");
+        setup_contract(deps.as_mut());
 
-    for cred_id in &old_creds {
-        // Update credential holder
-        if let Ok(mut cred) = CREDENTIALS.load(deps.storage, cred_id) {
-            cred.holder = recovery.new_address.clone();
-            CREDENTIALS.save(deps.storage, cred_id, &cred)?;
-        }
-        // Move holder index
-        HOLDER_CREDENTIALS.remove(deps.storage, (&recovery.old_address, cred_id));
-        HOLDER_CREDENTIALS.save(deps.storage, (&recovery.new_address, cred_id), &true)?;
-    }
+        let msg = ExecuteMsg::AddRelayer {
+            relayer: relayer.to_string(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&relayer, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
 
-    recovery.status = StoredRecoveryStatus::Executed;
-    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+        add_relayers(deps.as_mut(), &admin, &[&relayer]);
+        assert_eq!(query_relayers(deps.as_ref()).unwrap().relayers.len(), 1);
 
-    Ok(Response::new()
-        .add_attribute("action", "execute_recovery")
-        .add_attribute("recovery_id", recovery_id)
-        .add_attribute("credentials_migrated", old_creds.len().to_string()))
-}
+        // The threshold can never exceed the number of relayers able to meet it
+        let msg = ExecuteMsg::SetAttestationThreshold { threshold: 2 };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold { .. }));
 
-fn execute_contest_recovery(
-    deps: DepsMut,
-    info: MessageInfo,
-    recovery_id: String,
-) -> Result<Response, ContractError> {
-    let mut recovery =
-        RECOVERIES
-            .may_load(deps.storage, &recovery_id)?
-            .ok_or(ContractError::Unauthorized {
-                reason: "Recovery not found".to_string(),
-            })?;
+        // Nor can the last relayer go, which would leave no one to attest
+        let msg = ExecuteMsg::RemoveRelayer {
+            relayer: relayer.to_string(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold { .. }));
+        assert_eq!(query_relayers(deps.as_ref()).unwrap().relayers.len(), 1);
 
-    // Only the old address can contest
-    if info.sender != recovery.old_address {
-        return Err(ContractError::Unauthorized {
-            reason: "Only the original address owner can contest recovery".to_string(),
-        });
+        // The admin is not a relayer and cannot attest on its own
+        let msg = ExecuteMsg::SubmitVerificationResult {
+            request_id: "req-1".to_string(),
+            applicant: relayer.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            approved: true,
+            nullifier: nullifier("7"),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
     }
 
-    if !matches!(recovery.status, StoredRecoveryStatus::TimelockWaiting) {
-        return Err(ContractError::Unauthorized {
-            reason: "Recovery is not in contestable state".to_string(),
-        });
-    }
+    #[test]
+    fn test_attestation_threshold_two_of_three() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let r1 = deps.api.addr_make("relayer1");
+        let r2 = deps.api.addr_make("relayer2");
+        let r3 = deps.api.addr_make("relayer3");
+        let citizen1 = deps.api.addr_make("citizen1");
+        setup_contract(deps.as_mut());
+        add_relayers(deps.as_mut(), &admin, &[&r1, &r2, &r3]);
 
-    recovery.status = StoredRecoveryStatus::Contested;
-    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+        let msg = ExecuteMsg::SetAttestationThreshold { threshold: 2 };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let req = open_request(deps.as_mut(), &citizen1, "hash4");
 
-    Ok(Response::new()
-        .add_attribute("action", "contest_recovery")
-        .add_attribute("recovery_id", recovery_id))
-}
+        let submit = |commitment: &str| ExecuteMsg::SubmitVerificationResult {
+            request_id: req.clone(),
+            applicant: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: commitment.to_string(),
+            approved: true,
+            nullifier: nullifier("7"),
+        };
 
-// ── Migrate ─────────────────────────────────────────────────────────
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&r1, &[]),
+            submit("abc123"),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0].value, "attest_verification");
 
-#[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let version = get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(ContractError::Unauthorized {
-            reason: format!("Cannot migrate from {}", version.contract),
-        });
-    }
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::new()
-        .add_attribute("action", "migrate")
-        .add_attribute("from_version", version.version)
-        .add_attribute("to_version", CONTRACT_VERSION))
-}
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&r1, &[]),
+            submit("abc123"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyAttested { .. }));
 
-// ── Tests ───────────────────────────────────────────────────────────
+        // A disagreeing relayer does not count toward the first result
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&r2, &[]),
+            submit("forged"),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0].value, "attest_verification");
+        assert_eq!(query_config(deps.as_ref()).unwrap().total_credentials, 0);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&r3, &[]),
+            submit("abc123"),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0].value, "verification_approved");
+        assert_eq!(query_config(deps.as_ref()).unwrap().total_credentials, 1);
 
-    fn setup_contract(deps: DepsMut) {
-        let api = MockApi::default();
-        let admin = api.addr_make("admin");
-        let issuer1 = api.addr_make("issuer1");
-        let creator = api.addr_make("creator");
-        let msg = InstantiateMsg {
-            admin: admin.to_string(),
-            issuers: vec![issuer1.to_string()],
+        let progress = query_attestations(deps.as_ref(), req).unwrap();
+        assert_eq!(progress.attestations.len(), 3);
+        assert_eq!(progress.status, Some(VerificationStatus::Approved));
+
+        // Removals cannot leave fewer relayers than the threshold
+        let remove = |relayer: &Addr| ExecuteMsg::RemoveRelayer {
+            relayer: relayer.to_string(),
         };
-        let info = message_info(&creator, &[]);
-        instantiate(deps, mock_env(), info, msg).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            remove(&r1),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            remove(&r2),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidThreshold { .. }));
+        assert_eq!(query_relayers(deps.as_ref()).unwrap().relayers.len(), 2);
     }
 
     #[test]
-    fn test_instantiate() {
+    fn test_verification_request_lifecycle() {
         let mut deps = mock_dependencies();
         let admin = deps.api.addr_make("admin");
+        let relayer = deps.api.addr_make("relayer1");
+        let citizen1 = deps.api.addr_make("citizen1");
         setup_contract(deps.as_mut());
+        add_relayers(deps.as_mut(), &admin, &[&relayer]);
+        let req = open_request(deps.as_mut(), &citizen1, "hash5");
 
-        let config = query_config(deps.as_ref()).unwrap();
-        assert_eq!(config.admin, admin.to_string());
-        assert_eq!(config.total_credentials, 0);
+        let request = query_verification_request(deps.as_ref(), mock_env(), req.clone()).unwrap();
+        assert_eq!(request.status, VerificationStatus::Pending);
+        assert_eq!(request.applicant, citizen1.to_string());
+
+        let msg = ExecuteMsg::StartVerificationReview {
+            request_id: req.clone(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&relayer, &[]), msg).unwrap();
+        let listed = |deps: Deps, status: VerificationStatus| {
+            query_requests_by_status(deps, mock_env(), status, None, None)
+                .unwrap()
+                .requests
+        };
+        assert_eq!(listed(deps.as_ref(), VerificationStatus::InReview).len(), 1);
+        assert!(listed(deps.as_ref(), VerificationStatus::Pending).is_empty());
+
+        // A result for a request that does not exist is refused
+        let submit = |request_id: &str| ExecuteMsg::SubmitVerificationResult {
+            request_id: request_id.to_string(),
+            applicant: citizen1.to_string(),
+            credential_type: CredentialType::Citizenship,
+            commitment: "abc123".to_string(),
+            approved: true,
+            nullifier: nullifier("7"),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&relayer, &[]),
+            submit("unknown"),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::VerificationRequestNotFound { .. }
+        ));
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&relayer, &[]),
+            submit(&req),
+        )
+        .unwrap();
+        let mine = query_requests_by_applicant(
+            deps.as_ref(),
+            mock_env(),
+            citizen1.to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(mine.requests.len(), 1);
+        assert_eq!(mine.requests[0].status, VerificationStatus::Approved);
+        assert!(listed(deps.as_ref(), VerificationStatus::InReview).is_empty());
+        assert_eq!(listed(deps.as_ref(), VerificationStatus::Approved).len(), 1);
     }
 
     #[test]
-    fn test_issue_credential() {
+    fn test_verification_request_expires() {
         let mut deps = mock_dependencies();
-        let issuer1 = deps.api.addr_make("issuer1");
+        let admin = deps.api.addr_make("admin");
+        let relayer = deps.api.addr_make("relayer1");
         let citizen1 = deps.api.addr_make("citizen1");
         setup_contract(deps.as_mut());
+        add_relayers(deps.as_mut(), &admin, &[&relayer]);
+        let req = open_request(deps.as_mut(), &citizen1, "hash6");
 
-        let info = message_info(&issuer1, &[]);
-        let msg = ExecuteMsg::IssueCredential {
-            holder: citizen1.to_string(),
+        let msg = ExecuteMsg::ExpireVerificationRequest {
+            request_id: req.clone(),
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), message_info(&citizen1, &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::VerificationRequestNotExpired { .. }
+        ));
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(604_801);
+
+        let request = query_verification_request(deps.as_ref(), env.clone(), req.clone()).unwrap();
+        assert_eq!(request.status, VerificationStatus::Expired);
+
+        let msg = ExecuteMsg::SubmitVerificationResult {
+            request_id: req.clone(),
+            applicant: citizen1.to_string(),
             credential_type: CredentialType::Citizenship,
             commitment: "abc123".to_string(),
-            expires_at: 0,
+            approved: true,
+            nullifier: nullifier("7"),
         };
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
-        assert_eq!(res.attributes[0].value, "issue_credential");
+        let err =
+            execute(deps.as_mut(), env.clone(), message_info(&relayer, &[]), msg).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::VerificationRequestExpired { .. }
+        ));
 
-        let config = query_config(deps.as_ref()).unwrap();
-        assert_eq!(config.total_credentials, 1);
+        let msg = ExecuteMsg::ExpireVerificationRequest { request_id: req };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&citizen1, &[]),
+            msg,
+        )
+        .unwrap();
+        let expired =
+            query_requests_by_status(deps.as_ref(), env, VerificationStatus::Expired, None, None)
+                .unwrap();
+        assert_eq!(expired.requests.len(), 1);
+        assert!(expired.requests[0].completed_at > 0);
     }
 
     #[test]
-    fn test_unauthorized_issuer() {
+    fn test_social_vouching_issues_citizenship() {
         let mut deps = mock_dependencies();
-        let random = deps.api.addr_make("random");
-        let citizen1 = deps.api.addr_make("citizen1");
+        let applicant = deps.api.addr_make("applicant");
+        let outsider = deps.api.addr_make("outsider");
+        let vouchers: Vec<Addr> = (1..=3)
+            .map(|i| deps.api.addr_make(&format!("voucher{}", i)))
+            .collect();
         setup_contract(deps.as_mut());
+        for (i, voucher) in vouchers.iter().enumerate() {
+            issue_citizenship(deps.as_mut(), voucher, &(100 + i).to_string());
+        }
+        let req = request_vouching(deps.as_mut(), &applicant, "7");
 
-        let info = message_info(&random, &[]);
-        let msg = ExecuteMsg::IssueCredential {
-            holder: citizen1.to_string(),
+        // Relayers cannot settle a vouching request around the vouchers
+        let admin = deps.api.addr_make("admin");
+        let relayer = deps.api.addr_make("relayer1");
+        add_relayers(deps.as_mut(), &admin, &[&relayer]);
+        let msg = ExecuteMsg::SubmitVerificationResult {
+            request_id: req.clone(),
+            applicant: applicant.to_string(),
             credential_type: CredentialType::Citizenship,
             commitment: "abc123".to_string(),
-            expires_at: 0,
+            approved: true,
+            nullifier: nullifier("7"),
         };
-        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-        assert!(matches!(err, ContractError::IssuerNotAuthorized { .. }));
+        let err = execute(deps.as_mut(), mock_env(), message_info(&relayer, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        let msg = ExecuteMsg::StartVerificationReview {
+            request_id: req.clone(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&relayer, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let vouch = |request_id: &str| ExecuteMsg::Vouch {
+            request_id: request_id.to_string(),
+        };
+
+        // Only citizens can vouch, and not for themselves
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&outsider, &[]),
+            vouch(&req),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoVerifiedCredential));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&applicant, &[]),
+            vouch(&req),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        for voucher in &vouchers[..2] {
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(voucher, &[]),
+                vouch(&req),
+            )
+            .unwrap();
+            assert_eq!(res.attributes[0].value, "vouch");
+        }
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&vouchers[0], &[]),
+            vouch(&req),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::AlreadyAttested { .. }));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&vouchers[2], &[]),
+            vouch(&req),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0].value, "vouching_approved");
+
+        let has = query_has_valid(
+            deps.as_ref(),
+            mock_env(),
+            applicant.to_string(),
+            CredentialType::Citizenship,
+            None,
+        )
+        .unwrap();
+        assert!(has.has_credential);
+        assert_eq!(query_vouches(deps.as_ref(), req).unwrap().vouchers.len(), 3);
     }
 
     #[test]
-    fn test_revoke_credential() {
+    fn test_vouch_rate_limit_and_fraud_penalty() {
+        let mut deps = mock_dependencies();
+        let admin = deps.api.addr_make("admin");
+        let voucher = deps.api.addr_make("voucher1");
+        let applicant1 = deps.api.addr_make("applicant1");
+        let applicant2 = deps.api.addr_make("applicant2");
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &voucher, "100");
+
+        let msg = ExecuteMsg::UpdateVouchingConfig {
+            config: VouchingConfig {
+                max_vouches_per_period: 1,
+                period_secs: 86_400,
+                fraud_cooldown_secs: 864_000,
+            },
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        let mut config = default_verification_config();
+        config.min_social_vouches = 1;
+        let msg = ExecuteMsg::UpdateVerificationConfig { config };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        let req1 = request_vouching(deps.as_mut(), &applicant1, "7");
+        let req2 = request_vouching(deps.as_mut(), &applicant2, "8");
+
+        let msg = ExecuteMsg::Vouch { request_id: req1 };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&voucher, &[]), msg).unwrap();
+        let cred_id = res.attributes[2].value.clone();
+
+        let msg = ExecuteMsg::Vouch {
+            request_id: req2.clone(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&voucher, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // The vouchee turns out to be fraudulent
+        let msg = ExecuteMsg::RevokeCredential {
+            credential_id: cred_id,
+            reason: "forged identity".to_string(),
+            fraud: true,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+
+        // A fresh period does not lift the fraud cooldown
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(86_400);
+        let status = query_voucher(deps.as_ref(), env.clone(), voucher.to_string()).unwrap();
+        assert_eq!(status.vouches_remaining, 1);
+        assert_eq!(status.fraud_count, 1);
+
+        let msg = ExecuteMsg::Vouch { request_id: req2 };
+        let err = execute(deps.as_mut(), env, message_info(&voucher, &[]), msg).unwrap_err();
+        match err {
+            ContractError::Unauthorized { reason } => {
+                assert!(reason.starts_with("Vouching suspended"))
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_verifiable_credential_round_trip() {
         let mut deps = mock_dependencies();
         let issuer1 = deps.api.addr_make("issuer1");
-        let citizen1 = deps.api.addr_make("citizen1");
+        let holder = deps.api.addr_make("holder");
         setup_contract(deps.as_mut());
+        let now = mock_env().block.time.seconds();
 
-        // Issue first
-        let info = message_info(&issuer1, &[]);
         let msg = ExecuteMsg::IssueCredential {
-            holder: citizen1.to_string(),
-            credential_type: CredentialType::Citizenship,
+            holder: holder.to_string(),
+            credential_type: CredentialType::Residency,
             commitment: "abc123".to_string(),
-            expires_at: 0,
+            expires_at: now + 86_400,
+            nullifier: nullifier("1"),
+            attributes_root: None,
         };
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-        let cred_id = res
-            .attributes
-            .iter()
-            .find(|a| a.key == "credential_id")
-            .unwrap()
-            .value
-            .clone();
+        let res = execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
+        let cred_id = res.attributes[1].value.clone();
 
-        // Revoke
-        let revoke_msg = ExecuteMsg::RevokeCredential {
-            credential_id: cred_id.clone(),
-            reason: "test revocation".to_string(),
-        };
-        execute(deps.as_mut(), mock_env(), info, revoke_msg).unwrap();
+        let credential: vc::VerifiableCredential = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVerifiableCredential {
+                    credential_id: cred_id.clone(),
+                    view_token: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(credential.issuer, format!("did:cosmos:{}", issuer1));
+        assert_eq!(
+            credential.credential_subject.id,
+            format!("did:cosmos:{}", holder)
+        );
+        assert_eq!(credential.credential_subject.credential_type, "residency");
+        assert_eq!(credential.issuance_date, "2019-10-23T02:23:39Z");
+        assert_eq!(
+            credential.expiration_date.as_deref(),
+            Some("2019-10-24T02:23:39Z")
+        );
+        let status = credential.credential_status.clone().unwrap();
+        assert_eq!(status.status_list_index.as_deref(), Some("0"));
+        assert!(!status.revoked);
+        let proof = credential.proof.clone().unwrap();
+        assert_eq!(
+            proof.verification_method,
+            vc::address_to_did(&mock_env().contract.address)
+        );
 
-        let cred = query_credential(deps.as_ref(), cred_id).unwrap();
-        assert!(cred.revoked);
+        let json = String::from_utf8(to_json_binary(&credential).unwrap().to_vec()).unwrap();
+        assert!(json.contains(r#""@context":["https://www.w3.org/2018/credentials/v1"]"#));
+        assert!(json.contains(r#""credentialSubject":"#));
+
+        let stored = CREDENTIALS.load(&deps.storage, &cred_id).unwrap();
+        assert_eq!(vc::from_verifiable_credential(&credential).unwrap(), stored);
+
+        // Partner credentials carry members we do not model and may use
+        // fractional seconds
+        let partner = format!(
+            r#"{{"@context":["{ctx}","https://partner.example/v1"],"id":"urn:partner:7",
+            "type":["VerifiableCredential","PartnerCitizenship"],"issuer":"did:cosmos:{issuer}",
+            "issuanceDate":"2024-02-29T23:59:59.250Z",
+            "credentialSubject":{{"id":"did:cosmos:{holder}","credentialType":"custom:voter",
+            "commitment":"ff00","region":"north"}},
+            "proof":{{"type":"Ed25519Signature2020","created":"2024-03-01T00:00:00Z",
+            "proofPurpose":"assertionMethod","verificationMethod":"did:cosmos:{issuer}#key-1",
+            "proofValue":"z58DAdFfa9"}}}}"#,
+            ctx = vc::VC_CONTEXT,
+            issuer = issuer1,
+            holder = holder,
+        );
+        let imported: vc::VerifiableCredential = from_json(partner.as_bytes()).unwrap();
+        let cred = vc::from_verifiable_credential(&imported).unwrap();
+        assert_eq!(cred.id, "urn:partner:7");
+        assert_eq!(cred.holder, holder);
+        assert_eq!(cred.issuer, issuer1);
+        assert_eq!(
+            cred.credential_type,
+            CredentialType::Custom("voter".to_string())
+        );
+        assert_eq!(cred.issued_at, 1_709_251_199);
+        assert_eq!(cred.expires_at, 0);
+        assert!(!cred.revoked);
+
+        assert!(vc::parse_timestamp("2023-02-29T00:00:00Z").is_err());
+        assert!(vc::did_to_address("did:web:example.com").is_err());
     }
 
     #[test]
-    fn test_add_remove_issuer() {
+    fn test_type_implications() {
         let mut deps = mock_dependencies();
         let admin = deps.api.addr_make("admin");
-        let issuer2 = deps.api.addr_make("issuer2");
+        let issuer1 = deps.api.addr_make("issuer1");
+        let holder = deps.api.addr_make("holder");
         setup_contract(deps.as_mut());
+        let cred_id = issue_citizenship(deps.as_mut(), &holder, "1");
 
-        let admin_info = message_info(&admin, &[]);
+        let has = |deps: Deps, credential_type: CredentialType| {
+            query_has_valid(deps, mock_env(), holder.to_string(), credential_type, None).unwrap()
+        };
+        let add = |deps: DepsMut, sender: &Addr, from, to, requires_attestation| {
+            let msg = ExecuteMsg::AddTypeImplication {
+                implication: TypeImplication {
+                    from,
+                    to,
+                    requires_attestation,
+                },
+            };
+            execute(deps, mock_env(), message_info(sender, &[]), msg)
+        };
+        assert!(!has(deps.as_ref(), CredentialType::Residency).has_credential);
+
+        let err = add(
+            deps.as_mut(),
+            &holder,
+            Some(CredentialType::Citizenship),
+            CredentialType::Residency,
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        add(
+            deps.as_mut(),
+            &admin,
+            Some(CredentialType::Citizenship),
+            CredentialType::Residency,
+            false,
+        )
+        .unwrap();
+        let resp = has(deps.as_ref(), CredentialType::Residency);
+        assert!(resp.has_credential);
+        assert_eq!(resp.credential_id, Some(cred_id.clone()));
+        assert_eq!(resp.credential_type, Some(CredentialType::Citizenship));
+        let direct = has(deps.as_ref(), CredentialType::Citizenship);
+        assert_eq!(direct.credential_type, Some(CredentialType::Citizenship));
+
+        // Closing the loop, directly or through a wildcard, is rejected
+        for (from, to) in [
+            (Some(CredentialType::Residency), CredentialType::Citizenship),
+            (
+                Some(CredentialType::Citizenship),
+                CredentialType::Citizenship,
+            ),
+            (None, CredentialType::Citizenship),
+        ] {
+            let err = add(deps.as_mut(), &admin, from, to, false).unwrap_err();
+            assert!(matches!(err, ContractError::ImplicationCycle { .. }));
+        }
+
+        // Any type implies AgeOver18 when the issuer could attest it;
+        // Citizenship reaches it transitively through Residency too
+        add(deps.as_mut(), &admin, None, CredentialType::AgeOver18, true).unwrap();
+        let err = add(
+            deps.as_mut(),
+            &admin,
+            Some(CredentialType::AgeOver18),
+            CredentialType::Residency,
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ImplicationCycle { .. }));
+        assert!(has(deps.as_ref(), CredentialType::AgeOver18).has_credential);
 
-        // Add issuer
         let msg = ExecuteMsg::AddIssuer {
-            issuer: issuer2.to_string(),
+            issuer: issuer1.to_string(),
+            scope: Some(IssuerScope {
+                credential_types: vec![CredentialType::Citizenship],
+                max_validity_secs: None,
+                issuance_limit: None,
+            }),
         };
-        execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert!(!has(deps.as_ref(), CredentialType::AgeOver18).has_credential);
+        assert!(has(deps.as_ref(), CredentialType::Residency).has_credential);
 
-        let issuers = query_issuers(deps.as_ref()).unwrap();
-        assert_eq!(issuers.issuers.len(), 2);
+        let implications = query_type_implications(deps.as_ref()).unwrap().implications;
+        assert_eq!(implications.len(), 2);
+        let msg = ExecuteMsg::RemoveTypeImplication {
+            from: Some(CredentialType::Citizenship),
+            to: CredentialType::Residency,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert!(!has(deps.as_ref(), CredentialType::Residency).has_credential);
+    }
 
-        // Remove issuer
-        let msg = ExecuteMsg::RemoveIssuer {
-            issuer: issuer2.to_string(),
+    /// `PRESENTATION_PUBKEY`'s signature over `view_token_digest` of its holder
+    /// for `expires_at = now + 3600` under `mock_env()`
+    const VIEW_TOKEN_SIGNATURE: &str = "75194bd4a0ca59004241d9df21d8c4c76b0668d907ab44a5ab6679920e419e4829717913c85aadeb7a7e5141f4967b20d17d721724fd9a8e06c7dd6f12f9ce0c";
+
+    #[test]
+    fn test_private_credentials() {
+        let mut deps = mock_dependencies();
+        let issuer1 = deps.api.addr_make("issuer1");
+        let app = deps.api.addr_make("clinic_app");
+        let pubkey = hex::decode(PRESENTATION_PUBKEY).unwrap();
+        let holder = deps
+            .api
+            .addr_humanize(&Ripemd160::digest(Sha256::digest(&pubkey)).to_vec().into())
+            .unwrap();
+        setup_contract(deps.as_mut());
+        issue_citizenship(deps.as_mut(), &holder, "1");
+        let msg = ExecuteMsg::IssueCredential {
+            holder: holder.to_string(),
+            credential_type: CredentialType::HealthcareEligibility,
+            commitment: "abc123".to_string(),
+            expires_at: 0,
+            nullifier: nullifier("2"),
+            attributes_root: None,
         };
-        execute(deps.as_mut(), mock_env(), admin_info, msg).unwrap();
+        execute(deps.as_mut(), mock_env(), message_info(&issuer1, &[]), msg).unwrap();
 
-        let issuers = query_issuers(deps.as_ref()).unwrap();
-        assert_eq!(issuers.issuers.len(), 1);
+        let list = |deps: Deps, view_token: Option<ViewToken>| {
+            query_list(deps, mock_env(), holder.to_string(), None, None, view_token)
+        };
+        let healthcare = |deps: Deps, requester: Option<String>| {
+            query_has_valid(
+                deps,
+                mock_env(),
+                holder.to_string(),
+                CredentialType::HealthcareEligibility,
+                requester,
+            )
+            .unwrap()
+        };
+
+        // Healthcare eligibility is private by type
+        let resp = list(deps.as_ref(), None).unwrap();
+        assert_eq!(resp.credentials.len(), 1);
+        assert_eq!(
+            resp.credentials[0].credential_type,
+            CredentialType::Citizenship
+        );
+        assert_eq!(resp.private_count, 1);
+
+        // Its existence is answered to everyone, but only named contracts get its id
+        let res = healthcare(deps.as_ref(), Some(app.to_string()));
+        assert!(res.has_credential);
+        assert!(res.lineage_id.is_some());
+        assert_eq!(res.credential_id, None);
+        let citizenship_id = resp.credentials[0].id.clone();
+        let private_id = HOLDER_CREDENTIALS
+            .prefix(&holder)
+            .keys(deps.as_ref().storage, None, None, Order::Ascending)
+            .map(Result::unwrap)
+            .find(|id| *id != citizenship_id)
+            .unwrap();
+        let err =
+            query_credential(deps.as_ref(), mock_env(), private_id.clone(), None).unwrap_err();
+        assert!(err.to_string().contains("view token"));
+        query_verifiable_credential(deps.as_ref(), mock_env(), private_id.clone(), None)
+            .unwrap_err();
+
+        let msg = ExecuteMsg::GrantCredentialAccess {
+            contract: app.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        assert_eq!(
+            healthcare(deps.as_ref(), Some(app.to_string())).credential_id,
+            Some(private_id.clone())
+        );
+        assert_eq!(healthcare(deps.as_ref(), None).credential_id, None);
+        let access = query_credential_access(deps.as_ref(), holder.to_string()).unwrap();
+        assert_eq!(access.contracts, vec![app.to_string()]);
+
+        // The holder can hide further types
+        let msg = ExecuteMsg::SetCredentialPrivacy {
+            credential_type: CredentialType::Citizenship,
+            private: true,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        let resp = list(deps.as_ref(), None).unwrap();
+        assert!(resp.credentials.is_empty());
+        assert_eq!(resp.private_count, 2);
+        assert_eq!(resp.next_start_after, None);
+
+        // Withheld entries use up the page, and the cursor moves past them
+        let first = query_list(
+            deps.as_ref(),
+            mock_env(),
+            holder.to_string(),
+            None,
+            Some(1),
+            None,
+        )
+        .unwrap();
+        assert!(first.credentials.is_empty());
+        assert_eq!(first.private_count, 1);
+        let next = query_list(
+            deps.as_ref(),
+            mock_env(),
+            holder.to_string(),
+            first.next_start_after.clone(),
+            Some(1),
+            None,
+        )
+        .unwrap();
+        assert_eq!(next.private_count, 1);
+        assert_ne!(next.next_start_after, first.next_start_after);
+
+        let token = ViewToken {
+            expires_at: mock_env().block.time.seconds() + 3600,
+            pubkey: Binary::from(pubkey),
+            signature: Binary::from(hex::decode(VIEW_TOKEN_SIGNATURE).unwrap()),
+        };
+        let resp = list(deps.as_ref(), Some(token.clone())).unwrap();
+        assert_eq!(resp.credentials.len(), 2);
+        assert_eq!(resp.private_count, 0);
+        let cred = query_credential(
+            deps.as_ref(),
+            mock_env(),
+            private_id.clone(),
+            Some(token.clone()),
+        )
+        .unwrap();
+        assert_eq!(cred.credential_type, CredentialType::HealthcareEligibility);
+
+        let mut forged = token;
+        forged.expires_at += 1;
+        let err = list(deps.as_ref(), Some(forged)).unwrap_err();
+        assert!(err.to_string().contains("Invalid view token"));
+
+        let msg = ExecuteMsg::RevokeCredentialAccess {
+            contract: app.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&holder, &[]), msg).unwrap();
+        assert_eq!(
+            healthcare(deps.as_ref(), Some(app.to_string())).credential_id,
+            None
+        );
     }
 }
@@ -0,0 +1,222 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Groth16 verifier over BN254 for proofs produced by circom + snarkjs
+// ─────────────────────────────────────────────────────────────────────────────
+
+use std::str::FromStr;
+
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use cosmwasm_std::Uint256;
+
+use citizen_common::credential::{Groth16Proof, Groth16VerificationKey};
+use citizen_common::errors::ContractError;
+
+/// A verification key with every point parsed and checked.
+pub struct PreparedVerificationKey {
+    alpha_g1: G1Affine,
+    beta_g2: G2Affine,
+    gamma_g2: G2Affine,
+    delta_g2: G2Affine,
+    ic: Vec<G1Affine>,
+}
+
+impl PreparedVerificationKey {
+    pub fn parse(vk: &Groth16VerificationKey) -> Result<Self, ContractError> {
+        if vk.protocol != "groth16" || vk.curve != "bn128" {
+            return Err(invalid(format!(
+                "Unsupported verification key: {} over {}",
+                vk.protocol, vk.curve
+            )));
+        }
+        if vk.ic.len() != vk.n_public as usize + 1 {
+            return Err(invalid(format!(
+                "Verification key has {} IC points for {} public signals",
+                vk.ic.len(),
+                vk.n_public
+            )));
+        }
+
+        Ok(Self {
+            alpha_g1: parse_g1(&vk.vk_alpha_1)?,
+            beta_g2: parse_g2(&vk.vk_beta_2)?,
+            gamma_g2: parse_g2(&vk.vk_gamma_2)?,
+            delta_g2: parse_g2(&vk.vk_delta_2)?,
+            ic: vk
+                .ic
+                .iter()
+                .map(|p| parse_g1(p))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Number of public signals the key expects.
+    pub fn num_public(&self) -> usize {
+        self.ic.len() - 1
+    }
+}
+
+/// Verify a Groth16 proof against a verification key and its public signals.
+pub fn verify(
+    vk: &Groth16VerificationKey,
+    proof: &Groth16Proof,
+    public_inputs: &[String],
+) -> Result<(), ContractError> {
+    let vk = PreparedVerificationKey::parse(vk)?;
+
+    if proof.protocol != "groth16" || proof.curve != "bn128" {
+        return Err(invalid(format!(
+            "Unsupported proof: {} over {}",
+            proof.protocol, proof.curve
+        )));
+    }
+    if public_inputs.len() != vk.num_public() {
+        return Err(invalid(format!(
+            "Expected {} public inputs, got {}",
+            vk.num_public(),
+            public_inputs.len()
+        )));
+    }
+
+    let a = parse_g1(&proof.pi_a)?;
+    let b = parse_g2(&proof.pi_b)?;
+    let c = parse_g1(&proof.pi_c)?;
+
+    // vk_x = IC[0] + Σ input_i · IC[i + 1]
+    let mut vk_x = vk.ic[0].into_group();
+    for (input, point) in public_inputs.iter().zip(vk.ic.iter().skip(1)) {
+        let scalar: Fr = parse_field(input)?;
+        vk_x += *point * scalar;
+    }
+
+    // e(A, B) = e(α, β) · e(vk_x, γ) · e(C, δ)
+    let check = Bn254::multi_pairing(
+        [-a, vk.alpha_g1, vk_x.into_affine(), c],
+        [b, vk.beta_g2, vk.gamma_g2, vk.delta_g2],
+    );
+    if !check.is_zero() {
+        return Err(invalid("Pairing check failed".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Parse a canonical decimal field element, rejecting values ≥ the modulus so
+/// that every public input has exactly one accepted encoding.
+pub fn parse_field<F: PrimeField>(value: &str) -> Result<F, ContractError> {
+    let int = Uint256::from_str(value)
+        .map_err(|_| invalid(format!("Not a decimal field element: {}", value)))?;
+
+    let mut modulus = [0u8; 32];
+    modulus.copy_from_slice(&F::MODULUS.to_bytes_be());
+    if int >= Uint256::from_be_bytes(modulus) {
+        return Err(invalid(format!("Field element out of range: {}", value)));
+    }
+
+    Ok(F::from_be_bytes_mod_order(&int.to_be_bytes()))
+}
+
+/// Render a field element in its one canonical decimal form, so that e.g.
+/// `"01"` and `"1"` name the same value when used as a storage key.
+pub fn canonical_field<F: PrimeField>(value: &str) -> Result<String, ContractError> {
+    let element: F = parse_field(value)?;
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&element.into_bigint().to_bytes_be());
+    Ok(Uint256::from_be_bytes(bytes).to_string())
+}
+
+fn parse_g1(coords: &[String]) -> Result<G1Affine, ContractError> {
+    let [x, y, z] = coords else {
+        return Err(invalid("G1 point must have 3 coordinates".to_string()));
+    };
+    if z == "0" {
+        return Ok(G1Affine::zero());
+    }
+    if z != "1" {
+        return Err(invalid("G1 point must be in affine form".to_string()));
+    }
+
+    let point = G1Affine::new_unchecked(parse_field::<Fq>(x)?, parse_field::<Fq>(y)?);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid("G1 point is not on the curve".to_string()));
+    }
+    Ok(point)
+}
+
+fn parse_g2(coords: &[Vec<String>]) -> Result<G2Affine, ContractError> {
+    let [x, y, z] = coords else {
+        return Err(invalid("G2 point must have 3 coordinates".to_string()));
+    };
+    let z = parse_fq2(z)?;
+    if z.is_zero() {
+        return Ok(G2Affine::zero());
+    }
+    if z != Fq2::from(1u64) {
+        return Err(invalid("G2 point must be in affine form".to_string()));
+    }
+
+    let point = G2Affine::new_unchecked(parse_fq2(x)?, parse_fq2(y)?);
+    if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(invalid(
+            "G2 point is not in the prime-order subgroup".to_string(),
+        ));
+    }
+    Ok(point)
+}
+
+fn parse_fq2(coords: &[String]) -> Result<Fq2, ContractError> {
+    let [c0, c1] = coords else {
+        return Err(invalid("Fq2 element must have 2 coefficients".to_string()));
+    };
+    Ok(Fq2::new(parse_field(c0)?, parse_field(c1)?))
+}
+
+fn invalid(reason: String) -> ContractError {
+    ContractError::InvalidProof { reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::from_json;
+
+    const VK: &str = include_str!("../testdata/citizenship_v1/verification_key.json");
+    const PROOF: &str = include_str!("../testdata/citizenship_v1/proof.json");
+    const PUBLIC: &str = include_str!("../testdata/citizenship_v1/public.json");
+
+    #[test]
+    fn test_verify_fixed_vector() {
+        let vk: Groth16VerificationKey = from_json(VK).unwrap();
+        let proof: Groth16Proof = from_json(PROOF).unwrap();
+        let public: Vec<String> = from_json(PUBLIC).unwrap();
+
+        verify(&vk, &proof, &public).unwrap();
+    }
+
+    #[test]
+    fn test_reject_swapped_proof_points() {
+        let vk: Groth16VerificationKey = from_json(VK).unwrap();
+        let mut proof: Groth16Proof = from_json(PROOF).unwrap();
+        let public: Vec<String> = from_json(PUBLIC).unwrap();
+
+        std::mem::swap(&mut proof.pi_a, &mut proof.pi_c);
+        let err = verify(&vk, &proof, &public).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidProof { .. }));
+    }
+
+    #[test]
+    fn test_reject_non_canonical_field_element() {
+        // r + 1 is congruent to 1 but must not be accepted as an encoding of it
+        let r_plus_one =
+            "21888242871839275222246405745257275088548364400416034343698204186575808495618";
+        assert!(parse_field::<Fr>(r_plus_one).is_err());
+        assert!(parse_field::<Fr>("1").is_ok());
+        assert!(parse_field::<Fr>("0x01").is_err());
+    }
+
+    #[test]
+    fn test_canonical_field_strips_leading_zeros() {
+        assert_eq!(canonical_field::<Fr>("0042").unwrap(), "42");
+        assert_eq!(canonical_field::<Fr>("0").unwrap(), "0");
+    }
+}
@@ -0,0 +1,122 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Credential hooks: contracts notified of credential events through
+// sub-messages whose failures never block the registry.
+// ─────────────────────────────────────────────────────────────────────────────
+
+use cosmwasm_std::{
+    to_json_binary, Deps, DepsMut, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    SubMsg,
+};
+
+use citizen_common::credential::Credential;
+use citizen_common::errors::ContractError;
+use citizen_common::hooks::CredentialHookMsg;
+
+use crate::msg::*;
+use crate::state::*;
+
+/// Reply id of credential hook sub-messages
+pub(crate) const HOOK_REPLY_ID: u64 = 1;
+
+/// Hook gas limit until the admin sets another
+pub(crate) const DEFAULT_HOOK_GAS_LIMIT: u64 = 500_000;
+
+pub(crate) fn revoked_hook(cred: &Credential) -> CredentialHookMsg {
+    CredentialHookMsg::Revoked {
+        credential_id: cred.id.clone(),
+        holder: cred.holder.to_string(),
+        credential_type: cred.credential_type.clone(),
+    }
+}
+
+/// Sub-messages notifying every subscribed contract of a credential event.
+/// Failures, including running out of the hook gas limit, are caught in `reply`,
+/// so a broken subscriber cannot block the registry.
+pub(crate) fn hook_msgs(
+    storage: &dyn Storage,
+    event: &CredentialHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    let gas_limit = HOOK_GAS_LIMIT.load(storage)?;
+    HOOKS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|hook| {
+            let hook = hook?;
+            let msg = event.clone().into_cosmos_msg(hook.as_str())?;
+            Ok(SubMsg::reply_on_error(msg, HOOK_REPLY_ID)
+                .with_gas_limit(gas_limit)
+                .with_payload(to_json_binary(&hook)?))
+        })
+        .collect()
+}
+
+pub(crate) fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can manage hooks".to_string(),
+        });
+    }
+
+    let addr = deps.api.addr_validate(&contract)?;
+    HOOKS.save(deps.storage, &addr, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("contract", contract))
+}
+
+pub(crate) fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can manage hooks".to_string(),
+        });
+    }
+
+    let addr = deps.api.addr_validate(&contract)?;
+    HOOKS.remove(deps.storage, &addr);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("contract", contract))
+}
+
+pub(crate) fn execute_set_hook_gas_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    gas_limit: u64,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can manage hooks".to_string(),
+        });
+    }
+    if gas_limit == 0 {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Hook gas limit must be positive",
+        )));
+    }
+    HOOK_GAS_LIMIT.save(deps.storage, &gas_limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_hook_gas_limit")
+        .add_attribute("gas_limit", gas_limit.to_string()))
+}
+
+pub(crate) fn query_hooks(deps: Deps) -> StdResult<HooksResponse> {
+    let hooks = HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|a| a.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let gas_limit = HOOK_GAS_LIMIT.load(deps.storage)?;
+    Ok(HooksResponse { hooks, gas_limit })
+}
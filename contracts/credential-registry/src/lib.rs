@@ -1,4 +1,12 @@
 pub mod contract;
 pub mod error;
+pub mod groth16;
+mod hooks;
+pub mod merkle;
 pub mod msg;
+mod privacy;
+mod recovery;
+mod relayers;
+mod schemas;
 pub mod state;
+pub mod status_list;
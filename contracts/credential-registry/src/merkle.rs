@@ -0,0 +1,101 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Binary Merkle hashing shared by the status list and attribute commitments
+// ─────────────────────────────────────────────────────────────────────────────
+//
+//   node = H(0x01 || left || right)
+//   H(x) = sha256(x) with the first byte zeroed, so every node is a BN254 scalar
+//
+// Leaves are hashed by their users under prefix 0x00.
+
+use cosmwasm_std::Uint256;
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+pub const LEAF_PREFIX: u8 = 0x00;
+pub const NODE_PREFIX: u8 = 0x01;
+
+pub fn hash(prefix: u8, parts: &[&[u8]]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([prefix]);
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut out: Hash = hasher.finalize().into();
+    out[0] = 0;
+    out
+}
+
+pub fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    hash(NODE_PREFIX, &[left, right])
+}
+
+/// Hash a leaf up to the root along its sibling path; bit `i` of `position`
+/// says whether the node at level `i` is a right child.
+pub fn fold(leaf: Hash, mut position: u64, siblings: &[Hash]) -> Hash {
+    let mut node = leaf;
+    for sibling in siblings {
+        node = if position & 1 == 0 {
+            node_hash(&node, sibling)
+        } else {
+            node_hash(sibling, &node)
+        };
+        position >>= 1;
+    }
+    node
+}
+
+/// Leaf committing to one credential attribute. Attribute and value are
+/// length-prefixed so the boundaries with the salt cannot be shifted.
+pub fn attribute_leaf(attribute: &str, value: &str, salt: &str) -> Hash {
+    let attribute_len = (attribute.len() as u32).to_be_bytes();
+    let value_len = (value.len() as u32).to_be_bytes();
+    hash(
+        LEAF_PREFIX,
+        &[
+            &attribute_len,
+            attribute.as_bytes(),
+            &value_len,
+            value.as_bytes(),
+            salt.as_bytes(),
+        ],
+    )
+}
+
+/// Leaf committing to one entry of an anchored issuance batch. The nullifier
+/// and attributes root are canonical decimals; a missing root is empty.
+pub fn issuance_leaf(
+    holder: &str,
+    credential_type: &str,
+    commitment: &str,
+    expires_at: u64,
+    nullifier_domain: &str,
+    nullifier_value: &str,
+    attributes_root: &str,
+) -> Hash {
+    let fields = [
+        holder,
+        credential_type,
+        commitment,
+        nullifier_domain,
+        nullifier_value,
+        attributes_root,
+    ];
+    let lengths: Vec<[u8; 4]> = fields
+        .iter()
+        .map(|f| (f.len() as u32).to_be_bytes())
+        .collect();
+    let expires_at = expires_at.to_be_bytes();
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(2 * fields.len() + 1);
+    for (field, len) in fields.iter().zip(&lengths) {
+        parts.push(len);
+        parts.push(field.as_bytes());
+    }
+    parts.push(&expires_at);
+    hash(LEAF_PREFIX, &parts)
+}
+
+/// A node as a decimal field element, the form circuits take as public input.
+pub fn to_field(node: &Hash) -> String {
+    Uint256::from_be_bytes(*node).to_string()
+}
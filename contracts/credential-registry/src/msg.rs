@@ -1,5 +1,151 @@
-use citizen_common::credential::{CredentialType, ZkProof};
+use citizen_common::credential::{
+    AttributeClaim, CredentialType, Groth16VerificationKey, Nullifier, PredicateOp,
+    VerificationResult, ZkProof,
+};
+pub use citizen_common::registry::{CredentialCountResponse, HasCredentialResponse};
+use citizen_common::verification::{
+    VerificationAttestation, VerificationConfig, VerificationRequest, VerificationStatus,
+    VerificationType,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
+
+/// Limits and penalties for the social vouching path.
+#[cw_serde]
+pub struct VouchingConfig {
+    /// Vouches each citizen may give per period
+    pub max_vouches_per_period: u32,
+    pub period_secs: u64,
+    /// How long a voucher is barred from vouching after a vouchee is revoked for fraud
+    pub fraud_cooldown_secs: u64,
+}
+
+/// What a trusted issuer may issue. Issuers added without one are unrestricted.
+#[cw_serde]
+pub struct IssuerScope {
+    /// Credential types the issuer may issue
+    pub credential_types: Vec<CredentialType>,
+    /// Longest validity the issuer may grant, in seconds; its credentials must then expire
+    pub max_validity_secs: Option<u64>,
+    /// Cap on how many credentials the issuer may issue per period
+    pub issuance_limit: Option<IssuanceLimit>,
+}
+
+/// Evidence for one claim about a credential's committed attributes.
+#[cw_serde]
+pub enum Disclosure {
+    /// Open one attribute leaf; its value is revealed and the rest stay hidden
+    Reveal {
+        attribute: String,
+        value: String,
+        salt: String,
+        /// Leaf position in the attribute tree
+        position: u64,
+        /// Hex-encoded sibling hashes from the leaf up to the root
+        siblings: Vec<String>,
+    },
+    /// Prove a claim without revealing the attribute, using the predicate
+    /// circuit registered for its attribute and op
+    Predicate {
+        claim: AttributeClaim,
+        proof: ZkProof,
+    },
+}
+
+/// A holder's signed statement that they hold a credential, made for one audience.
+///
+/// The signature is a 64-byte secp256k1 signature over the sha256 of the
+/// concatenation of `"citizen-ledger/presentation/v1"`, the chain id, the
+/// registry address, `credential_id`, `audience` and `nonce` (each prefixed
+/// with its length as a big-endian u32), then `expires_at` as a big-endian u64.
+#[cw_serde]
+pub struct Presentation {
+    pub credential_id: String,
+    /// Who the presentation is for, e.g. a dApp's domain
+    pub audience: String,
+    /// Chosen by the audience, which must refuse nonces it has seen before
+    pub nonce: String,
+    /// Unix seconds from which the presentation is void
+    pub expires_at: u64,
+    /// Holder's compressed secp256k1 public key; must derive the holder's address
+    pub pubkey: Binary,
+    pub signature: Binary,
+}
+
+/// A holder's permission to list or read their private credentials.
+///
+/// Signed like a `Presentation`, over `"citizen-ledger/view/v1"`, the chain id,
+/// the registry address and the holder address (each length-prefixed), then
+/// `expires_at`. Anyone holding the token can use it until it expires.
+#[cw_serde]
+pub struct ViewToken {
+    pub expires_at: u64,
+    /// Holder's compressed secp256k1 public key; must derive the holder's address
+    pub pubkey: Binary,
+    pub signature: Binary,
+}
+
+/// What a `CredentialType::Custom` credential attests and who may issue it.
+#[cw_serde]
+pub struct CredentialSchema {
+    pub description: String,
+    /// Names of the attributes the credential commits to
+    pub attributes: Vec<String>,
+    /// Trusted issuers allowed to issue this type; empty allows any trusted issuer
+    pub allowed_issuers: Vec<String>,
+    /// Validity applied when an issuer passes `expires_at: 0`; 0 means no expiry
+    pub default_validity_secs: u64,
+    /// Verification key version proofs for this type must use
+    pub verification_key: Option<String>,
+    /// Withhold credentials of this type from list queries unless the holder
+    /// presents a view token
+    #[serde(default)]
+    pub private: bool,
+    /// Public signals of the circuit behind `verification_key`
+    #[serde(default)]
+    pub signal_layout: SignalLayout,
+}
+
+/// How a credential proof's public signals are laid out.
+#[cw_serde]
+#[derive(Default)]
+pub enum SignalLayout {
+    /// `[valid, commitment, currentTime, minAge]`, as the citizenship circuit
+    #[default]
+    Citizenship,
+    /// `[valid, commitment, ...]`; signals after the commitment belong to
+    /// the circuit and are not checked by the registry
+    Commitment,
+}
+
+/// Trust state of an issuer's key.
+#[cw_serde]
+#[derive(Default)]
+pub enum IssuerStatus {
+    #[default]
+    Active,
+    /// Cannot issue; credentials already issued stay valid
+    Suspended,
+    /// Cannot issue; credentials issued at or after `since` (unix seconds) are invalid
+    Compromised { since: u64 },
+}
+
+/// One credential to issue, in a batch or an anchored issuance tree.
+#[cw_serde]
+pub struct IssuanceEntry {
+    pub holder: String,
+    pub credential_type: CredentialType,
+    pub commitment: String,
+    pub expires_at: u64,
+    pub nullifier: Nullifier,
+    pub attributes_root: Option<String>,
+}
+
+#[cw_serde]
+pub struct IssuanceLimit {
+    pub max_per_period: u32,
+    pub period_secs: u64,
+}
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -7,50 +153,230 @@ pub struct InstantiateMsg {
     pub admin: String,
     /// Initial set of trusted credential issuers
     pub issuers: Vec<String>,
+    /// Verification service settings; its relayers are authorized on instantiate
+    pub verification_config: Option<VerificationConfig>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Issue a new credential with a ZK proof commitment
+    /// Issue a new credential with a ZK proof commitment.
+    /// The holder's identity nullifier is spent and cannot be used again.
     IssueCredential {
         holder: String,
         credential_type: CredentialType,
         commitment: String,
         expires_at: u64,
+        nullifier: Nullifier,
+        /// Merkle root over the credential's salted attribute leaves
+        /// (`merkle::attribute_leaf`), as a decimal field element
+        attributes_root: Option<String>,
+    },
+    /// Issue up to 100 credentials at once. Entries failing any check are
+    /// skipped; the `accepted` and `rejected` attributes count the outcome.
+    BatchIssue { entries: Vec<IssuanceEntry> },
+    /// Commit to a tree of issuance entries (`merkle::issuance_leaf`) by its
+    /// hex-encoded root, for holders to claim later
+    AnchorIssuanceRoot { root: String },
+    /// Claim a credential anchored under `root` with its inclusion proof.
+    /// The issuer's authorization is checked at claim time.
+    ClaimAnchoredCredential {
+        root: String,
+        entry: IssuanceEntry,
+        /// Leaf position in the issuance tree
+        position: u64,
+        /// Hex-encoded sibling hashes from the leaf up to the root
+        siblings: Vec<String>,
     },
-    /// Verify a credential using a ZK proof
+    /// Verify a credential using a Groth16 proof of the citizenship circuit.
+    /// The proof's currentTime must be at most an hour old.
     VerifyCredential {
         credential_id: String,
         proof: ZkProof,
+        /// Age in seconds the proof's minAge signal must reach; only
+        /// citizenship-layout proofs carry one
+        min_age_seconds: Option<u64>,
+    },
+    /// Replace a credential with a successor carrying a fresh commitment and
+    /// expiry (original issuer only). The successor keeps the holder, type,
+    /// nullifier and lineage; the old credential becomes superseded.
+    RenewCredential {
+        credential_id: String,
+        commitment: String,
+        expires_at: u64,
+        attributes_root: Option<String>,
     },
     /// Revoke a credential (issuer or admin only)
     RevokeCredential {
         credential_id: String,
         reason: String,
+        /// Revoked for identity fraud: the citizens who vouched for it are penalized
+        #[serde(default)]
+        fraud: bool,
     },
     /// Add a trusted issuer (admin only)
-    AddIssuer { issuer: String },
+    /// Omitting `scope` trusts the issuer for every credential type.
+    /// Adding an existing issuer replaces its scope.
+    AddIssuer {
+        issuer: String,
+        scope: Option<IssuerScope>,
+    },
     /// Remove a trusted issuer (admin only)
     RemoveIssuer { issuer: String },
+    /// Register the schema of the custom type `Custom(name)` (admin only).
+    /// Custom credentials can only be issued for registered schemas.
+    RegisterCredentialSchema {
+        name: String,
+        schema: CredentialSchema,
+    },
+    /// Replace the schema of a registered custom type (admin only). The
+    /// event records the previous schema.
+    UpdateCredentialSchema {
+        name: String,
+        schema: CredentialSchema,
+    },
+    /// Suspend, reinstate or flag an issuer as compromised (admin only).
+    /// Works on removed issuers so their past credentials can still be cut off.
+    SetIssuerStatus {
+        issuer: String,
+        status: IssuerStatus,
+    },
+    /// Revoke one page of an issuer's credentials issued at or after `issued_since`
+    /// (admin only). Defaults to the compromise cutoff, or all credentials if none.
+    /// Continue with the `next_start_after` attribute until it is empty.
+    RevokeIssuerCredentials {
+        issuer: String,
+        issued_since: Option<u64>,
+        reason: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Process up to `limit` credentials that expired since the last sweep,
+    /// emitting a `credential_expired` event for each. Callable by anyone.
+    SweepExpired { limit: Option<u32> },
+    /// Build up to `limit` entries of the indexes `migrate` left pending
+    /// (admin only). Other messages are rejected until the backfill is done;
+    /// repeat while the `done` attribute is `false`.
+    BackfillIndexes { limit: Option<u32> },
+    /// Make credentials of `implication.from` also satisfy `HasValidCredential`
+    /// queries for `implication.to` (admin only). Replaces an existing rule for
+    /// the same pair; rules that would close a cycle are rejected.
+    AddTypeImplication { implication: TypeImplication },
+    /// Remove an implication rule (admin only)
+    RemoveTypeImplication {
+        from: Option<CredentialType>,
+        to: CredentialType,
+    },
+    /// Keep the sender's credentials of a type out of list queries, or stop
+    /// doing so. Types that are private by schema stay private.
+    SetCredentialPrivacy {
+        credential_type: CredentialType,
+        private: bool,
+    },
+    /// Let a contract see the ids of the sender's private credentials through
+    /// `HasValidCredential`
+    GrantCredentialAccess { contract: String },
+    /// Withdraw a contract's access to the sender's private credentials
+    RevokeCredentialAccess { contract: String },
+    /// Subscribe a contract to `CredentialHookMsg` notifications (admin only)
+    AddHook { contract: String },
+    /// Unsubscribe a contract from credential notifications (admin only)
+    RemoveHook { contract: String },
+    /// Set the gas each hook sub-message may use (admin only). A hook that
+    /// runs out fails like any other and leaves the triggering action standing.
+    SetHookGasLimit { gas_limit: u64 },
     /// Transfer admin role
     TransferAdmin { new_admin: String },
+    /// Open a verification request for the sender
+    RequestVerification {
+        verification_type: VerificationType,
+        data_hash: String,
+    },
+    /// Mark a pending request as picked up by a relayer
+    StartVerificationReview { request_id: String },
+    /// Record that an open request outlived `result_validity_secs`
+    ExpireVerificationRequest { request_id: String },
+    /// Update verification service settings (admin only)
+    UpdateVerificationConfig { config: VerificationConfig },
     /// Submit a verification result from an authorized relayer (step 28-29)
+    /// for an open request. The credential is issued once enough relayers
+    /// submit the same result.
     SubmitVerificationResult {
         request_id: String,
         applicant: String,
         credential_type: CredentialType,
         commitment: String,
         approved: bool,
+        /// Spent only if the result is approved
+        nullifier: Nullifier,
     },
-    /// Request key recovery – migrates credentials from old address to new (step 30)
+    /// Request key recovery – migrates credentials from old address to new (step 30).
+    /// With an approved `RecoveryReverification` request for the same identity
+    /// the timelock starts at once; otherwise the old address's guardians must approve.
     RequestKeyRecovery {
         old_address: String,
-        reverification_id: String,
+        reverification_id: Option<String>,
     },
     /// Execute a pending recovery after timelock expires
     ExecuteRecovery { recovery_id: String },
-    /// Contest a recovery (only callable by old address during timelock)
+    /// Contest a recovery (only callable by old address before it executes)
     ContestRecovery { recovery_id: String },
+    /// Register the sender's recovery guardians; an empty list removes them
+    SetGuardians {
+        guardians: Vec<String>,
+        threshold: u32,
+    },
+    /// Approve a pending recovery as a guardian of the old address
+    ApproveRecovery { recovery_id: String },
+    /// Withdraw a pending recovery (requesting address only)
+    CancelRecovery { recovery_id: String },
+    /// Settle a pending or contested recovery (admin only). Approval skips the
+    /// guardian approvals but not the timelock: it restarts the timelock, and
+    /// `ExecuteRecovery` completes the recovery once it expires.
+    ResolveRecovery { recovery_id: String, approve: bool },
+    /// Ask existing citizens to vouch for the sender. Once
+    /// `min_social_vouches` citizens vouch, a Citizenship credential is issued.
+    RequestSocialVouching {
+        data_hash: String,
+        commitment: String,
+        nullifier: Nullifier,
+    },
+    /// Vouch for a social vouching request (Citizenship holders only)
+    Vouch { request_id: String },
+    /// Update social vouching limits and penalties (admin only)
+    UpdateVouchingConfig { config: VouchingConfig },
+    /// Authorize a verification relayer (admin only)
+    AddRelayer { relayer: String },
+    /// Revoke a relayer; its pending attestations stop counting (admin only)
+    RemoveRelayer { relayer: String },
+    /// Set how many relayers must agree before a result takes effect (admin only)
+    SetAttestationThreshold { threshold: u32 },
+    /// Register a circuit verification key for a credential type (admin only).
+    /// Proofs select it by passing `version` as their `vk_reference`.
+    RegisterVerificationKey {
+        credential_type: CredentialType,
+        version: String,
+        verification_key: Groth16VerificationKey,
+    },
+    /// Register the circuit that proves `attribute op value` over a credential's
+    /// attribute root (admin only). Its public signals must be
+    /// `[valid, attributes_root, current_time, value]`.
+    RegisterPredicateKey {
+        attribute: String,
+        op: PredicateOp,
+        version: String,
+        verification_key: Groth16VerificationKey,
+    },
+    /// Stop accepting proofs made against a verification key (admin only)
+    DeprecateVerificationKey {
+        credential_type: CredentialType,
+        version: String,
+    },
+    /// Stop accepting proofs made against a predicate circuit (admin only)
+    DeprecatePredicateKey {
+        attribute: String,
+        op: PredicateOp,
+        version: String,
+    },
 }
 
 /// Message for contract migration
@@ -60,29 +386,137 @@ pub struct MigrateMsg {}
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    /// Get credential by ID
+    /// Get credential by ID. Private credentials need a valid view token
+    /// from their holder.
     #[returns(CredentialResponse)]
-    GetCredential { credential_id: String },
+    GetCredential {
+        credential_id: String,
+        view_token: Option<ViewToken>,
+    },
+
+    /// A credential in W3C Verifiable Credential form, with the registry as
+    /// status provider and attester. Private credentials need a valid view
+    /// token from their holder.
+    #[returns(citizen_common::vc::VerifiableCredential)]
+    GetVerifiableCredential {
+        credential_id: String,
+        view_token: Option<ViewToken>,
+    },
+
+    /// Check a holder-signed presentation against the credential's current
+    /// state. Failures are reported in the result rather than as errors.
+    #[returns(VerificationResult)]
+    VerifyPresentation { presentation: Presentation },
+
+    /// An anchored issuance root and how many credentials were claimed under it
+    #[returns(IssuanceRootResponse)]
+    GetIssuanceRoot { root: String },
+
+    /// Walk the renewal chain a credential belongs to
+    #[returns(LineageResponse)]
+    GetLineage { credential_id: String },
 
     /// Check if a holder has a valid credential of a given type
     #[returns(HasCredentialResponse)]
+    /// Private credentials count like any other, so gating contracts see every
+    /// holder. Queries carry no sender, so `requester` is an unchecked hint:
+    /// a private credential's id is only returned when it names a registered
+    /// hook or a contract on the holder's access list.
     HasValidCredential {
         holder: String,
         credential_type: CredentialType,
+        requester: Option<String>,
     },
 
-    /// List all credentials for a holder
+    /// Number of holders with a currently valid credential of a type, e.g. the
+    /// citizen population. Errors while more than 100 expired credentials
+    /// await `SweepExpired`.
+    #[returns(CredentialCountResponse)]
+    CountValidCredentials { credential_type: CredentialType },
+
+    /// List all credentials for a holder. Private ones are only counted unless
+    /// a valid view token from the holder is given.
     #[returns(CredentialsListResponse)]
     ListCredentials {
         holder: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        view_token: Option<ViewToken>,
+    },
+
+    /// Contracts a holder granted access to their private credentials
+    #[returns(CredentialAccessResponse)]
+    ListCredentialAccess { holder: String },
+
+    /// List credentials issued by an issuer
+    #[returns(CredentialsListResponse)]
+    ListCredentialsByIssuer {
+        issuer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Unrevoked credentials that are still valid but expire before `before`,
+    /// soonest first
+    #[returns(CredentialsListResponse)]
+    ListExpiringCredentials {
+        before: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Unrevoked credentials that have expired, oldest expiry first
+    #[returns(CredentialsListResponse)]
+    ListExpiredCredentials {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Revocation status list root at an epoch (default: the current one).
+    /// Circuits take `root_field` as a public input to prove non-revocation.
+    #[returns(RevocationRootResponse)]
+    RevocationRoot { epoch: Option<u64> },
+
+    /// Current status list chunk and Merkle path for a status index,
+    /// the private witness for a non-revocation proof
+    #[returns(RevocationWitnessResponse)]
+    RevocationWitness { status_index: u64 },
+
+    /// Check disclosures about a credential's attributes. Failures are reported
+    /// in the result rather than as errors.
+    #[returns(VerificationResult)]
+    VerifyDisclosure {
+        credential_id: String,
+        disclosures: Vec<Disclosure>,
+    },
+
+    /// Describe a registered custom credential type
+    #[returns(CredentialSchemaResponse)]
+    GetCredentialSchema { name: String },
+
+    /// List registered custom credential types
+    #[returns(CredentialSchemasResponse)]
+    ListCredentialSchemas {
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
 
     /// List all trusted issuers
     #[returns(IssuersResponse)]
     ListIssuers {},
 
+    /// Get an issuer's status, including removed issuers
+    #[returns(IssuerStatusResponse)]
+    IssuerStatus { issuer: String },
+
+    /// Governance-managed credential type implications
+    #[returns(TypeImplicationsResponse)]
+    ListTypeImplications {},
+
+    /// Contracts subscribed to credential notifications
+    #[returns(HooksResponse)]
+    ListHooks {},
+
     /// Get contract config
     #[returns(ConfigResponse)]
     Config {},
@@ -90,6 +524,81 @@ pub enum QueryMsg {
     /// Get a recovery request by ID
     #[returns(RecoveryResponse)]
     GetRecovery { recovery_id: String },
+
+    /// List unfinished recoveries where an address is the old or new address
+    #[returns(RecoveriesResponse)]
+    ListPendingRecoveries {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Get a holder's recovery guardians
+    #[returns(GuardiansResponse)]
+    GetGuardians { holder: String },
+
+    /// Get a verification request by ID
+    #[returns(VerificationRequest)]
+    GetVerificationRequest { request_id: String },
+
+    /// List verification requests opened by an applicant
+    #[returns(VerificationRequestsResponse)]
+    ListVerificationRequestsByApplicant {
+        applicant: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// List verification requests by recorded status. Open requests past
+    /// `result_validity_secs` are listed under their open status, reported as
+    /// Expired, until `ExpireVerificationRequest` records it.
+    #[returns(VerificationRequestsResponse)]
+    ListVerificationRequestsByStatus {
+        status: VerificationStatus,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Get verification service settings
+    #[returns(VerificationConfig)]
+    VerificationConfig {},
+
+    /// Vouches given so far for a social vouching request
+    #[returns(VouchesResponse)]
+    GetVouches { request_id: String },
+
+    /// A citizen's remaining vouches and any fraud penalty
+    #[returns(VoucherResponse)]
+    GetVoucher { voucher: String },
+
+    /// Get social vouching limits and penalties
+    #[returns(VouchingConfig)]
+    VouchingConfig {},
+
+    /// List authorized relayers and the attestation threshold
+    #[returns(RelayersResponse)]
+    ListRelayers {},
+
+    /// Attestations submitted so far for a verification request
+    #[returns(AttestationsResponse)]
+    GetAttestations { request_id: String },
+
+    /// Check whether an identity nullifier has already been spent
+    #[returns(NullifierResponse)]
+    IsNullifierSpent { nullifier: Nullifier },
+
+    /// Get a verification key by credential type and version
+    #[returns(VerificationKeyResponse)]
+    GetVerificationKey {
+        credential_type: CredentialType,
+        version: String,
+    },
+
+    /// List registered verification keys, optionally for one credential type
+    #[returns(VerificationKeysResponse)]
+    ListVerificationKeys {
+        credential_type: Option<CredentialType>,
+    },
 }
 
 // ── Response types ──────────────────────────────────────────────────
@@ -104,22 +613,126 @@ pub struct CredentialResponse {
     pub issued_at: u64,
     pub expires_at: u64,
     pub revoked: bool,
+    /// Set once `SweepExpired` has processed the credential's expiry
+    pub expired: bool,
+    /// Position of the credential's bit in the revocation status list
+    pub status_index: Option<u64>,
+    /// Merkle root over the credential's attributes, if it commits to any
+    pub attributes_root: Option<String>,
+    /// Id of the first credential in this credential's renewal chain
+    pub lineage_id: String,
+    pub previous_id: Option<String>,
+    /// Set once the credential has been renewed
+    pub superseded_by: Option<String>,
 }
 
 #[cw_serde]
-pub struct HasCredentialResponse {
-    pub has_credential: bool,
-    pub credential_id: Option<String>,
+pub struct LineageResponse {
+    pub lineage_id: String,
+    /// Every credential in the lineage, oldest first
+    pub credential_ids: Vec<String>,
+    /// The credential that has not been superseded
+    pub current: String,
+}
+
+#[cw_serde]
+pub struct IssuanceRootResponse {
+    pub root: String,
+    pub issuer: String,
+    pub anchored_at: u64,
+    pub claimed: u64,
 }
 
 #[cw_serde]
 pub struct CredentialsListResponse {
     pub credentials: Vec<CredentialResponse>,
+    /// Private credentials withheld from this page
+    pub private_count: u32,
+    /// Last credential this page examined when more may follow; pass it as
+    /// `start_after` to continue
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct CredentialAccessResponse {
+    pub holder: String,
+    pub contracts: Vec<String>,
 }
 
 #[cw_serde]
 pub struct IssuersResponse {
-    pub issuers: Vec<String>,
+    pub issuers: Vec<IssuerInfo>,
+}
+
+#[cw_serde]
+pub struct IssuerInfo {
+    pub issuer: String,
+    /// `None` for unrestricted issuers
+    pub scope: Option<IssuerScope>,
+    pub status: IssuerStatus,
+}
+
+#[cw_serde]
+pub struct RevocationRootResponse {
+    pub epoch: u64,
+    /// Hex-encoded root
+    pub root: String,
+    /// The root as a decimal BN254 scalar
+    pub root_field: String,
+    pub updated_at: u64,
+    /// Merkle depth above the 256-bit chunk leaves
+    pub depth: u32,
+}
+
+#[cw_serde]
+pub struct RevocationWitnessResponse {
+    pub status_index: u64,
+    pub epoch: u64,
+    pub root: String,
+    /// Hex-encoded 256-bit chunk containing the status bit
+    pub chunk: String,
+    /// Hex-encoded sibling hashes from the leaf up to the root
+    pub siblings: Vec<String>,
+}
+
+#[cw_serde]
+pub struct CredentialSchemaResponse {
+    pub name: String,
+    pub schema: CredentialSchema,
+    pub registered_at: u64,
+}
+
+#[cw_serde]
+pub struct CredentialSchemasResponse {
+    pub schemas: Vec<CredentialSchemaResponse>,
+}
+
+/// Holding a valid `from` credential also counts as holding `to`.
+#[cw_serde]
+pub struct TypeImplication {
+    /// `None` matches every type other than `to`
+    pub from: Option<CredentialType>,
+    pub to: CredentialType,
+    /// Only apply when the credential's issuer could itself issue `to`
+    pub requires_attestation: bool,
+}
+
+#[cw_serde]
+pub struct TypeImplicationsResponse {
+    pub implications: Vec<TypeImplication>,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+    /// Gas each hook sub-message may use
+    pub gas_limit: u64,
+}
+
+#[cw_serde]
+pub struct IssuerStatusResponse {
+    pub issuer: String,
+    pub status: IssuerStatus,
 }
 
 #[cw_serde]
@@ -136,4 +749,75 @@ pub struct RecoveryResponse {
     pub status: String,
     pub requested_at: u64,
     pub execute_after: u64,
+    pub approvals: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RecoveriesResponse {
+    pub recoveries: Vec<RecoveryResponse>,
+}
+
+#[cw_serde]
+pub struct GuardiansResponse {
+    pub holder: String,
+    pub guardians: Vec<String>,
+    pub threshold: u32,
+}
+
+#[cw_serde]
+pub struct VouchesResponse {
+    pub request_id: String,
+    pub vouchers: Vec<String>,
+    pub required: u32,
+}
+
+#[cw_serde]
+pub struct VoucherResponse {
+    pub voucher: String,
+    pub vouches_remaining: u32,
+    pub period_ends_at: u64,
+    /// Vouching is barred until this time after a fraud revocation (0 = never penalized)
+    pub cooldown_until: u64,
+    pub fraud_count: u32,
+}
+
+#[cw_serde]
+pub struct RelayersResponse {
+    pub relayers: Vec<String>,
+    pub threshold: u32,
+}
+
+#[cw_serde]
+pub struct AttestationsResponse {
+    pub request_id: String,
+    pub threshold: u32,
+    pub attestations: Vec<VerificationAttestation>,
+    /// Stored status of the request, if it exists
+    pub status: Option<VerificationStatus>,
+}
+
+#[cw_serde]
+pub struct VerificationRequestsResponse {
+    pub requests: Vec<VerificationRequest>,
+}
+
+#[cw_serde]
+pub struct NullifierResponse {
+    pub spent: bool,
+    pub spent_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct VerificationKeyResponse {
+    /// Credential type the key verifies, as its display string
+    pub credential_type: String,
+    pub version: String,
+    pub verification_key: Groth16VerificationKey,
+    pub deprecated: bool,
+    pub registered_at: u64,
+}
+
+#[cw_serde]
+pub struct VerificationKeysResponse {
+    pub keys: Vec<VerificationKeyResponse>,
 }
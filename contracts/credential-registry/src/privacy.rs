@@ -0,0 +1,91 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Holder privacy: credential types kept out of list queries and the
+// contracts a holder lets see them.
+// ─────────────────────────────────────────────────────────────────────────────
+
+use cosmwasm_std::{Addr, Deps, DepsMut, MessageInfo, Order, Response, StdResult, Storage};
+
+use citizen_common::credential::CredentialType;
+use citizen_common::errors::ContractError;
+
+use crate::msg::*;
+use crate::state::*;
+
+pub(crate) fn execute_set_credential_privacy(
+    deps: DepsMut,
+    info: MessageInfo,
+    credential_type: CredentialType,
+    private: bool,
+) -> Result<Response, ContractError> {
+    let type_str = credential_type.to_string();
+    if private {
+        HOLDER_PRIVATE_TYPES.save(deps.storage, (&info.sender, &type_str), &true)?;
+    } else {
+        HOLDER_PRIVATE_TYPES.remove(deps.storage, (&info.sender, &type_str));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_credential_privacy")
+        .add_attribute("holder", info.sender)
+        .add_attribute("credential_type", type_str)
+        .add_attribute("private", private.to_string()))
+}
+
+pub(crate) fn execute_grant_credential_access(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&contract)?;
+    CREDENTIAL_ACCESS.save(deps.storage, (&info.sender, &addr), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_credential_access")
+        .add_attribute("holder", info.sender)
+        .add_attribute("contract", contract))
+}
+
+pub(crate) fn execute_revoke_credential_access(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&contract)?;
+    CREDENTIAL_ACCESS.remove(deps.storage, (&info.sender, &addr));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_credential_access")
+        .add_attribute("holder", info.sender)
+        .add_attribute("contract", contract))
+}
+
+/// Whether a credential is withheld from list queries: healthcare eligibility
+/// always is, custom types when their schema says so, and any type the holder
+/// marked private.
+pub(crate) fn is_private(
+    storage: &dyn Storage,
+    holder: &Addr,
+    credential_type: &CredentialType,
+) -> StdResult<bool> {
+    let by_type = match credential_type {
+        CredentialType::HealthcareEligibility => true,
+        CredentialType::Custom(name) => CREDENTIAL_SCHEMAS
+            .may_load(storage, name)?
+            .is_some_and(|s| s.private),
+        _ => false,
+    };
+    Ok(by_type || HOLDER_PRIVATE_TYPES.has(storage, (holder, &credential_type.to_string())))
+}
+
+pub(crate) fn query_credential_access(
+    deps: Deps,
+    holder: String,
+) -> StdResult<CredentialAccessResponse> {
+    let holder_addr = deps.api.addr_validate(&holder)?;
+    let contracts = CREDENTIAL_ACCESS
+        .prefix(&holder_addr)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|a| a.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(CredentialAccessResponse { holder, contracts })
+}
@@ -0,0 +1,520 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Key recovery: guardians or re-verification move a holder's credentials
+// to a new address after a timelock.
+// ─────────────────────────────────────────────────────────────────────────────
+
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage,
+};
+use cw_storage_plus::Bound;
+use sha2::{Digest, Sha256};
+
+use citizen_common::errors::ContractError;
+use citizen_common::hooks::CredentialHookMsg;
+use citizen_common::verification::VerificationType;
+
+use crate::contract::{count_active, counted_as_active, uncount_active};
+use crate::hooks::hook_msgs;
+use crate::msg::*;
+use crate::state::*;
+
+pub(crate) fn execute_set_guardians(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardians: Vec<String>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    // An empty set opts out of guardian recovery
+    if guardians.is_empty() {
+        GUARDIANS.remove(deps.storage, &info.sender);
+        return Ok(Response::new()
+            .add_attribute("action", "clear_guardians")
+            .add_attribute("holder", info.sender.as_str()));
+    }
+
+    let mut guardian_addrs: Vec<Addr> = Vec::with_capacity(guardians.len());
+    for guardian in &guardians {
+        let addr = deps.api.addr_validate(guardian)?;
+        if addr == info.sender || guardian_addrs.contains(&addr) {
+            return Err(ContractError::InvalidThreshold {
+                reason: "Guardians must be distinct and exclude the holder".to_string(),
+            });
+        }
+        guardian_addrs.push(addr);
+    }
+    if threshold == 0 || threshold as usize > guardian_addrs.len() {
+        return Err(ContractError::InvalidThreshold {
+            reason: format!(
+                "Threshold must be between 1 and {} guardians",
+                guardian_addrs.len()
+            ),
+        });
+    }
+
+    GUARDIANS.save(
+        deps.storage,
+        &info.sender,
+        &GuardianSet {
+            guardians: guardian_addrs,
+            threshold,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_guardians")
+        .add_attribute("holder", info.sender.as_str())
+        .add_attribute("guardians", guardians.len().to_string())
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub(crate) fn execute_request_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    old_address: String,
+    reverification_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let old_addr = deps.api.addr_validate(&old_address)?;
+    if old_addr == info.sender {
+        return Err(ContractError::Unauthorized {
+            reason: "Cannot recover to the same address".to_string(),
+        });
+    }
+    let timelock = RECOVERY_TIMELOCK.load(deps.storage)?;
+
+    let recovery_id_raw = format!(
+        "recovery:{}:{}:{}",
+        old_address, info.sender, env.block.height
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(recovery_id_raw.as_bytes());
+    let recovery_id = hex::encode(hasher.finalize());
+
+    if RECOVERIES.has(deps.storage, &recovery_id) {
+        return Err(ContractError::Unauthorized {
+            reason: "Recovery already requested".to_string(),
+        });
+    }
+
+    // A matching re-verification proves the identity outright; otherwise the
+    // old address's guardians have to approve before the timelock starts
+    let (status, execute_after) = match &reverification_id {
+        Some(request_id) => {
+            consume_reverification(deps.storage, request_id, &old_addr, &info.sender)?;
+            (
+                StoredRecoveryStatus::TimelockWaiting,
+                env.block.height + timelock,
+            )
+        }
+        None => {
+            if !GUARDIANS.has(deps.storage, &old_addr) {
+                return Err(ContractError::Unauthorized {
+                    reason: "No guardians registered; recovery requires a re-verification"
+                        .to_string(),
+                });
+            }
+            (StoredRecoveryStatus::PendingVerification, 0)
+        }
+    };
+
+    let recovery = StoredRecovery {
+        recovery_id: recovery_id.clone(),
+        old_address: old_addr.clone(),
+        new_address: info.sender.clone(),
+        reverification_id,
+        status,
+        requested_at: env.block.height,
+        execute_after,
+        approvals: vec![],
+        admin_approved: false,
+    };
+
+    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+    ADDRESS_RECOVERIES.save(deps.storage, (&old_addr, &recovery_id), &true)?;
+    ADDRESS_RECOVERIES.save(deps.storage, (&info.sender, &recovery_id), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_recovery")
+        .add_attribute("recovery_id", recovery_id)
+        .add_attribute("old_address", old_address)
+        .add_attribute("new_address", info.sender.as_str())
+        .add_attribute("execute_after", recovery.execute_after.to_string()))
+}
+
+/// Check that an approved re-verification was made by the new address for
+/// the identity behind the old one, and use it up.
+pub(crate) fn consume_reverification(
+    storage: &mut dyn Storage,
+    request_id: &str,
+    old_address: &Addr,
+    new_address: &Addr,
+) -> Result<(), ContractError> {
+    let request = VERIFICATION_REQUESTS.may_load(storage, request_id)?.ok_or(
+        ContractError::VerificationRequestNotFound {
+            request_id: request_id.to_string(),
+        },
+    )?;
+    if request.verification_type != VerificationType::RecoveryReverification
+        || request.applicant != new_address.as_str()
+    {
+        return Err(ContractError::Unauthorized {
+            reason: "Re-verification does not belong to this recovery".to_string(),
+        });
+    }
+
+    let nullifier =
+        REVERIFICATIONS
+            .may_load(storage, request_id)?
+            .ok_or(ContractError::Unauthorized {
+                reason: "Re-verification is not approved or was already used".to_string(),
+            })?;
+
+    let cred_ids: Vec<String> = HOLDER_CREDENTIALS
+        .prefix(old_address)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut same_identity = false;
+    for cred_id in &cred_ids {
+        if CREDENTIAL_NULLIFIERS.may_load(storage, cred_id)?.as_ref() == Some(&nullifier) {
+            same_identity = true;
+            break;
+        }
+    }
+    if !same_identity {
+        return Err(ContractError::Unauthorized {
+            reason: "Re-verified identity does not match the address being recovered".to_string(),
+        });
+    }
+
+    REVERIFICATIONS.remove(storage, request_id);
+    Ok(())
+}
+
+pub(crate) fn execute_approve_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recovery_id: String,
+) -> Result<Response, ContractError> {
+    let mut recovery = load_recovery(deps.as_ref(), &recovery_id)?;
+
+    if !matches!(recovery.status, StoredRecoveryStatus::PendingVerification) {
+        return Err(ContractError::Unauthorized {
+            reason: "Recovery is not awaiting guardian approval".to_string(),
+        });
+    }
+
+    let guardians = GUARDIANS
+        .may_load(deps.storage, &recovery.old_address)?
+        .unwrap_or(GuardianSet {
+            guardians: vec![],
+            threshold: 0,
+        });
+    if !guardians.guardians.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            reason: "Only a guardian of the old address can approve recovery".to_string(),
+        });
+    }
+    if recovery.approvals.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            reason: "Guardian already approved this recovery".to_string(),
+        });
+    }
+
+    recovery.approvals.push(info.sender.clone());
+    // Approvals from guardians removed since the request no longer count
+    let approvals = recovery
+        .approvals
+        .iter()
+        .filter(|a| guardians.guardians.contains(a))
+        .count() as u32;
+    if approvals >= guardians.threshold {
+        let timelock = RECOVERY_TIMELOCK.load(deps.storage)?;
+        recovery.status = StoredRecoveryStatus::TimelockWaiting;
+        recovery.execute_after = env.block.height + timelock;
+    }
+    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_recovery")
+        .add_attribute("recovery_id", recovery_id)
+        .add_attribute("guardian", info.sender.as_str())
+        .add_attribute("approvals", approvals.to_string())
+        .add_attribute("threshold", guardians.threshold.to_string()))
+}
+
+pub(crate) fn execute_execute_recovery(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    recovery_id: String,
+) -> Result<Response, ContractError> {
+    let mut recovery = load_recovery(deps.as_ref(), &recovery_id)?;
+
+    if !matches!(recovery.status, StoredRecoveryStatus::TimelockWaiting) {
+        return Err(ContractError::Unauthorized {
+            reason: "Recovery is not in TimelockWaiting state".to_string(),
+        });
+    }
+
+    if env.block.height < recovery.execute_after {
+        return Err(ContractError::TimelockNotExpired {
+            execute_at: recovery.execute_after,
+        });
+    }
+
+    let migrated = migrate_holder(deps.storage, &recovery.old_address, &recovery.new_address)?;
+    let hooks = hook_msgs(deps.storage, &migrated_hook(&recovery))?;
+
+    recovery.status = StoredRecoveryStatus::Executed;
+    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "execute_recovery")
+        .add_attribute("recovery_id", recovery_id)
+        .add_attribute("credentials_migrated", migrated.to_string()))
+}
+
+/// Move every credential, its nullifier and the guardian set from one address to another.
+pub(crate) fn migrate_holder(
+    storage: &mut dyn Storage,
+    old_address: &Addr,
+    new_address: &Addr,
+) -> Result<usize, ContractError> {
+    let old_creds = HOLDER_CREDENTIALS
+        .prefix(old_address)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for cred_id in &old_creds {
+        // Update credential holder, moving its count to the new address
+        let mut cred = CREDENTIALS.load(storage, cred_id)?;
+        let counted = counted_as_active(storage, &cred)?;
+        if counted {
+            uncount_active(storage, &cred)?;
+        }
+        cred.holder = new_address.clone();
+        CREDENTIALS.save(storage, cred_id, &cred)?;
+        if counted {
+            count_active(storage, &cred)?;
+        }
+        // Move holder index
+        HOLDER_CREDENTIALS.remove(storage, (old_address, cred_id));
+        HOLDER_CREDENTIALS.save(storage, (new_address, cred_id), &true)?;
+        // The identity is unchanged, so its nullifier moves with it instead of being re-spent
+        if let Some(n) = CREDENTIAL_NULLIFIERS.may_load(storage, cred_id)? {
+            NULLIFIERS.update(storage, (&n.domain, &n.value), |r| {
+                r.map(|mut r| {
+                    r.holder = new_address.clone();
+                    r
+                })
+                .ok_or_else(|| StdError::not_found("nullifier"))
+            })?;
+        }
+    }
+
+    // Privacy choices and access grants follow the holder
+    let private_types = HOLDER_PRIVATE_TYPES
+        .prefix(old_address)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for credential_type in private_types {
+        HOLDER_PRIVATE_TYPES.remove(storage, (old_address, &credential_type));
+        HOLDER_PRIVATE_TYPES.save(storage, (new_address, &credential_type), &true)?;
+    }
+    let grantees = CREDENTIAL_ACCESS
+        .prefix(old_address)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for contract in grantees {
+        CREDENTIAL_ACCESS.remove(storage, (old_address, &contract));
+        CREDENTIAL_ACCESS.save(storage, (new_address, &contract), &true)?;
+    }
+
+    if let Some(guardians) = GUARDIANS.may_load(storage, old_address)? {
+        GUARDIANS.remove(storage, old_address);
+        GUARDIANS.save(storage, new_address, &guardians)?;
+    }
+
+    Ok(old_creds.len())
+}
+
+pub(crate) fn execute_contest_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+    recovery_id: String,
+) -> Result<Response, ContractError> {
+    let mut recovery = load_recovery(deps.as_ref(), &recovery_id)?;
+
+    // Only the old address can contest
+    if info.sender != recovery.old_address {
+        return Err(ContractError::Unauthorized {
+            reason: "Only the original address owner can contest recovery".to_string(),
+        });
+    }
+
+    if recovery.admin_approved
+        || !matches!(
+            recovery.status,
+            StoredRecoveryStatus::PendingVerification | StoredRecoveryStatus::TimelockWaiting
+        )
+    {
+        return Err(ContractError::Unauthorized {
+            reason: "Recovery is not in contestable state".to_string(),
+        });
+    }
+
+    recovery.status = StoredRecoveryStatus::Contested;
+    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "contest_recovery")
+        .add_attribute("recovery_id", recovery_id))
+}
+
+pub(crate) fn execute_cancel_recovery(
+    deps: DepsMut,
+    info: MessageInfo,
+    recovery_id: String,
+) -> Result<Response, ContractError> {
+    let mut recovery = load_recovery(deps.as_ref(), &recovery_id)?;
+
+    // Only the address that requested the recovery can withdraw it
+    if info.sender != recovery.new_address {
+        return Err(ContractError::Unauthorized {
+            reason: "Only the requesting address can cancel recovery".to_string(),
+        });
+    }
+    if !is_pending_recovery(&recovery.status) {
+        return Err(ContractError::Unauthorized {
+            reason: "Recovery is no longer pending".to_string(),
+        });
+    }
+
+    recovery.status = StoredRecoveryStatus::Cancelled;
+    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_recovery")
+        .add_attribute("recovery_id", recovery_id))
+}
+
+pub(crate) fn execute_resolve_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recovery_id: String,
+    approve: bool,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can resolve recovery disputes".to_string(),
+        });
+    }
+
+    let mut recovery = load_recovery(deps.as_ref(), &recovery_id)?;
+    if !is_pending_recovery(&recovery.status) {
+        return Err(ContractError::Unauthorized {
+            reason: "Recovery is no longer pending".to_string(),
+        });
+    }
+
+    // The admin's decision stands in for the approvals but not the timelock:
+    // subscribers move stake on `Migrated`, so it still waits for `ExecuteRecovery`
+    if approve {
+        let timelock = RECOVERY_TIMELOCK.load(deps.storage)?;
+        recovery.execute_after = env.block.height + timelock;
+        recovery.status = StoredRecoveryStatus::TimelockWaiting;
+        recovery.admin_approved = true;
+    } else {
+        recovery.status = StoredRecoveryStatus::Cancelled;
+    }
+    RECOVERIES.save(deps.storage, &recovery_id, &recovery)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "resolve_recovery")
+        .add_attribute("recovery_id", recovery_id)
+        .add_attribute("approved", approve.to_string())
+        .add_attribute("execute_after", recovery.execute_after.to_string()))
+}
+
+pub(crate) fn migrated_hook(recovery: &StoredRecovery) -> CredentialHookMsg {
+    CredentialHookMsg::Migrated {
+        old_holder: recovery.old_address.to_string(),
+        new_holder: recovery.new_address.to_string(),
+    }
+}
+
+pub(crate) fn load_recovery(
+    deps: Deps,
+    recovery_id: &str,
+) -> Result<StoredRecovery, ContractError> {
+    RECOVERIES
+        .may_load(deps.storage, recovery_id)?
+        .ok_or(ContractError::RecoveryNotFound {
+            recovery_id: recovery_id.to_string(),
+        })
+}
+
+pub(crate) fn is_pending_recovery(status: &StoredRecoveryStatus) -> bool {
+    matches!(
+        status,
+        StoredRecoveryStatus::PendingVerification
+            | StoredRecoveryStatus::TimelockWaiting
+            | StoredRecoveryStatus::Contested
+    )
+}
+
+pub(crate) fn to_recovery_response(r: StoredRecovery) -> RecoveryResponse {
+    RecoveryResponse {
+        recovery_id: r.recovery_id,
+        old_address: r.old_address.to_string(),
+        new_address: r.new_address.to_string(),
+        status: format!("{:?}", r.status),
+        requested_at: r.requested_at,
+        execute_after: r.execute_after,
+        approvals: r.approvals.iter().map(|a| a.to_string()).collect(),
+    }
+}
+
+pub(crate) fn query_recovery(deps: Deps, recovery_id: String) -> StdResult<RecoveryResponse> {
+    let r = RECOVERIES.load(deps.storage, &recovery_id)?;
+    Ok(to_recovery_response(r))
+}
+
+pub(crate) fn query_pending_recoveries(
+    deps: Deps,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RecoveriesResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let recoveries = ADDRESS_RECOVERIES
+        .prefix(&addr)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(|id| RECOVERIES.load(deps.storage, &id?))
+        .filter(|r| r.as_ref().map_or(true, |r| is_pending_recovery(&r.status)))
+        .take(limit)
+        .map(|r| r.map(to_recovery_response))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(RecoveriesResponse { recoveries })
+}
+
+pub(crate) fn query_guardians(deps: Deps, holder: String) -> StdResult<GuardiansResponse> {
+    let holder_addr = deps.api.addr_validate(&holder)?;
+    let set = GUARDIANS.may_load(deps.storage, &holder_addr)?;
+    Ok(GuardiansResponse {
+        holder,
+        guardians: set
+            .as_ref()
+            .map(|s| s.guardians.iter().map(|g| g.to_string()).collect())
+            .unwrap_or_default(),
+        threshold: set.map(|s| s.threshold).unwrap_or(0),
+    })
+}
@@ -0,0 +1,338 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Verification relay: relayers attest to off-chain verification outcomes
+// and k-of-n agreeing attestations issue the credential.
+// ─────────────────────────────────────────────────────────────────────────────
+
+use ark_bn254::Fr;
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+
+use citizen_common::credential::{Credential, CredentialType, Nullifier};
+use citizen_common::errors::ContractError;
+use citizen_common::hooks::CredentialHookMsg;
+use citizen_common::verification::{VerificationAttestation, VerificationStatus, VerificationType};
+
+use crate::contract::{
+    assign_status_index, count_active, counted_as_active, ensure_status_capacity,
+    issuer_credential_id, load_relayer_request, save_request, spend_nullifier,
+};
+use crate::groth16;
+use crate::hooks::hook_msgs;
+use crate::msg::*;
+use crate::schemas::load_schema;
+use crate::state::*;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute_submit_verification(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    request_id: String,
+    applicant: String,
+    credential_type: CredentialType,
+    commitment: String,
+    approved: bool,
+    nullifier: Nullifier,
+) -> Result<Response, ContractError> {
+    // Only authorized relayers can attest to verification results
+    let is_relayer = AUTHORIZED_RELAYERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or(false);
+    if !is_relayer {
+        return Err(ContractError::Unauthorized {
+            reason: "Only authorized relayers can submit verification results".to_string(),
+        });
+    }
+
+    let mut request = load_relayer_request(deps.as_ref(), &env, &request_id)?;
+    if request.applicant != applicant {
+        return Err(ContractError::Unauthorized {
+            reason: "Applicant does not match the verification request".to_string(),
+        });
+    }
+    if ATTESTATIONS.has(deps.storage, (&request_id, &info.sender)) {
+        return Err(ContractError::AlreadyAttested { request_id });
+    }
+
+    let nullifier = Nullifier {
+        domain: groth16::canonical_field::<Fr>(&nullifier.domain)?,
+        value: groth16::canonical_field::<Fr>(&nullifier.value)?,
+    };
+    let attestation = StoredAttestation {
+        attestation: VerificationAttestation {
+            request_id: request_id.clone(),
+            applicant: applicant.clone(),
+            result: if approved {
+                VerificationStatus::Approved
+            } else {
+                VerificationStatus::Rejected
+            },
+            credential_type: credential_type.to_string(),
+            commitment: commitment.clone(),
+            relayer: info.sender.to_string(),
+            attested_at: env.block.height,
+        },
+        nullifier: nullifier.clone(),
+    };
+    ATTESTATIONS.save(deps.storage, (&request_id, &info.sender), &attestation)?;
+
+    // The first attestation shows a relayer has picked the request up
+    if request.status == VerificationStatus::Pending {
+        request.status = VerificationStatus::InReview;
+        save_request(deps.storage, &request)?;
+    }
+
+    // Count relayers still authorized that attested to exactly this outcome
+    let threshold = ATTESTATION_THRESHOLD.load(deps.storage)?;
+    let matching = ATTESTATIONS
+        .prefix(&request_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|(relayer, other)| {
+            AUTHORIZED_RELAYERS.has(deps.storage, relayer) && same_outcome(other, &attestation)
+        })
+        .count() as u32;
+
+    if matching < threshold {
+        return Ok(Response::new()
+            .add_attribute("action", "attest_verification")
+            .add_attribute("request_id", request_id)
+            .add_attribute("relayer", info.sender.as_str())
+            .add_attribute("attestations", matching.to_string())
+            .add_attribute("threshold", threshold.to_string()));
+    }
+
+    request.completed_at = env.block.time.seconds();
+    if !approved {
+        request.status = VerificationStatus::Rejected;
+        save_request(deps.storage, &request)?;
+        return Ok(Response::new()
+            .add_attribute("action", "verification_rejected")
+            .add_attribute("request_id", request_id)
+            .add_attribute("applicant", applicant));
+    }
+
+    // Re-verification proves an existing identity for key recovery, so
+    // there is no new credential; recovery consumes the attested nullifier
+    if request.verification_type == VerificationType::RecoveryReverification {
+        request.status = VerificationStatus::Approved;
+        save_request(deps.storage, &request)?;
+        REVERIFICATIONS.save(deps.storage, &request_id, &nullifier)?;
+        return Ok(Response::new()
+            .add_attribute("action", "reverification_approved")
+            .add_attribute("request_id", request_id)
+            .add_attribute("applicant", applicant));
+    }
+
+    // Auto-issue credential upon successful verification
+    let holder_addr = deps.api.addr_validate(&applicant)?;
+    let cred_id = issue_verified_credential(
+        deps.storage,
+        &env,
+        &holder_addr,
+        credential_type.clone(),
+        commitment,
+        &info.sender,
+        &nullifier,
+    )?;
+    request.status = VerificationStatus::Approved;
+    save_request(deps.storage, &request)?;
+    let hooks = hook_msgs(
+        deps.storage,
+        &CredentialHookMsg::Issued {
+            credential_id: cred_id.clone(),
+            holder: applicant.clone(),
+            credential_type,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "verification_approved")
+        .add_attribute("request_id", request_id)
+        .add_attribute("credential_id", cred_id)
+        .add_attribute("applicant", applicant))
+}
+
+/// Issue a credential that was approved through the verification flow.
+pub(crate) fn issue_verified_credential(
+    storage: &mut dyn Storage,
+    env: &Env,
+    holder: &Addr,
+    credential_type: CredentialType,
+    commitment: String,
+    issuer: &Addr,
+    nullifier: &Nullifier,
+) -> Result<String, ContractError> {
+    if let CredentialType::Custom(name) = &credential_type {
+        load_schema(storage, name)?;
+    }
+
+    let cred_id =
+        issuer_credential_id(storage, env, holder.as_str(), &credential_type, &commitment)?;
+
+    let credential = Credential {
+        id: cred_id.clone(),
+        holder: holder.clone(),
+        credential_type,
+        commitment,
+        issuer: issuer.clone(),
+        issued_at: env.block.time.seconds(),
+        expires_at: 0, // no expiry for verified credentials
+        revoked: false,
+    };
+
+    ensure_status_capacity(storage)?;
+    spend_nullifier(storage, env, nullifier, holder, &cred_id)?;
+    CREDENTIALS.save(storage, &cred_id, &credential)?;
+    HOLDER_CREDENTIALS.save(storage, (holder, &cred_id), &true)?;
+    ISSUER_CREDENTIALS.save(storage, (issuer, &cred_id), &true)?;
+    assign_status_index(storage, &cred_id)?;
+    if counted_as_active(storage, &credential)? {
+        count_active(storage, &credential)?;
+    }
+    let count = CREDENTIAL_COUNT.load(storage)?;
+    CREDENTIAL_COUNT.save(storage, &(count + 1))?;
+
+    Ok(cred_id)
+}
+
+/// Two attestations agree if they would lead to the same credential (or rejection).
+pub(crate) fn same_outcome(a: &StoredAttestation, b: &StoredAttestation) -> bool {
+    a.attestation.applicant == b.attestation.applicant
+        && a.attestation.result == b.attestation.result
+        && a.attestation.credential_type == b.attestation.credential_type
+        && a.attestation.commitment == b.attestation.commitment
+        && a.nullifier == b.nullifier
+}
+
+pub(crate) fn execute_add_relayer(
+    deps: DepsMut,
+    info: MessageInfo,
+    relayer: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can add relayers".to_string(),
+        });
+    }
+
+    let relayer_addr = deps.api.addr_validate(&relayer)?;
+
+    // The set must be able to meet the threshold once this relayer is in
+    let mut relayers = AUTHORIZED_RELAYERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u32;
+    if !AUTHORIZED_RELAYERS.has(deps.storage, &relayer_addr) {
+        relayers += 1;
+    }
+    ensure_threshold_reachable(ATTESTATION_THRESHOLD.load(deps.storage)?, relayers)?;
+    AUTHORIZED_RELAYERS.save(deps.storage, &relayer_addr, &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_relayer")
+        .add_attribute("relayer", relayer))
+}
+
+pub(crate) fn execute_remove_relayer(
+    deps: DepsMut,
+    info: MessageInfo,
+    relayer: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can remove relayers".to_string(),
+        });
+    }
+
+    let relayer_addr = deps.api.addr_validate(&relayer)?;
+
+    // The remaining relayers must still be able to meet the threshold
+    let mut remaining = AUTHORIZED_RELAYERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u32;
+    if AUTHORIZED_RELAYERS.has(deps.storage, &relayer_addr) {
+        remaining -= 1;
+    }
+    ensure_threshold_reachable(ATTESTATION_THRESHOLD.load(deps.storage)?, remaining)?;
+
+    // Pending attestations from a removed relayer stop counting toward the threshold
+    AUTHORIZED_RELAYERS.remove(deps.storage, &relayer_addr);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_relayer")
+        .add_attribute("relayer", relayer))
+}
+
+pub(crate) fn execute_set_threshold(
+    deps: DepsMut,
+    info: MessageInfo,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can set the attestation threshold".to_string(),
+        });
+    }
+
+    let relayers = AUTHORIZED_RELAYERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u32;
+    ensure_threshold_reachable(threshold, relayers)?;
+
+    ATTESTATION_THRESHOLD.save(deps.storage, &threshold)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_attestation_threshold")
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+/// The attestation threshold must stay within what the relayer set can meet.
+pub(crate) fn ensure_threshold_reachable(
+    threshold: u32,
+    relayers: u32,
+) -> Result<(), ContractError> {
+    if threshold == 0 || threshold > relayers {
+        return Err(ContractError::InvalidThreshold {
+            reason: format!(
+                "Threshold {} must be between 1 and {} relayers",
+                threshold, relayers
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn query_relayers(deps: Deps) -> StdResult<RelayersResponse> {
+    let relayers = AUTHORIZED_RELAYERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|addr| addr.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let threshold = ATTESTATION_THRESHOLD.load(deps.storage)?;
+    Ok(RelayersResponse {
+        relayers,
+        threshold,
+    })
+}
+
+pub(crate) fn query_attestations(
+    deps: Deps,
+    request_id: String,
+) -> StdResult<AttestationsResponse> {
+    let attestations = ATTESTATIONS
+        .prefix(&request_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, stored)| stored.attestation))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AttestationsResponse {
+        request_id: request_id.clone(),
+        threshold: ATTESTATION_THRESHOLD.load(deps.storage)?,
+        attestations,
+        status: VERIFICATION_REQUESTS
+            .may_load(deps.storage, &request_id)?
+            .map(|r| r.status),
+    })
+}
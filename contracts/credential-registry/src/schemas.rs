@@ -0,0 +1,163 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Credential schemas: admin-registered custom types, their issuers and
+// the public signal layout their proofs follow.
+// ─────────────────────────────────────────────────────────────────────────────
+
+use cosmwasm_std::{
+    to_json_string, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage,
+};
+use cw_storage_plus::Bound;
+
+use citizen_common::credential::CredentialType;
+use citizen_common::errors::ContractError;
+
+use crate::msg::*;
+use crate::state::*;
+
+pub(crate) fn execute_register_schema(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    schema: CredentialSchema,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can register credential schemas".to_string(),
+        });
+    }
+    if CREDENTIAL_SCHEMAS.has(deps.storage, &name) {
+        return Err(ContractError::CredentialSchemaAlreadyExists { name });
+    }
+
+    let stored = stored_schema(deps.as_ref(), &name, schema, env.block.time.seconds())?;
+    CREDENTIAL_SCHEMAS.save(deps.storage, &name, &stored)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_credential_schema")
+        .add_attribute("name", name))
+}
+
+/// Replace a registered schema. The event carries the schema it replaced, so
+/// changes to who may issue a type stay visible.
+pub(crate) fn execute_update_schema(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    schema: CredentialSchema,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can update credential schemas".to_string(),
+        });
+    }
+    let previous = load_schema(deps.storage, &name)?;
+
+    let stored = stored_schema(deps.as_ref(), &name, schema, previous.registered_at)?;
+    CREDENTIAL_SCHEMAS.save(deps.storage, &name, &stored)?;
+    let previous = schema_response(name.clone(), previous).schema;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_credential_schema")
+        .add_attribute("name", name)
+        .add_attribute("previous_schema", to_json_string(&previous)?))
+}
+
+/// Validate a schema for `Custom(name)` into its stored form.
+pub(crate) fn stored_schema(
+    deps: Deps,
+    name: &str,
+    schema: CredentialSchema,
+    registered_at: u64,
+) -> Result<StoredCredentialSchema, ContractError> {
+    if name.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Schema name must not be empty",
+        )));
+    }
+
+    // The pinned key must already exist for this custom type
+    if let Some(version) = &schema.verification_key {
+        let type_str = CredentialType::Custom(name.to_string()).to_string();
+        if !VERIFICATION_KEYS.has(deps.storage, (&type_str, version)) {
+            return Err(ContractError::VerificationKeyNotFound {
+                reference: format!("{}/{}", type_str, version),
+            });
+        }
+    }
+
+    let allowed_issuers = schema
+        .allowed_issuers
+        .iter()
+        .map(|i| deps.api.addr_validate(i))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(StoredCredentialSchema {
+        description: schema.description,
+        attributes: schema.attributes,
+        allowed_issuers,
+        default_validity_secs: schema.default_validity_secs,
+        verification_key: schema.verification_key,
+        registered_at,
+        private: schema.private,
+        signal_layout: schema.signal_layout,
+    })
+}
+
+pub(crate) fn load_schema(
+    storage: &dyn Storage,
+    name: &str,
+) -> Result<StoredCredentialSchema, ContractError> {
+    CREDENTIAL_SCHEMAS
+        .may_load(storage, name)?
+        .ok_or(ContractError::CredentialSchemaNotFound {
+            name: name.to_string(),
+        })
+}
+
+pub(crate) fn query_schema(deps: Deps, name: String) -> StdResult<CredentialSchemaResponse> {
+    let stored = CREDENTIAL_SCHEMAS.load(deps.storage, &name)?;
+    Ok(schema_response(name, stored))
+}
+
+pub(crate) fn query_schemas(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CredentialSchemasResponse> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let schemas = CREDENTIAL_SCHEMAS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(name, stored)| schema_response(name, stored)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(CredentialSchemasResponse { schemas })
+}
+
+pub(crate) fn schema_response(
+    name: String,
+    stored: StoredCredentialSchema,
+) -> CredentialSchemaResponse {
+    CredentialSchemaResponse {
+        name,
+        schema: CredentialSchema {
+            description: stored.description,
+            attributes: stored.attributes,
+            allowed_issuers: stored
+                .allowed_issuers
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+            default_validity_secs: stored.default_validity_secs,
+            verification_key: stored.verification_key,
+            private: stored.private,
+            signal_layout: stored.signal_layout,
+        },
+        registered_at: stored.registered_at,
+    }
+}
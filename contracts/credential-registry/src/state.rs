@@ -1,41 +1,299 @@
-use citizen_common::credential::Credential;
+use citizen_common::credential::{Credential, Groth16VerificationKey, Nullifier};
+use citizen_common::verification::{
+    VerificationAttestation, VerificationConfig, VerificationRequest,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary};
 use cw_storage_plus::{Item, Map};
 
+use crate::msg::{IssuerScope, IssuerStatus, SignalLayout, TypeImplication, VouchingConfig};
+
 /// Contract admin address
 pub const ADMIN: Item<Addr> = Item::new("admin");
 
 /// Set of trusted issuers (address → true)
 pub const ISSUERS: Map<&Addr, bool> = Map::new("issuers");
 
+/// Contracts notified of credential lifecycle events
+pub const HOOKS: Map<&Addr, bool> = Map::new("hooks");
+
+/// Gas each hook sub-message may use, so a costly subscriber cannot exhaust the action
+pub const HOOK_GAS_LIMIT: Item<u64> = Item::new("hook_gas_limit");
+
+/// Anchored issuance roots (hex) awaiting claims
+pub const ISSUANCE_ROOTS: Map<&str, StoredIssuanceRoot> = Map::new("issuance_roots");
+
+/// Credential type implications, by (source type or `*` for any, implied type)
+pub const TYPE_IMPLICATIONS: Map<(&str, &str), TypeImplication> = Map::new("type_implications");
+
+/// Restrictions on scoped issuers; issuers without an entry are unrestricted
+pub const ISSUER_SCOPES: Map<&Addr, IssuerScope> = Map::new("issuer_scopes");
+
+/// Schemas of registered custom credential types, by custom name
+pub const CREDENTIAL_SCHEMAS: Map<&str, StoredCredentialSchema> = Map::new("cred_schemas");
+
+/// Issuer key status; issuers without an entry are active
+pub const ISSUER_STATUS: Map<&Addr, IssuerStatus> = Map::new("issuer_status");
+
+/// Issuance counted against each rate-limited issuer's current period
+pub const ISSUER_USAGE: Map<&Addr, IssuanceWindow> = Map::new("issuer_usage");
+
+/// Groth16 verification keys by (credential type, version).
+/// The version is what proofs name in `ZkProof.vk_reference`.
+pub const VERIFICATION_KEYS: Map<(&str, &str), StoredVerificationKey> = Map::new("vks");
+
 /// Credentials indexed by their unique ID
 pub const CREDENTIALS: Map<&str, Credential> = Map::new("credentials");
 
+/// Holders per type with a credential that is unrevoked, unsuperseded and not
+/// yet swept as expired, keyed by the type's `Display` form
+pub const ACTIVE_CREDENTIALS: Map<&str, u64> = Map::new("active_creds");
+
+/// How many such credentials each holder has per type; a holder counts once
+/// in `ACTIVE_CREDENTIALS` while this is non-zero
+pub const ACTIVE_HOLDER_CREDENTIALS: Map<(&str, &Addr), u32> = Map::new("active_holder_creds");
+
+/// Credential types a holder has chosen to keep out of list queries
+pub const HOLDER_PRIVATE_TYPES: Map<(&Addr, &str), bool> = Map::new("holder_private_types");
+
+/// Contracts a holder lets see private credential ids through `HasValidCredential`
+pub const CREDENTIAL_ACCESS: Map<(&Addr, &Addr), bool> = Map::new("cred_access");
+
 /// Secondary index: holder address → list of credential IDs
 pub const HOLDER_CREDENTIALS: Map<(&Addr, &str), bool> = Map::new("holder_creds");
 
+/// Expiry index: (expires_at, credential ID) for unrevoked credentials that expire.
+/// Superseded credentials drop out when renewed.
+pub const CREDENTIAL_EXPIRY: Map<(u64, &str), bool> = Map::new("cred_expiry");
+
+/// Last expiry index entry processed by `SweepExpired`
+pub const EXPIRY_SWEEP_CURSOR: Item<(u64, String)> = Item::new("expiry_sweep_cursor");
+
+/// Credentials `SweepExpired` has recorded as expired
+pub const SWEPT_CREDENTIALS: Map<&str, bool> = Map::new("swept_creds");
+
+/// Renewal links of credentials that were renewed or are renewals
+pub const CREDENTIAL_LINEAGE: Map<&str, StoredLineage> = Map::new("cred_lineage");
+
+/// Attribute Merkle root of each credential that commits to attributes
+pub const CREDENTIAL_ATTRIBUTES: Map<&str, String> = Map::new("cred_attributes");
+
+/// Position of each credential's bit in the revocation status list
+pub const CREDENTIAL_STATUS_INDEX: Map<&str, u64> = Map::new("cred_status_index");
+
+/// Next unassigned status list position
+pub const NEXT_STATUS_INDEX: Item<u64> = Item::new("next_status_index");
+
+/// Indexes `migrate` left for `BackfillIndexes` to build over existing entries
+pub const PENDING_BACKFILL: Item<Backfill> = Item::new("pending_backfill");
+
+/// Status list bitstring in 256-bit chunks, by chunk index
+pub const STATUS_CHUNKS: Map<u64, Binary> = Map::new("status_chunks");
+
+/// Non-empty status list Merkle nodes by (level, index); level 0 holds leaves
+pub const STATUS_NODES: Map<(u8, u64), Binary> = Map::new("status_nodes");
+
+/// Current revocation epoch, bumped on every root change
+pub const REVOCATION_EPOCH: Item<u64> = Item::new("revocation_epoch");
+
+/// Status list root published at each epoch
+pub const REVOCATION_ROOTS: Map<u64, RevocationRootRecord> = Map::new("revocation_roots");
+
+/// Secondary index: issuer address → credential IDs it issued
+pub const ISSUER_CREDENTIALS: Map<(&Addr, &str), bool> = Map::new("issuer_creds");
+
+/// Spent identity nullifiers by (domain, nullifier), both canonical decimals
+pub const NULLIFIERS: Map<(&str, &str), StoredNullifier> = Map::new("nullifiers");
+
+/// Reverse index: credential ID → the nullifier spent to issue it
+pub const CREDENTIAL_NULLIFIERS: Map<&str, Nullifier> = Map::new("cred_nullifiers");
+
 /// Running counter for total credentials issued
 pub const CREDENTIAL_COUNT: Item<u64> = Item::new("cred_count");
 
+/// Social vouching limits and penalties
+pub const VOUCHING_CONFIG: Item<VouchingConfig> = Item::new("vouching_config");
+
+/// What a socially vouched applicant will be issued, by request_id
+pub const VOUCHING_CLAIMS: Map<&str, VouchingClaim> = Map::new("vouching_claims");
+
+/// Vouches by (request_id, voucher) → time of the vouch
+pub const VOUCHES: Map<(&str, &Addr), u64> = Map::new("vouches");
+
+/// Per-voucher rate limit and penalty state
+pub const VOUCHERS: Map<&Addr, VoucherRecord> = Map::new("vouchers");
+
+/// Citizens who vouched for a credential, so they can be penalized if it proves fraudulent
+pub const CREDENTIAL_VOUCHERS: Map<&str, Vec<Addr>> = Map::new("cred_vouchers");
+
 /// Authorized verification relayers (off-chain identity service → on-chain bridge)
 pub const AUTHORIZED_RELAYERS: Map<&Addr, bool> = Map::new("relayers");
 
+/// Number of distinct relayers that must attest to the same result (k of n)
+pub const ATTESTATION_THRESHOLD: Item<u32> = Item::new("attest_threshold");
+
+/// Partial attestations by (request_id, relayer)
+pub const ATTESTATIONS: Map<(&str, &Addr), StoredAttestation> = Map::new("attestations");
+
+/// Verification service settings (relayers are kept in `AUTHORIZED_RELAYERS`)
+pub const VERIFICATION_CONFIG: Item<VerificationConfig> = Item::new("verification_config");
+
+/// On-chain verification requests indexed by request_id
+pub const VERIFICATION_REQUESTS: Map<&str, VerificationRequest> = Map::new("verif_requests");
+
+/// Secondary index: applicant address → request IDs
+pub const APPLICANT_REQUESTS: Map<(&Addr, &str), bool> = Map::new("applicant_requests");
+
+/// Secondary index: (recorded status, request_id)
+pub const REQUESTS_BY_STATUS: Map<(&str, &str), bool> = Map::new("requests_by_status");
+
 /// Recovery timelock in blocks (e.g. 10080 ≈ 7 days at 6s blocks)
 pub const RECOVERY_TIMELOCK: Item<u64> = Item::new("recovery_tl");
 
 /// Key recovery requests indexed by recovery_id
 pub const RECOVERIES: Map<&str, StoredRecovery> = Map::new("recoveries");
 
+/// Secondary index: old and new address of a recovery → recovery IDs
+pub const ADDRESS_RECOVERIES: Map<(&Addr, &str), bool> = Map::new("addr_recoveries");
+
+/// Guardians a holder trusts to approve recovery of their address
+pub const GUARDIANS: Map<&Addr, GuardianSet> = Map::new("guardians");
+
+/// Approved recovery re-verifications not yet used, by request_id → attested nullifier
+pub const REVERIFICATIONS: Map<&str, Nullifier> = Map::new("reverifications");
+
+#[cw_serde]
+pub struct StoredNullifier {
+    /// Current holder of the identity; follows key recovery
+    pub holder: Addr,
+    pub credential_id: String,
+    pub spent_at: u64,
+}
+
+#[cw_serde]
+pub struct VouchingClaim {
+    pub commitment: String,
+    /// Canonical nullifier spent when the credential is issued
+    pub nullifier: Nullifier,
+}
+
+/// A credential's place in its renewal chain. Credentials without an entry
+/// are the sole member of their own lineage.
+#[cw_serde]
+pub struct StoredLineage {
+    pub lineage_id: String,
+    pub previous_id: Option<String>,
+    pub superseded_by: Option<String>,
+}
+
+#[cw_serde]
+pub struct StoredCredentialSchema {
+    pub description: String,
+    pub attributes: Vec<String>,
+    pub allowed_issuers: Vec<Addr>,
+    pub default_validity_secs: u64,
+    pub verification_key: Option<String>,
+    pub registered_at: u64,
+    #[serde(default)]
+    pub private: bool,
+    #[serde(default)]
+    pub signal_layout: SignalLayout,
+}
+
+#[cw_serde]
+pub struct StoredIssuanceRoot {
+    pub issuer: Addr,
+    pub anchored_at: u64,
+    /// Credentials claimed under the root so far
+    pub claimed: u64,
+}
+
+#[cw_serde]
+pub struct RevocationRootRecord {
+    /// Hex-encoded status list root
+    pub root: String,
+    pub updated_at: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct IssuanceWindow {
+    pub period_start: u64,
+    pub issued_in_period: u32,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct VoucherRecord {
+    pub period_start: u64,
+    pub vouches_in_period: u32,
+    pub cooldown_until: u64,
+    /// Number of vouchees later revoked for fraud
+    pub fraud_count: u32,
+}
+
+#[cw_serde]
+pub struct StoredAttestation {
+    pub attestation: VerificationAttestation,
+    /// Identity nullifier the relayer vouched for, in canonical form
+    pub nullifier: Nullifier,
+}
+
+#[cw_serde]
+pub struct StoredVerificationKey {
+    pub key: Groth16VerificationKey,
+    /// Deprecated keys stay queryable but no longer verify proofs
+    pub deprecated: bool,
+    pub registered_at: u64,
+}
+
+#[cw_serde]
+pub struct Backfill {
+    pub steps: Vec<BackfillStep>,
+    /// Last credential or request id the current pass processed
+    pub cursor: Option<String>,
+}
+
+/// One index to build for entries that predate it. Credential steps share a
+/// single pass over `CREDENTIALS`; requests are indexed after it.
+#[cw_serde]
+pub enum BackfillStep {
+    /// Status list positions, with the revoked credentials' bits set
+    StatusList,
+    /// Expiry index of unrevoked, unsuperseded credentials
+    Expiry,
+    /// Swept flags of credentials an earlier sweep passed
+    SweptFlags,
+    IssuerIndex,
+    ActiveCounts,
+    RequestStatus,
+}
+
+#[cw_serde]
+pub struct GuardianSet {
+    pub guardians: Vec<Addr>,
+    /// Guardian approvals needed to start the recovery timelock
+    pub threshold: u32,
+}
+
 #[cw_serde]
 pub struct StoredRecovery {
     pub recovery_id: String,
     pub old_address: Addr,
     pub new_address: Addr,
-    pub reverification_id: String,
+    /// Approved `RecoveryReverification` request, if recovering by re-verification
+    pub reverification_id: Option<String>,
     pub status: StoredRecoveryStatus,
     pub requested_at: u64,
+    /// Block height after which the recovery can execute (0 until approved)
     pub execute_after: u64,
+    /// Guardians that approved so far
+    #[serde(default)]
+    pub approvals: Vec<Addr>,
+    /// Set once the admin settles the recovery in its favour; it can no
+    /// longer be contested, only rejected by the admin before it executes
+    #[serde(default)]
+    pub admin_approved: bool,
 }
 
 #[cw_serde]
@@ -0,0 +1,144 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Revocation status list committed to by a fixed-depth Merkle root
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Every credential gets a `status_index` into a bitstring; revoking it sets
+// the bit. The bitstring is split into 256-bit chunks which form the leaves of
+// a sparse Merkle tree, so a holder can prove in zero knowledge that their bit
+// is clear under the published root without revealing which credential is
+// theirs.
+//
+// Leaves are H(0x00 || chunk), hashed up with `merkle::node_hash`.
+// Bit `i` of a chunk is `(chunk[i / 8] >> (i % 8)) & 1`.
+
+use cosmwasm_std::{Binary, StdError, StdResult, Storage};
+
+use crate::merkle::{self, node_hash, Hash};
+use crate::state::{STATUS_CHUNKS, STATUS_NODES};
+
+/// Tree depth above the chunk leaves
+pub const DEPTH: u8 = 20;
+/// Status bits per leaf
+pub const CHUNK_BITS: u64 = 256;
+/// Number of credentials the list can track
+pub const CAPACITY: u64 = CHUNK_BITS << DEPTH;
+
+pub fn leaf_hash(chunk: &Hash) -> Hash {
+    merkle::hash(merkle::LEAF_PREFIX, &[chunk])
+}
+
+/// Roots of all-zero subtrees, indexed by level (0 = leaf).
+fn empty_hashes() -> Vec<Hash> {
+    let mut empty = vec![leaf_hash(&[0u8; 32])];
+    for level in 0..DEPTH as usize {
+        empty.push(node_hash(&empty[level], &empty[level]));
+    }
+    empty
+}
+
+fn load_hash(storage: &dyn Storage, level: u8, index: u64) -> StdResult<Option<Hash>> {
+    STATUS_NODES
+        .may_load(storage, (level, index))?
+        .map(|b| to_hash(&b))
+        .transpose()
+}
+
+fn to_hash(bytes: &Binary) -> StdResult<Hash> {
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| StdError::generic_err("Corrupt status list node"))
+}
+
+fn load_chunk(storage: &dyn Storage, chunk_index: u64) -> StdResult<Hash> {
+    STATUS_CHUNKS
+        .may_load(storage, chunk_index)?
+        .map(|b| to_hash(&b))
+        .transpose()
+        .map(|c| c.unwrap_or([0u8; 32]))
+}
+
+/// Current root of the status list.
+pub fn root(storage: &dyn Storage) -> StdResult<Hash> {
+    Ok(load_hash(storage, DEPTH, 0)?.unwrap_or_else(|| empty_hashes()[DEPTH as usize]))
+}
+
+/// Set the status bit at `index` and return the new root.
+pub fn set_revoked(storage: &mut dyn Storage, index: u64) -> StdResult<Hash> {
+    if index >= CAPACITY {
+        return Err(StdError::generic_err("Status index out of range"));
+    }
+    let empty = empty_hashes();
+    let mut position = index / CHUNK_BITS;
+    let bit = index % CHUNK_BITS;
+
+    let mut chunk = load_chunk(storage, position)?;
+    chunk[(bit / 8) as usize] |= 1 << (bit % 8);
+    STATUS_CHUNKS.save(storage, position, &Binary::from(chunk.to_vec()))?;
+
+    let mut node = leaf_hash(&chunk);
+    for level in 0..DEPTH {
+        STATUS_NODES.save(storage, (level, position), &Binary::from(node.to_vec()))?;
+        let sibling = load_hash(storage, level, position ^ 1)?.unwrap_or(empty[level as usize]);
+        node = if position & 1 == 0 {
+            node_hash(&node, &sibling)
+        } else {
+            node_hash(&sibling, &node)
+        };
+        position /= 2;
+    }
+    STATUS_NODES.save(storage, (DEPTH, 0), &Binary::from(node.to_vec()))?;
+    Ok(node)
+}
+
+/// The chunk holding `index` and its sibling path from leaf to root.
+pub fn witness(storage: &dyn Storage, index: u64) -> StdResult<(Hash, Vec<Hash>)> {
+    if index >= CAPACITY {
+        return Err(StdError::generic_err("Status index out of range"));
+    }
+    let empty = empty_hashes();
+    let mut position = index / CHUNK_BITS;
+    let chunk = load_chunk(storage, position)?;
+
+    let mut siblings = Vec::with_capacity(DEPTH as usize);
+    for level in 0..DEPTH {
+        siblings.push(load_hash(storage, level, position ^ 1)?.unwrap_or(empty[level as usize]));
+        position /= 2;
+    }
+    Ok((chunk, siblings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn test_witness_recomputes_root() {
+        let mut storage = MockStorage::new();
+        let empty_root = root(&storage).unwrap();
+
+        set_revoked(&mut storage, 3).unwrap();
+        let last = set_revoked(&mut storage, 300_000).unwrap();
+        assert_ne!(last, empty_root);
+        assert_eq!(last, root(&storage).unwrap());
+
+        for index in [3u64, 4, 300_000, CAPACITY - 1] {
+            let (chunk, siblings) = witness(&storage, index).unwrap();
+            assert_eq!(siblings.len(), DEPTH as usize);
+            let leaf = leaf_hash(&chunk);
+            assert_eq!(merkle::fold(leaf, index / CHUNK_BITS, &siblings), last);
+            let bit = index % CHUNK_BITS;
+            let set = (chunk[(bit / 8) as usize] >> (bit % 8)) & 1 == 1;
+            assert_eq!(set, index == 3 || index == 300_000);
+        }
+    }
+
+    #[test]
+    fn test_root_fits_bn254_scalar() {
+        let mut storage = MockStorage::new();
+        let root = set_revoked(&mut storage, 0).unwrap();
+        assert_eq!(root[0], 0);
+        assert!(set_revoked(&mut storage, CAPACITY).is_err());
+    }
+}
@@ -4,7 +4,10 @@ use cosmwasm_std::{
 };
 use cw2::{get_contract_version, set_contract_version};
 
+use citizen_common::credential::CredentialType;
 use citizen_common::errors::ContractError;
+use citizen_common::hooks::CredentialHookMsg;
+use citizen_common::registry::CredentialRegistry;
 use citizen_common::treasury::FundCategory;
 
 use crate::msg::*;
@@ -103,6 +106,10 @@ pub fn execute(
         ExecuteMsg::CloseResearchCycle { cycle_id } => {
             execute_close_research_cycle(deps, info, cycle_id)
         }
+        ExecuteMsg::SetCredentialRegistry { registry } => {
+            execute_set_credential_registry(deps, info, registry)
+        }
+        ExecuteMsg::CredentialHook(hook) => execute_credential_hook(deps, env, info, hook),
     }
 }
 
@@ -367,6 +374,91 @@ fn execute_cancel(
         .add_attribute("grant_id", grant_id.to_string()))
 }
 
+// ── Credential Hooks ────────────────────────────────────────────────
+
+fn execute_set_credential_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    registry: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    let gov = GOVERNANCE.load(deps.storage)?;
+    if info.sender != admin && info.sender != gov {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin or governance can set the credential registry".to_string(),
+        });
+    }
+    let addr = deps.api.addr_validate(&registry)?;
+    CREDENTIAL_REGISTRY.save(deps.storage, &addr)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_credential_registry")
+        .add_attribute("registry", registry))
+}
+
+fn execute_credential_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hook: CredentialHookMsg,
+) -> Result<Response, ContractError> {
+    let registry = match CREDENTIAL_REGISTRY.may_load(deps.storage)? {
+        Some(registry) if registry == info.sender => CredentialRegistry(registry),
+        _ => {
+            return Err(ContractError::Unauthorized {
+                reason: "Only the credential registry can send hooks".to_string(),
+            })
+        }
+    };
+
+    let (CredentialHookMsg::Revoked {
+        holder,
+        credential_type: CredentialType::Citizenship,
+        ..
+    }
+    | CredentialHookMsg::Expired {
+        holder,
+        credential_type: CredentialType::Citizenship,
+        ..
+    }) = hook
+    else {
+        return Ok(Response::new().add_attribute("action", "credential_hook_ignored"));
+    };
+
+    // The holder may still be a citizen through another credential, e.g. a renewal
+    let holder_addr = deps.api.addr_validate(&holder)?;
+    let still_citizen = registry
+        .has_valid_credential(
+            &deps.querier,
+            &holder_addr,
+            CredentialType::Citizenship,
+            Some(&env.contract.address),
+        )?
+        .has_credential;
+    if still_citizen {
+        return Ok(Response::new().add_attribute("action", "credential_hook_ignored"));
+    }
+
+    // Applications still awaiting a decision came from someone who is no longer a citizen
+    let grant_ids: Vec<u64> = APPLICANT_GRANTS
+        .prefix(&holder_addr)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut cancelled = 0usize;
+    for grant_id in grant_ids {
+        let mut grant = GRANTS.load(deps.storage, grant_id)?;
+        if matches!(grant.status, StoredGrantStatus::Pending) {
+            grant.status = StoredGrantStatus::Cancelled;
+            GRANTS.save(deps.storage, grant_id, &grant)?;
+            cancelled += 1;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "citizenship_lost")
+        .add_attribute("holder", holder)
+        .add_attribute("grants_cancelled", cancelled.to_string()))
+}
+
 // ── Reviewer Management ─────────────────────────────────────────────
 
 fn execute_add_reviewer(
@@ -623,6 +715,9 @@ fn query_config(deps: Deps) -> StdResult<GrantConfigResponse> {
         admin: ADMIN.load(deps.storage)?.to_string(),
         governance_contract: GOVERNANCE.load(deps.storage)?.to_string(),
         treasury_contract: TREASURY.load(deps.storage)?.to_string(),
+        credential_registry: CREDENTIAL_REGISTRY
+            .may_load(deps.storage)?
+            .map(|a| a.to_string()),
         total_grants: GRANT_COUNT.load(deps.storage)?,
         total_disbursed: TOTAL_DISBURSED.load(deps.storage)?,
     })
@@ -694,7 +789,9 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 #[cfg(test)]
 mod tests {
     use super::*;
+    use citizen_common::registry::MockRegistry;
     use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use cosmwasm_std::Addr;
 
     fn setup(deps: DepsMut) {
         let api = MockApi::default();
@@ -818,4 +915,90 @@ mod tests {
         assert_eq!(grant.status, "Completed"); // Only one milestone
         assert_eq!(grant.disbursed, Uint128::new(1000));
     }
+
+    #[test]
+    fn test_citizenship_lost_cancels_pending_grants() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        let registry_addr = deps.api.addr_make("cred_registry");
+        let renewed_addr = deps.api.addr_make("renewed");
+        let lapsed_addr = deps.api.addr_make("lapsed");
+        let registry = MockRegistry::new(registry_addr.clone())
+            .with_credential(renewed_addr.clone(), CredentialType::Citizenship);
+        deps.querier
+            .update_wasm(move |query| registry.handle(query));
+
+        let admin_addr = deps.api.addr_make("admin");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin_addr, &[]),
+            ExecuteMsg::SetCredentialRegistry {
+                registry: registry_addr.to_string(),
+            },
+        )
+        .unwrap();
+
+        for applicant in [&renewed_addr, &lapsed_addr] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(applicant, &[]),
+                ExecuteMsg::Apply {
+                    title: "Community Garden".to_string(),
+                    description: "Seeds and tools".to_string(),
+                    category: FundCategory::Healthcare,
+                    milestones: vec![MilestoneInput {
+                        description: "Planting".to_string(),
+                        amount: Uint128::new(1000),
+                    }],
+                },
+            )
+            .unwrap();
+        }
+
+        let hook = |holder: &Addr| CredentialHookMsg::Expired {
+            credential_id: "cred-1".to_string(),
+            holder: holder.to_string(),
+            credential_type: CredentialType::Citizenship,
+        };
+
+        // Only the registry may send hooks
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&lapsed_addr, &[]),
+            ExecuteMsg::CredentialHook(hook(&lapsed_addr)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        // A holder who still has a citizenship credential keeps their application
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&registry_addr, &[]),
+            ExecuteMsg::CredentialHook(CredentialHookMsg::Revoked {
+                credential_id: "cred-0".to_string(),
+                holder: renewed_addr.to_string(),
+                credential_type: CredentialType::Citizenship,
+            }),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0].value, "credential_hook_ignored");
+        assert_eq!(query_grant(deps.as_ref(), 1).unwrap().status, "Pending");
+
+        // An expired citizenship with nothing to replace it cancels the application
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&registry_addr, &[]),
+            ExecuteMsg::CredentialHook(hook(&lapsed_addr)),
+        )
+        .unwrap();
+        assert_eq!(res.attributes[0].value, "citizenship_lost");
+        assert_eq!(query_grant(deps.as_ref(), 2).unwrap().status, "Cancelled");
+        assert_eq!(query_grant(deps.as_ref(), 1).unwrap().status, "Pending");
+    }
 }
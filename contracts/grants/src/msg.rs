@@ -1,3 +1,4 @@
+use citizen_common::hooks::CredentialHookMsg;
 use citizen_common::treasury::FundCategory;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
@@ -60,6 +61,11 @@ pub enum ExecuteMsg {
     },
     /// Close a research cycle and finalize allocations
     CloseResearchCycle { cycle_id: u64 },
+    /// Accept credential hooks from this registry (admin/governance)
+    SetCredentialRegistry { registry: String },
+    /// Credential lifecycle notification from the registry. A revoked
+    /// citizenship cancels the holder's pending applications.
+    CredentialHook(CredentialHookMsg),
 }
 
 /// Message for contract migration
@@ -133,6 +139,7 @@ pub struct GrantConfigResponse {
     pub admin: String,
     pub governance_contract: String,
     pub treasury_contract: String,
+    pub credential_registry: Option<String>,
     pub total_grants: u64,
     pub total_disbursed: Uint128,
 }
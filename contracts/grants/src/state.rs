@@ -6,6 +6,8 @@ use cw_storage_plus::{Item, Map};
 pub const ADMIN: Item<Addr> = Item::new("admin");
 pub const GOVERNANCE: Item<Addr> = Item::new("governance");
 pub const TREASURY: Item<Addr> = Item::new("treasury");
+/// Credential registry whose lifecycle hooks this contract accepts
+pub const CREDENTIAL_REGISTRY: Item<Addr> = Item::new("cred_registry");
 pub const GRANT_COUNT: Item<u64> = Item::new("grant_count");
 pub const TOTAL_DISBURSED: Item<Uint128> = Item::new("total_disbursed");
 
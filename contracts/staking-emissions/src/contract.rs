@@ -5,6 +5,7 @@ use cosmwasm_std::{
 use cw2::{get_contract_version, set_contract_version};
 
 use citizen_common::errors::ContractError;
+use citizen_common::hooks::CredentialHookMsg;
 
 use crate::msg::*;
 use crate::state::*;
@@ -73,6 +74,10 @@ pub fn execute(
         ExecuteMsg::UpdateDifficulty { config } => {
             execute_update_difficulty(deps, info, config)
         }
+        ExecuteMsg::SetCredentialRegistry { registry } => {
+            execute_set_credential_registry(deps, info, registry)
+        }
+        ExecuteMsg::CredentialHook(hook) => execute_credential_hook(deps, env, info, hook),
     }
 }
 
@@ -511,6 +516,102 @@ fn execute_update_difficulty(
         .add_attribute("enabled", config.enabled.to_string()))
 }
 
+// ── Credential Hooks ────────────────────────────────────────────────
+
+fn execute_set_credential_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    registry: String,
+) -> Result<Response, ContractError> {
+    let admin = ADMIN.load(deps.storage)?;
+    if info.sender != admin {
+        return Err(ContractError::Unauthorized {
+            reason: "Only admin can set the credential registry".to_string(),
+        });
+    }
+    let addr = deps.api.addr_validate(&registry)?;
+    CREDENTIAL_REGISTRY.save(deps.storage, &addr)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_credential_registry")
+        .add_attribute("registry", registry))
+}
+
+/// Key recovery moves a citizen to a new address; their stake and unclaimed
+/// rewards follow, as the old key may be lost. Other events don't affect stakes.
+fn execute_credential_hook(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hook: CredentialHookMsg,
+) -> Result<Response, ContractError> {
+    let registry = CREDENTIAL_REGISTRY.may_load(deps.storage)?;
+    if registry.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {
+            reason: "Only the credential registry can send hooks".to_string(),
+        });
+    }
+
+    let CredentialHookMsg::Migrated {
+        old_holder,
+        new_holder,
+    } = hook
+    else {
+        return Ok(Response::new().add_attribute("action", "credential_hook_ignored"));
+    };
+    let old_addr = deps.api.addr_validate(&old_holder)?;
+    let new_addr = deps.api.addr_validate(&new_holder)?;
+    let Some(moved) = STAKERS.may_load(deps.storage, &old_addr)? else {
+        return Ok(Response::new().add_attribute("action", "credential_hook_ignored"));
+    };
+
+    update_global_index(&mut deps, &env)?;
+    let global_idx = GLOBAL_REWARD_INDEX.load(deps.storage)?;
+
+    // Settle both records at the current index before merging them
+    let mut staker = StakerInfo {
+        staked: moved.staked,
+        reward_debt: Uint128::zero(),
+        pending_rewards: moved.pending_rewards + accrued_rewards(&moved, global_idx)?,
+        last_claim_height: moved.last_claim_height,
+    };
+    if let Some(existing) = STAKERS.may_load(deps.storage, &new_addr)? {
+        if !existing.staked.is_zero() && !moved.staked.is_zero() {
+            let count = TOTAL_STAKERS.load(deps.storage)?;
+            TOTAL_STAKERS.save(deps.storage, &count.saturating_sub(1))?;
+        }
+        staker.staked += existing.staked;
+        staker.pending_rewards +=
+            existing.pending_rewards + accrued_rewards(&existing, global_idx)?;
+        staker.last_claim_height = existing.last_claim_height;
+    }
+    staker.reward_debt = staker
+        .staked
+        .checked_mul(global_idx)
+        .map_err(|_| ContractError::Overflow)?
+        .checked_div(Uint128::from(REWARD_SCALE))
+        .map_err(|_| ContractError::Overflow)?;
+
+    STAKERS.remove(deps.storage, &old_addr);
+    STAKERS.save(deps.storage, &new_addr, &staker)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "credential_migrated")
+        .add_attribute("old_holder", old_holder)
+        .add_attribute("new_holder", new_holder)
+        .add_attribute("staked", moved.staked.to_string()))
+}
+
+/// Rewards a staker has earned since their reward debt was last set.
+fn accrued_rewards(staker: &StakerInfo, global_idx: Uint128) -> Result<Uint128, ContractError> {
+    Ok(staker
+        .staked
+        .checked_mul(global_idx)
+        .map_err(|_| ContractError::Overflow)?
+        .checked_div(Uint128::from(REWARD_SCALE))
+        .map_err(|_| ContractError::Overflow)?
+        .saturating_sub(staker.reward_debt))
+}
+
 // ── Query ───────────────────────────────────────────────────────────
 
 #[entry_point]
@@ -634,6 +735,9 @@ fn query_config(deps: Deps) -> StdResult<StakingConfigResponse> {
         last_distribution_height: LAST_DISTRIBUTION_HEIGHT.load(deps.storage)?,
         slash_penalty_bps: SLASH_PENALTY_BPS.load(deps.storage)?,
         total_slashed: TOTAL_SLASHED.load(deps.storage)?,
+        credential_registry: CREDENTIAL_REGISTRY
+            .may_load(deps.storage)?
+            .map(|a| a.to_string()),
     })
 }
 
@@ -964,4 +1068,48 @@ mod tests {
         let difficulty = query_difficulty(deps.as_ref(), mock_env()).unwrap();
         assert!(!difficulty.enabled);
     }
+
+    #[test]
+    fn test_credential_hook_moves_stake() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let admin_addr = deps.api.addr_make("admin");
+        let registry_addr = deps.api.addr_make("registry");
+        let old_addr = deps.api.addr_make("staker1");
+        let new_addr = deps.api.addr_make("new_wallet");
+
+        let msg = ExecuteMsg::SetCredentialRegistry {
+            registry: registry_addr.to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin_addr, &[]), msg).unwrap();
+        for (addr, amount) in [(&old_addr, 1_000_000), (&new_addr, 500_000)] {
+            let info = message_info(addr, &coins(amount, "ucitizen"));
+            execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Stake {}).unwrap();
+        }
+
+        let hook = ExecuteMsg::CredentialHook(CredentialHookMsg::Migrated {
+            old_holder: old_addr.to_string(),
+            new_holder: new_addr.to_string(),
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&old_addr, &[]),
+            hook.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let mut env = mock_env();
+        env.block.height += 10;
+        execute(deps.as_mut(), env, message_info(&registry_addr, &[]), hook).unwrap();
+
+        assert!(STAKERS.may_load(&deps.storage, &old_addr).unwrap().is_none());
+        let staker = query_staker(deps.as_ref(), new_addr.to_string()).unwrap();
+        assert_eq!(staker.staked, Uint128::new(1_500_000));
+        assert!(!staker.pending_rewards.is_zero());
+        let config = query_config(deps.as_ref()).unwrap();
+        assert_eq!(config.total_stakers, 1);
+        assert_eq!(config.total_staked, Uint128::new(1_500_000));
+    }
 }
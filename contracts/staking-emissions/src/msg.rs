@@ -1,3 +1,4 @@
+use citizen_common::hooks::CredentialHookMsg;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
 
@@ -62,6 +63,10 @@ pub enum ExecuteMsg {
     UpdateSlashPenalty { slash_penalty_bps: u64 },
     /// Update difficulty scaling config (admin/governance only)
     UpdateDifficulty { config: DifficultyConfig },
+    /// Set the credential registry whose hooks are accepted (admin only)
+    SetCredentialRegistry { registry: String },
+    /// Credential lifecycle notification from the credential registry
+    CredentialHook(CredentialHookMsg),
 }
 
 /// Message for contract migration
@@ -152,6 +157,7 @@ pub struct StakingConfigResponse {
     pub last_distribution_height: u64,
     pub slash_penalty_bps: u64,
     pub total_slashed: Uint128,
+    pub credential_registry: Option<String>,
 }
 
 #[cw_serde]
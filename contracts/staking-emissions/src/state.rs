@@ -13,6 +13,8 @@ pub const TOTAL_STAKERS: Item<u64> = Item::new("total_stakers");
 pub const TREASURY: Item<Addr> = Item::new("treasury");
 pub const TREASURY_SHARE_BPS: Item<u64> = Item::new("treasury_bps");
 pub const LAST_DISTRIBUTION_HEIGHT: Item<u64> = Item::new("last_dist_height");
+/// Credential registry whose lifecycle hooks this contract accepts
+pub const CREDENTIAL_REGISTRY: Item<Addr> = Item::new("cred_registry");
 
 /// Emission phases list
 pub const PHASES: Item<Vec<EmissionPhase>> = Item::new("phases");
@@ -1,3 +1,5 @@
+pub use citizen_common::treasury::TreasuryConfigResponse;
+
 use citizen_common::treasury::FundCategory;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
@@ -97,12 +99,3 @@ pub struct CategorySpendResponse {
     pub category: FundCategory,
     pub total_spent: Uint128,
 }
-
-#[cw_serde]
-pub struct TreasuryConfigResponse {
-    pub admin: String,
-    pub governance_contract: String,
-    pub denom: String,
-    pub total_deposited: Uint128,
-    pub total_spent: Uint128,
-}
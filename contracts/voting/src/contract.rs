@@ -1,11 +1,18 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest,
-    Response, StdResult, Uint128, WasmQuery,
+    coins, entry_point, from_json, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut,
+    Env, Isqrt, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128,
+    WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
 
+use citizen_common::credential::CredentialType;
 use citizen_common::errors::ContractError;
-use citizen_common::governance::{ProposalStatus, VoteOption, VotingMethod};
+use citizen_common::governance::{ProposalAction, ProposalStatus, VoteOption, VotingMethod};
+use citizen_common::registry::{CredentialRegistry, RegistryExecuteMsg};
+use citizen_common::templates::{GovernanceParameter, IssuerAction, ProposalTemplate};
+use citizen_common::treasury::{
+    GrantsExecuteMsg, TreasuryConfigResponse, TreasuryExecuteMsg, TreasuryQueryMsg,
+};
 
 use crate::msg::*;
 use crate::state::*;
@@ -13,6 +20,17 @@ use crate::state::*;
 const CONTRACT_NAME: &str = "crates.io:citizen-voting";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Reply id of proposal action sub-messages
+const ACTION_REPLY_ID: u64 = 1;
+
+/// Reply id of escrow disposal sub-messages
+const ESCROW_REPLY_ID: u64 = 2;
+
+/// Most actions a single proposal may carry
+const MAX_PROPOSAL_ACTIONS: usize = 10;
+
+const DEFAULT_QUADRATIC_DENOM: &str = "ucitizen";
+
 // ── Instantiate ─────────────────────────────────────────────────────
 
 #[entry_point]
@@ -37,6 +55,22 @@ pub fn instantiate(
     QUORUM_BPS.save(deps.storage, &msg.quorum_bps)?;
     THRESHOLD_BPS.save(deps.storage, &msg.threshold_bps)?;
     TIMELOCK_PERIOD.save(deps.storage, &msg.timelock_period)?;
+    EMERGENCY_TIMELOCK_PERIOD.save(
+        deps.storage,
+        &msg.emergency_timelock_period
+            .unwrap_or(msg.timelock_period / 4),
+    )?;
+    ELEVATED_QUORUM_BPS.save(
+        deps.storage,
+        &msg.elevated_quorum_bps
+            .unwrap_or(default_elevated_quorum(msg.quorum_bps)),
+    )?;
+    QUADRATIC_DENOM.save(
+        deps.storage,
+        &msg.quadratic_denom
+            .unwrap_or_else(|| DEFAULT_QUADRATIC_DENOM.to_string()),
+    )?;
+    ESCROW_DISPOSAL.save(deps.storage, &msg.escrow_disposal.unwrap_or_default())?;
     PROPOSAL_COUNT.save(deps.storage, &0u64)?;
 
     Ok(Response::new()
@@ -59,6 +93,7 @@ pub fn execute(
             description,
             voting_method,
             voting_period,
+            actions,
         } => execute_create_proposal(
             deps,
             env,
@@ -67,12 +102,18 @@ pub fn execute(
             description,
             voting_method,
             voting_period,
+            actions.unwrap_or_default(),
         ),
+        ExecuteMsg::CreateFromTemplate {
+            template,
+            voting_period,
+        } => execute_create_from_template(deps, env, info, template, voting_period),
         ExecuteMsg::CastVote {
             proposal_id,
             vote,
             tokens,
         } => execute_cast_vote(deps, env, info, proposal_id, vote, tokens),
+        ExecuteMsg::ClaimEscrow { proposal_id } => execute_claim_escrow(deps, info, proposal_id),
         ExecuteMsg::TallyProposal { proposal_id } => execute_tally(deps, env, info, proposal_id),
         ExecuteMsg::ExecuteProposal { proposal_id } => {
             execute_execute_proposal(deps, env, info, proposal_id)
@@ -83,70 +124,162 @@ pub fn execute(
             quorum_bps,
             threshold_bps,
             timelock_period,
+            emergency_timelock_period,
+            elevated_quorum_bps,
+            grants_contract,
+            escrow_disposal,
         } => execute_update_config(
             deps,
+            env,
             info,
-            voting_period,
-            quorum_bps,
-            threshold_bps,
-            timelock_period,
+            ConfigUpdate {
+                voting_period,
+                quorum_bps,
+                threshold_bps,
+                timelock_period,
+                emergency_timelock_period,
+                elevated_quorum_bps,
+                grants_contract,
+                escrow_disposal,
+            },
         ),
     }
 }
 
-/// Check if a user has a valid citizenship credential by querying the registry
-fn check_credential(deps: &DepsMut, voter: &str) -> Result<bool, ContractError> {
-    let registry = CREDENTIAL_REGISTRY.load(deps.storage)?;
+fn credential_registry(deps: &DepsMut) -> StdResult<CredentialRegistry> {
+    Ok(CredentialRegistry(CREDENTIAL_REGISTRY.load(deps.storage)?))
+}
 
-    // Query the credential registry to check if voter has a valid citizenship credential
-    let query_msg = serde_json::json!({
-        "has_valid_credential": {
-            "holder": voter,
-            "credential_type": "Citizenship"
-        }
-    });
-
-    let result: StdResult<serde_json::Value> =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: registry.to_string(),
-            msg: to_json_binary(&query_msg)?,
-        }));
-
-    match result {
-        // In unit tests without real contracts, we accept all voters
-        Err(_) => Ok(true),
-        Ok(val) => {
-            let has = val
-                .get("has_credential")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            Ok(has)
-        }
+/// Require a valid citizenship credential and return its lineage. Registry
+/// errors are returned as they are, so a registry that can't answer blocks
+/// rather than admits.
+fn check_credential(deps: &DepsMut, env: &Env, voter: &Addr) -> Result<String, ContractError> {
+    let res = credential_registry(deps)?.has_valid_credential(
+        &deps.querier,
+        voter,
+        CredentialType::Citizenship,
+        Some(&env.contract.address),
+    )?;
+    match res.lineage_id {
+        Some(lineage_id) if res.has_credential => Ok(lineage_id),
+        _ => Err(ContractError::NoVerifiedCredential),
     }
 }
 
+/// Holders of a valid citizenship credential in the registry, the population a
+/// proposal's turnout is measured against.
+fn eligible_citizens(deps: &DepsMut) -> StdResult<u64> {
+    credential_registry(deps)?.count_valid_credentials(&deps.querier, CredentialType::Citizenship)
+}
+
+/// Voters as a share of the eligible population in basis points, capped at
+/// 100% as citizens naturalised after the snapshot may vote too.
+fn turnout_bps(proposal: &StoredProposal) -> Option<u64> {
+    proposal.eligible_voters.map(|eligible| {
+        if eligible == 0 {
+            return if proposal.total_voters > 0 { 10000 } else { 0 };
+        }
+        (proposal.total_voters.saturating_mul(10000) / eligible).min(10000)
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 fn execute_create_proposal(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     title: String,
     description: String,
     voting_method: VotingMethod,
     custom_period: Option<u64>,
+    actions: Vec<ProposalAction>,
 ) -> Result<Response, ContractError> {
     // Check proposer has valid credential
-    if !check_credential(&deps, info.sender.as_str())? {
-        return Err(ContractError::NoVerifiedCredential);
-    }
+    check_credential(&deps, &env, &info.sender)?;
+    validate_actions(&deps, &actions)?;
 
     let period = custom_period.unwrap_or(VOTING_PERIOD.load(deps.storage)?);
-    let count = PROPOSAL_COUNT.load(deps.storage)?;
-    let new_id = count + 1;
+    let mut proposal = new_proposal(
+        &mut deps,
+        &env,
+        info.sender,
+        title,
+        description,
+        voting_method,
+        period,
+    )?;
+    proposal.actions = actions;
+    PROPOSALS.save(deps.storage, proposal.id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_proposal")
+        .add_attribute("proposal_id", proposal.id.to_string())
+        .add_attribute("proposer", proposal.proposer.as_str())
+        .add_attribute("title", proposal.title))
+}
 
-    let proposal = StoredProposal {
-        id: new_id,
-        proposer: info.sender.clone(),
-        title: title.clone(),
+fn execute_create_from_template(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    template: ProposalTemplate,
+    custom_period: Option<u64>,
+) -> Result<Response, ContractError> {
+    check_credential(&deps, &env, &info.sender)?;
+    template.validate()?;
+    let actions = template_actions(&deps, &template)?;
+    validate_actions(&deps, &actions)?;
+
+    let metadata = template.metadata();
+    let period = custom_period
+        .unwrap_or(VOTING_PERIOD.load(deps.storage)?)
+        .max(metadata.min_voting_period);
+    let mut proposal = new_proposal(
+        &mut deps,
+        &env,
+        info.sender,
+        template.auto_title(),
+        template.auto_description(),
+        metadata.suggested_voting_method,
+        period,
+    )?;
+    if metadata.elevated_quorum {
+        proposal.quorum_bps = ELEVATED_QUORUM_BPS.load(deps.storage)?;
+    }
+    if matches!(template, ProposalTemplate::Emergency { .. }) {
+        proposal.timelock_period = Some(EMERGENCY_TIMELOCK_PERIOD.load(deps.storage)?);
+    }
+    proposal.actions = actions;
+    proposal.template = Some(template);
+    PROPOSALS.save(deps.storage, proposal.id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_proposal")
+        .add_attribute("proposal_id", proposal.id.to_string())
+        .add_attribute("proposer", proposal.proposer.as_str())
+        .add_attribute("title", proposal.title)
+        .add_attribute("template", metadata.template.label()))
+}
+
+/// A fresh active proposal under the next id, with the configured quorum and
+/// threshold, the current citizen population and no actions.
+fn new_proposal(
+    deps: &mut DepsMut,
+    env: &Env,
+    proposer: Addr,
+    title: String,
+    description: String,
+    voting_method: VotingMethod,
+    period: u64,
+) -> StdResult<StoredProposal> {
+    let eligible_voters = eligible_citizens(deps)?;
+    let storage = &mut *deps.storage;
+    let id = PROPOSAL_COUNT.load(storage)? + 1;
+    PROPOSAL_COUNT.save(storage, &id)?;
+    Ok(StoredProposal {
+        id,
+        proposer,
+        title,
         description,
         status: ProposalStatus::Active,
         voting_method,
@@ -155,20 +288,112 @@ fn execute_create_proposal(
         votes_for: Uint128::zero(),
         votes_against: Uint128::zero(),
         votes_abstain: Uint128::zero(),
-        quorum_bps: QUORUM_BPS.load(deps.storage)?,
-        threshold_bps: THRESHOLD_BPS.load(deps.storage)?,
+        quorum_bps: QUORUM_BPS.load(storage)?,
+        threshold_bps: THRESHOLD_BPS.load(storage)?,
         total_voters: 0,
+        eligible_voters: Some(eligible_voters),
+        escrowed: Uint128::zero(),
+        escrow_disposal: ESCROW_DISPOSAL.load(storage)?,
         execute_at: 0,
-    };
+        timelock_period: None,
+        template: None,
+        actions: vec![],
+        action_results: vec![],
+    })
+}
 
-    PROPOSALS.save(deps.storage, new_id, &proposal)?;
-    PROPOSAL_COUNT.save(deps.storage, &new_id)?;
+/// The action carrying out a template. Emission updates stay advisory: the
+/// staking contract only accepts whole schedules.
+fn template_actions(
+    deps: &DepsMut,
+    template: &ProposalTemplate,
+) -> Result<Vec<ProposalAction>, ContractError> {
+    let action = match template {
+        ProposalTemplate::TreasurySpend {
+            recipient,
+            amount,
+            denom,
+            category,
+            memo,
+        } => {
+            check_treasury_denom(deps, template, denom)?;
+            ProposalAction::TreasurySpend {
+                recipient: recipient.clone(),
+                amount: *amount,
+                category: category.parse()?,
+                memo: memo.clone(),
+            }
+        }
+        ProposalTemplate::ParameterChange {
+            parameter,
+            new_value,
+            ..
+        } => {
+            let value = Some(*new_value);
+            let mut change = (None, None, None, None);
+            match parameter {
+                GovernanceParameter::VotingPeriod => change.0 = value,
+                GovernanceParameter::QuorumBps => change.1 = value,
+                GovernanceParameter::ThresholdBps => change.2 = value,
+                GovernanceParameter::TimelockPeriod => change.3 = value,
+            }
+            ProposalAction::UpdateVotingConfig {
+                voting_period: change.0,
+                quorum_bps: change.1,
+                threshold_bps: change.2,
+                timelock_period: change.3,
+            }
+        }
+        ProposalTemplate::GrantFunding {
+            grant_id, denom, ..
+        } => {
+            check_treasury_denom(deps, template, denom)?;
+            let grants = GRANTS_CONTRACT.may_load(deps.storage)?.ok_or_else(|| {
+                StdError::generic_err("No grants contract configured for grant funding")
+            })?;
+            ProposalAction::ApproveGrant {
+                grants_contract: grants.to_string(),
+                grant_id: *grant_id,
+            }
+        }
+        ProposalTemplate::IssuerManagement {
+            action,
+            issuer_address,
+            ..
+        } => {
+            deps.api.addr_validate(issuer_address)?;
+            let issuer = issuer_address.clone();
+            let msg = match action {
+                IssuerAction::Add => RegistryExecuteMsg::AddIssuer { issuer },
+                IssuerAction::Remove => RegistryExecuteMsg::RemoveIssuer { issuer },
+            };
+            ProposalAction::WasmExecute {
+                contract_addr: CREDENTIAL_REGISTRY.load(deps.storage)?.to_string(),
+                msg: to_json_binary(&msg)?,
+                funds: vec![],
+            }
+        }
+        ProposalTemplate::EmissionUpdate { .. }
+        | ProposalTemplate::Emergency { .. }
+        | ProposalTemplate::TextProposal { .. } => return Ok(vec![]),
+    };
+    Ok(vec![action])
+}
 
-    Ok(Response::new()
-        .add_attribute("action", "create_proposal")
-        .add_attribute("proposal_id", new_id.to_string())
-        .add_attribute("proposer", info.sender.as_str())
-        .add_attribute("title", title))
+/// Templates paying out of the treasury must name the denom it holds.
+fn check_treasury_denom(deps: &DepsMut, template: &ProposalTemplate, denom: &str) -> StdResult<()> {
+    let config: TreasuryConfigResponse = deps
+        .querier
+        .query_wasm_smart(TREASURY.load(deps.storage)?, &TreasuryQueryMsg::Config {})?;
+    if config.denom != denom {
+        return Err(StdError::generic_err(format!(
+            "{} template: treasury pays out {}, not {}",
+            template.label(),
+            config.denom,
+            denom
+        )));
+    }
+    Ok(())
 }
 
 fn execute_cast_vote(
@@ -180,9 +405,7 @@ fn execute_cast_vote(
     tokens: Option<Uint128>,
 ) -> Result<Response, ContractError> {
     // Check voter has valid credential
-    if !check_credential(&deps, info.sender.as_str())? {
-        return Err(ContractError::NoVerifiedCredential);
-    }
+    let lineage_id = check_credential(&deps, &env, &info.sender)?;
 
     let mut proposal = PROPOSALS
         .may_load(deps.storage, proposal_id)?
@@ -196,22 +419,27 @@ fn execute_cast_vote(
         return Err(ContractError::VotingPeriodEnded);
     }
 
-    // Check if already voted
-    if VOTES.has(deps.storage, (proposal_id, &info.sender)) {
+    // Check if already voted, also from an address the credential was recovered from
+    if VOTES.has(deps.storage, (proposal_id, &info.sender))
+        || LINEAGE_VOTES.has(deps.storage, (proposal_id, &lineage_id))
+    {
         return Err(ContractError::AlreadyVoted { id: proposal_id });
     }
 
-    // Calculate vote weight
-    let weight = match proposal.voting_method {
-        VotingMethod::OnePersonOneVote => Uint128::one(),
+    // Quadratic voting: cost = weight^2, so N escrowed tokens buy sqrt(N)
+    // votes (rounded down)
+    let (weight, escrowed) = match proposal.voting_method {
+        VotingMethod::OnePersonOneVote => {
+            if !info.funds.is_empty() {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "One person one vote proposals take no funds",
+                )));
+            }
+            (Uint128::one(), Uint128::zero())
+        }
         VotingMethod::Quadratic => {
-            // Quadratic voting: cost = weight^2
-            // If user sends N tokens, they get sqrt(N) votes (rounded down)
-            let tok = tokens.unwrap_or(Uint128::one());
-            // Integer square root
-            let n = tok.u128();
-            let sqrt = (n as f64).sqrt() as u128;
-            Uint128::new(sqrt.max(1))
+            let paid = quadratic_payment(deps.storage, &info, tokens)?;
+            (paid.isqrt(), paid)
         }
     };
 
@@ -222,15 +450,18 @@ fn execute_cast_vote(
         VoteOption::Abstain => proposal.votes_abstain += weight,
     }
     proposal.total_voters += 1;
+    proposal.escrowed += escrowed;
 
     let stored_vote = StoredVote {
         voter: info.sender.clone(),
         proposal_id,
         vote: vote.clone(),
         weight,
+        escrowed,
     };
 
     VOTES.save(deps.storage, (proposal_id, &info.sender), &stored_vote)?;
+    LINEAGE_VOTES.save(deps.storage, (proposal_id, &lineage_id), &info.sender)?;
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
     Ok(Response::new()
@@ -238,7 +469,103 @@ fn execute_cast_vote(
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("voter", info.sender.as_str())
         .add_attribute("vote", format!("{:?}", vote))
-        .add_attribute("weight", weight.to_string()))
+        .add_attribute("weight", weight.to_string())
+        .add_attribute("escrowed", escrowed.to_string()))
+}
+
+/// Tokens attached to a quadratic vote, all of them in the configured denom.
+fn quadratic_payment(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    tokens: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+    let denom = QUADRATIC_DENOM.load(storage)?;
+    let mut paid = Uint128::zero();
+    for coin in &info.funds {
+        if coin.denom != denom {
+            return Err(ContractError::Std(StdError::generic_err(format!(
+                "Quadratic votes are paid in {}",
+                denom
+            ))));
+        }
+        paid += coin.amount;
+    }
+    if paid.is_zero() {
+        return Err(ContractError::InsufficientFunds {
+            needed: format!("at least 1{}", denom),
+            available: "0".to_string(),
+        });
+    }
+    if tokens.is_some_and(|t| t != paid) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Tokens must match the attached funds",
+        )));
+    }
+    Ok(paid)
+}
+
+/// Settle a tallied proposal's escrow. Refunds are left for voters to claim,
+/// as paying everyone out here could exceed the gas limit. A failed burn or
+/// deposit falls back to refunds in `reply` rather than undoing the tally.
+fn escrow_disposal_msgs(
+    storage: &dyn Storage,
+    proposal: &StoredProposal,
+) -> StdResult<Vec<SubMsg>> {
+    if proposal.escrowed.is_zero() {
+        return Ok(vec![]);
+    }
+    let funds = coins(proposal.escrowed.u128(), QUADRATIC_DENOM.load(storage)?);
+    let msg: CosmosMsg = match proposal.escrow_disposal {
+        EscrowDisposal::Refund => return Ok(vec![]),
+        EscrowDisposal::Burn => BankMsg::Burn { amount: funds }.into(),
+        EscrowDisposal::Treasury => WasmMsg::Execute {
+            contract_addr: TREASURY.load(storage)?.to_string(),
+            msg: to_json_binary(&TreasuryExecuteMsg::Deposit {})?,
+            funds,
+        }
+        .into(),
+    };
+    Ok(vec![
+        SubMsg::reply_on_error(msg, ESCROW_REPLY_ID).with_payload(to_json_binary(&proposal.id)?)
+    ])
+}
+
+fn execute_claim_escrow(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let proposal = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::ProposalNotFound { id: proposal_id })?;
+    if matches!(proposal.status, ProposalStatus::Active) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Escrow is held until the proposal is tallied",
+        )));
+    }
+    if proposal.escrow_disposal != EscrowDisposal::Refund {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Escrow of proposal {} is not refunded",
+            proposal_id
+        ))));
+    }
+    let mut vote = VOTES
+        .may_load(deps.storage, (proposal_id, &info.sender))?
+        .filter(|v| !v.escrowed.is_zero())
+        .ok_or_else(|| StdError::generic_err("No escrow to claim"))?;
+    let amount = vote.escrowed;
+    vote.escrowed = Uint128::zero();
+    VOTES.save(deps.storage, (proposal_id, &info.sender), &vote)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), QUADRATIC_DENOM.load(deps.storage)?),
+        })
+        .add_attribute("action", "claim_escrow")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender.as_str())
+        .add_attribute("amount", amount.to_string()))
 }
 
 fn execute_tally(
@@ -261,15 +588,18 @@ fn execute_tally(
 
     let total_votes = proposal.votes_for + proposal.votes_against + proposal.votes_abstain;
 
-    // Check quorum — for simplicity, we check total_voters against a minimum
-    // In production, this would be total_voters / total_eligible_voters
-    // For now, quorum is met if at least 1 vote was cast (placeholder)
-    let quorum_met = !total_votes.is_zero();
+    // Turnout against the citizen population; proposals from before the
+    // snapshot existed only need a single vote
+    let quorum_met = match turnout_bps(&proposal) {
+        Some(turnout) => proposal.total_voters > 0 && turnout >= proposal.quorum_bps,
+        None => !total_votes.is_zero(),
+    };
 
     if !quorum_met {
         proposal.status = ProposalStatus::Expired;
         PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
         return Ok(Response::new()
+            .add_submessages(escrow_disposal_msgs(deps.storage, &proposal)?)
             .add_attribute("action", "tally")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("result", "expired_no_quorum"));
@@ -286,7 +616,10 @@ fn execute_tally(
     };
 
     if threshold_met {
-        let timelock = TIMELOCK_PERIOD.load(deps.storage)?;
+        let timelock = match proposal.timelock_period {
+            Some(timelock) => timelock,
+            None => TIMELOCK_PERIOD.load(deps.storage)?,
+        };
         if timelock > 0 {
             proposal.status = ProposalStatus::Timelocked;
             proposal.execute_at = env.block.height + timelock;
@@ -301,6 +634,7 @@ fn execute_tally(
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
     Ok(Response::new()
+        .add_submessages(escrow_disposal_msgs(deps.storage, &proposal)?)
         .add_attribute("action", "tally")
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("result", format!("{:?}", proposal.status))
@@ -334,16 +668,143 @@ fn execute_execute_proposal(
         }
     }
 
+    // Replies downgrade the status if any action fails
+    let sub_msgs = proposal
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(index, action)| {
+            let msg = action_msg(deps.as_ref(), &env, proposal_id, action)?;
+            Ok(SubMsg::reply_always(msg, ACTION_REPLY_ID)
+                .with_payload(to_json_binary(&(proposal_id, index as u32))?))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     proposal.status = ProposalStatus::Executed;
+    proposal.action_results = vec![ActionOutcome::Pending; sub_msgs.len()];
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
-    // In a full implementation, this would parse the proposal description
-    // for executable messages (e.g., treasury spends, parameter changes)
-    // and include them as sub-messages.
-
     Ok(Response::new()
+        .add_submessages(sub_msgs)
         .add_attribute("action", "execute_proposal")
-        .add_attribute("proposal_id", proposal_id.to_string()))
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("actions", proposal.actions.len().to_string()))
+}
+
+fn default_elevated_quorum(quorum_bps: u64) -> u64 {
+    (quorum_bps * 2).min(10_000)
+}
+
+/// Reject malformed actions up front rather than when the proposal executes.
+fn validate_actions(deps: &DepsMut, actions: &[ProposalAction]) -> Result<(), ContractError> {
+    if actions.len() > MAX_PROPOSAL_ACTIONS {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "A proposal may carry at most {} actions",
+            MAX_PROPOSAL_ACTIONS
+        ))));
+    }
+    for action in actions {
+        match action {
+            ProposalAction::TreasurySpend { recipient, .. } => {
+                deps.api.addr_validate(recipient)?;
+            }
+            ProposalAction::UpdateVotingConfig { .. } => {}
+            ProposalAction::ApproveGrant {
+                grants_contract, ..
+            } => {
+                deps.api.addr_validate(grants_contract)?;
+            }
+            ProposalAction::WasmExecute { contract_addr, .. }
+            | ProposalAction::WasmMigrate { contract_addr, .. } => {
+                deps.api.addr_validate(contract_addr)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The message carrying out a proposal action.
+fn action_msg(
+    deps: Deps,
+    env: &Env,
+    proposal_id: u64,
+    action: &ProposalAction,
+) -> StdResult<CosmosMsg> {
+    let (contract_addr, msg, funds) = match action {
+        ProposalAction::TreasurySpend {
+            recipient,
+            amount,
+            category,
+            memo,
+        } => {
+            let spend_msg = TreasuryExecuteMsg::Spend {
+                recipient: recipient.clone(),
+                amount: *amount,
+                category: category.clone(),
+                memo: memo.clone(),
+            };
+            (
+                TREASURY.load(deps.storage)?.to_string(),
+                to_json_binary(&spend_msg)?,
+                vec![],
+            )
+        }
+        ProposalAction::UpdateVotingConfig {
+            voting_period,
+            quorum_bps,
+            threshold_bps,
+            timelock_period,
+        } => (
+            env.contract.address.to_string(),
+            to_json_binary(&ExecuteMsg::UpdateConfig {
+                voting_period: *voting_period,
+                quorum_bps: *quorum_bps,
+                threshold_bps: *threshold_bps,
+                timelock_period: *timelock_period,
+                emergency_timelock_period: None,
+                elevated_quorum_bps: None,
+                grants_contract: None,
+                escrow_disposal: None,
+            })?,
+            vec![],
+        ),
+        ProposalAction::ApproveGrant {
+            grants_contract,
+            grant_id,
+        } => {
+            let approve_msg = GrantsExecuteMsg::Approve {
+                grant_id: *grant_id,
+                proposal_id,
+            };
+            (
+                grants_contract.clone(),
+                to_json_binary(&approve_msg)?,
+                vec![],
+            )
+        }
+        ProposalAction::WasmExecute {
+            contract_addr,
+            msg,
+            funds,
+        } => (contract_addr.clone(), msg.clone(), funds.clone()),
+        ProposalAction::WasmMigrate {
+            contract_addr,
+            new_code_id,
+            msg,
+        } => {
+            return Ok(WasmMsg::Migrate {
+                contract_addr: contract_addr.clone(),
+                new_code_id: *new_code_id,
+                msg: msg.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr,
+        msg,
+        funds,
+    }
+    .into())
 }
 
 fn execute_cancel(
@@ -362,7 +823,20 @@ fn execute_cancel(
             reason: "Only proposer or admin can cancel".to_string(),
         });
     }
+    // Once executed, or settled without passing, there is nothing left to stop
+    if !matches!(
+        proposal.status,
+        ProposalStatus::Active | ProposalStatus::Timelocked | ProposalStatus::Passed
+    ) {
+        return Err(ContractError::Unauthorized {
+            reason: "Only active or passed, unexecuted proposals can be cancelled".to_string(),
+        });
+    }
 
+    // Votes on a proposal that never got tallied are refunded
+    if matches!(proposal.status, ProposalStatus::Active) {
+        proposal.escrow_disposal = EscrowDisposal::Refund;
+    }
     proposal.status = ProposalStatus::Cancelled;
     PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
 
@@ -371,33 +845,55 @@ fn execute_cancel(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
-fn execute_update_config(
-    deps: DepsMut,
-    info: MessageInfo,
+/// Fields of `ExecuteMsg::UpdateConfig`; `None` leaves a setting unchanged.
+struct ConfigUpdate {
     voting_period: Option<u64>,
     quorum_bps: Option<u64>,
     threshold_bps: Option<u64>,
     timelock_period: Option<u64>,
+    emergency_timelock_period: Option<u64>,
+    elevated_quorum_bps: Option<u64>,
+    grants_contract: Option<String>,
+    escrow_disposal: Option<EscrowDisposal>,
+}
+
+fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
 ) -> Result<Response, ContractError> {
     let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
+    if info.sender != admin && info.sender != env.contract.address {
         return Err(ContractError::Unauthorized {
             reason: "Only admin can update config".to_string(),
         });
     }
 
-    if let Some(vp) = voting_period {
+    if let Some(vp) = update.voting_period {
         VOTING_PERIOD.save(deps.storage, &vp)?;
     }
-    if let Some(q) = quorum_bps {
+    if let Some(q) = update.quorum_bps {
         QUORUM_BPS.save(deps.storage, &q)?;
     }
-    if let Some(t) = threshold_bps {
+    if let Some(t) = update.threshold_bps {
         THRESHOLD_BPS.save(deps.storage, &t)?;
     }
-    if let Some(tl) = timelock_period {
+    if let Some(tl) = update.timelock_period {
         TIMELOCK_PERIOD.save(deps.storage, &tl)?;
     }
+    if let Some(tl) = update.emergency_timelock_period {
+        EMERGENCY_TIMELOCK_PERIOD.save(deps.storage, &tl)?;
+    }
+    if let Some(q) = update.elevated_quorum_bps {
+        ELEVATED_QUORUM_BPS.save(deps.storage, &q)?;
+    }
+    if let Some(grants) = update.grants_contract {
+        GRANTS_CONTRACT.save(deps.storage, &deps.api.addr_validate(&grants)?)?;
+    }
+    if let Some(disposal) = update.escrow_disposal {
+        ESCROW_DISPOSAL.save(deps.storage, &disposal)?;
+    }
 
     Ok(Response::new().add_attribute("action", "update_config"))
 }
@@ -464,6 +960,7 @@ fn query_vote(deps: Deps, proposal_id: u64, voter: String) -> StdResult<VoteResp
         proposal_id: v.proposal_id,
         vote: v.vote,
         weight: v.weight,
+        escrowed: v.escrowed,
     })
 }
 
@@ -485,6 +982,7 @@ fn query_list_votes(
             proposal_id: v.proposal_id,
             vote: v.vote,
             weight: v.weight,
+            escrowed: v.escrowed,
         })
         .collect();
 
@@ -500,11 +998,19 @@ fn query_config(deps: Deps) -> StdResult<VotingConfigResponse> {
         quorum_bps: QUORUM_BPS.load(deps.storage)?,
         threshold_bps: THRESHOLD_BPS.load(deps.storage)?,
         timelock_period: TIMELOCK_PERIOD.load(deps.storage)?,
+        emergency_timelock_period: EMERGENCY_TIMELOCK_PERIOD.load(deps.storage)?,
+        elevated_quorum_bps: ELEVATED_QUORUM_BPS.load(deps.storage)?,
+        grants_contract: GRANTS_CONTRACT
+            .may_load(deps.storage)?
+            .map(|a| a.to_string()),
+        quadratic_denom: QUADRATIC_DENOM.load(deps.storage)?,
+        escrow_disposal: ESCROW_DISPOSAL.load(deps.storage)?,
         total_proposals: PROPOSAL_COUNT.load(deps.storage)?,
     })
 }
 
 fn to_proposal_response(p: StoredProposal) -> ProposalResponse {
+    let turnout_bps = turnout_bps(&p);
     ProposalResponse {
         id: p.id,
         proposer: p.proposer.to_string(),
@@ -519,8 +1025,67 @@ fn to_proposal_response(p: StoredProposal) -> ProposalResponse {
         votes_abstain: p.votes_abstain,
         quorum_bps: p.quorum_bps,
         threshold_bps: p.threshold_bps,
+        eligible_voters: p.eligible_voters,
+        turnout_bps,
+        escrowed: p.escrowed,
+        escrow_disposal: p.escrow_disposal,
         execute_at: p.execute_at,
+        template: p.template,
+        actions: p.actions,
+        action_results: p.action_results,
+    }
+}
+
+// ── Reply ───────────────────────────────────────────────────────────
+
+/// Record the outcome of a proposal action. A failed action leaves the
+/// others in place and marks the proposal `ExecutionFailed`.
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id == ESCROW_REPLY_ID {
+        return escrow_disposal_failed(deps, msg);
     }
+    if msg.id != ACTION_REPLY_ID {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Unknown reply id {}",
+            msg.id
+        ))));
+    }
+    let (proposal_id, index): (u64, u32) = from_json(&msg.payload)?;
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let outcome = match msg.result.into_result() {
+        Ok(_) => ActionOutcome::Succeeded,
+        Err(error) => {
+            proposal.status = ProposalStatus::ExecutionFailed;
+            ActionOutcome::Failed { error }
+        }
+    };
+    let result = match &outcome {
+        ActionOutcome::Failed { .. } => "failed",
+        _ => "succeeded",
+    };
+    proposal.action_results[index as usize] = outcome;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "proposal_action_result")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("index", index.to_string())
+        .add_attribute("result", result))
+}
+
+/// The escrow could not be burned or deposited, so it stays here for voters to claim.
+fn escrow_disposal_failed(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let proposal_id: u64 = from_json(&msg.payload)?;
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    proposal.escrow_disposal = EscrowDisposal::Refund;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    let error = msg.result.into_result().err().unwrap_or_default();
+
+    Ok(Response::new()
+        .add_attribute("action", "escrow_disposal_failed")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("error", error))
 }
 
 // ── Migrate ─────────────────────────────────────────────────────────
@@ -534,6 +1099,22 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         });
     }
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // Deployments from before template proposals
+    if !EMERGENCY_TIMELOCK_PERIOD.exists(deps.storage) {
+        let timelock = TIMELOCK_PERIOD.load(deps.storage)?;
+        EMERGENCY_TIMELOCK_PERIOD.save(deps.storage, &(timelock / 4))?;
+    }
+    if !ELEVATED_QUORUM_BPS.exists(deps.storage) {
+        let quorum = QUORUM_BPS.load(deps.storage)?;
+        ELEVATED_QUORUM_BPS.save(deps.storage, &default_elevated_quorum(quorum))?;
+    }
+    // Deployments from before quadratic votes were paid for
+    if !QUADRATIC_DENOM.exists(deps.storage) {
+        QUADRATIC_DENOM.save(deps.storage, &DEFAULT_QUADRATIC_DENOM.to_string())?;
+    }
+    if !ESCROW_DISPOSAL.exists(deps.storage) {
+        ESCROW_DISPOSAL.save(deps.storage, &EscrowDisposal::default())?;
+    }
     Ok(Response::new()
         .add_attribute("action", "migrate")
         .add_attribute("from_version", version.version)
@@ -545,7 +1126,25 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env, MockApi};
+    use citizen_common::registry::MockRegistry;
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+        MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{ContractResult, OwnedDeps, SystemResult, WasmQuery};
+
+    /// Dependencies whose credential registry knows `citizens` as citizens
+    fn citizen_deps(citizens: &[&str]) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        let mut registry = MockRegistry::new(deps.api.addr_make("cred_registry"));
+        for citizen in citizens {
+            let holder = deps.api.addr_make(citizen);
+            registry = registry.with_credential(holder, CredentialType::Citizenship);
+        }
+        deps.querier
+            .update_wasm(move |query| registry.handle(query));
+        deps
+    }
 
     fn setup(deps: DepsMut) {
         let api = MockApi::default();
@@ -561,6 +1160,10 @@ mod tests {
             quorum_bps: 3000,
             threshold_bps: 5000,
             timelock_period: 50,
+            emergency_timelock_period: Some(10),
+            elevated_quorum_bps: None,
+            quadratic_denom: None,
+            escrow_disposal: None,
         };
         let info = message_info(&creator_addr, &[]);
         instantiate(deps, mock_env(), info, msg).unwrap();
@@ -568,7 +1171,7 @@ mod tests {
 
     #[test]
     fn test_create_proposal() {
-        let mut deps = mock_dependencies();
+        let mut deps = citizen_deps(&["citizen1"]);
         setup(deps.as_mut());
 
         let citizen1_addr = deps.api.addr_make("citizen1");
@@ -578,6 +1181,7 @@ mod tests {
             description: "Allocate 10000 ucitizen for cancer research".to_string(),
             voting_method: VotingMethod::OnePersonOneVote,
             voting_period: None,
+            actions: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(res.attributes[0].value, "create_proposal");
@@ -588,7 +1192,7 @@ mod tests {
 
     #[test]
     fn test_vote_one_person_one_vote() {
-        let mut deps = mock_dependencies();
+        let mut deps = citizen_deps(&["citizen1", "citizen2"]);
         setup(deps.as_mut());
 
         // Create proposal
@@ -599,6 +1203,7 @@ mod tests {
             description: "Test desc".to_string(),
             voting_method: VotingMethod::OnePersonOneVote,
             voting_period: None,
+            actions: None,
         };
         execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -618,7 +1223,7 @@ mod tests {
 
     #[test]
     fn test_double_vote_rejected() {
-        let mut deps = mock_dependencies();
+        let mut deps = citizen_deps(&["citizen1"]);
         setup(deps.as_mut());
 
         let citizen1_addr = deps.api.addr_make("citizen1");
@@ -632,6 +1237,7 @@ mod tests {
                 description: "Desc".to_string(),
                 voting_method: VotingMethod::OnePersonOneVote,
                 voting_period: None,
+                actions: None,
             },
         )
         .unwrap();
@@ -666,7 +1272,7 @@ mod tests {
 
     #[test]
     fn test_tally_passed() {
-        let mut deps = mock_dependencies();
+        let mut deps = citizen_deps(&["citizen1"]);
         setup(deps.as_mut());
 
         let citizen1_addr = deps.api.addr_make("citizen1");
@@ -680,6 +1286,7 @@ mod tests {
                 description: "Desc".to_string(),
                 voting_method: VotingMethod::OnePersonOneVote,
                 voting_period: Some(10),
+                actions: None,
             },
         )
         .unwrap();
@@ -740,5 +1347,435 @@ mod tests {
 
         let p2 = query_proposal(deps.as_ref(), 1).unwrap();
         assert_eq!(p2.status, "Executed");
+
+        // An executed proposal can no longer be cancelled, even by its proposer
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&citizen1_addr, &[]),
+            ExecuteMsg::CancelProposal { proposal_id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+        assert_eq!(query_proposal(deps.as_ref(), 1).unwrap().status, "Executed");
+    }
+
+    #[test]
+    fn test_execute_dispatches_actions() {
+        let mut deps = citizen_deps(&["citizen1"]);
+        setup(deps.as_mut());
+
+        let citizen1_addr = deps.api.addr_make("citizen1");
+        let info = message_info(&citizen1_addr, &[]);
+        let spend = ProposalAction::TreasurySpend {
+            recipient: citizen1_addr.to_string(),
+            amount: Uint128::new(1_000),
+            category: citizen_common::treasury::FundCategory::Research,
+            memo: "Lab equipment".to_string(),
+        };
+        let create = |actions: Vec<ProposalAction>| ExecuteMsg::CreateProposal {
+            title: "Test".to_string(),
+            description: "Desc".to_string(),
+            voting_method: VotingMethod::OnePersonOneVote,
+            voting_period: Some(10),
+            actions: Some(actions),
+        };
+
+        // Malformed actions are refused when the proposal is created
+        let bad = ProposalAction::ApproveGrant {
+            grants_contract: "not-an-address".to_string(),
+            grant_id: 1,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), create(vec![bad])).unwrap_err();
+
+        let config = ProposalAction::UpdateVotingConfig {
+            voting_period: Some(200),
+            quorum_bps: None,
+            threshold_bps: None,
+            timelock_period: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            create(vec![spend, config]),
+        )
+        .unwrap();
+        let vote = ExecuteMsg::CastVote {
+            proposal_id: 1,
+            vote: VoteOption::Yes,
+            tokens: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), vote).unwrap();
+
+        // Past the voting period, then past the timelock
+        let mut env = mock_env();
+        env.block.height += 20;
+        let tally = ExecuteMsg::TallyProposal { proposal_id: 1 };
+        execute(deps.as_mut(), env.clone(), info.clone(), tally).unwrap();
+        env.block.height += 50;
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        match &res.messages[1].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+                assert_eq!(contract_addr, env.contract.address.as_str())
+            }
+            msg => panic!("unexpected message: {msg:?}"),
+        }
+        let p = query_proposal(deps.as_ref(), 1).unwrap();
+        assert_eq!(p.status, "Executed");
+        assert_eq!(p.action_results, vec![ActionOutcome::Pending; 2]);
+
+        // A failing action marks the proposal, the other keeps its outcome
+        let failed = Reply {
+            id: ACTION_REPLY_ID,
+            payload: res.messages[0].payload.clone(),
+            gas_used: 0,
+            result: cosmwasm_std::SubMsgResult::Err("insufficient funds".to_string()),
+        };
+        reply(deps.as_mut(), env, failed).unwrap();
+        let p = query_proposal(deps.as_ref(), 1).unwrap();
+        assert_eq!(p.status, "ExecutionFailed");
+        assert_eq!(
+            p.action_results[0],
+            ActionOutcome::Failed {
+                error: "insufficient funds".to_string()
+            }
+        );
+        assert_eq!(p.action_results[1], ActionOutcome::Pending);
+    }
+
+    #[test]
+    fn test_quadratic_escrow() {
+        let mut deps = citizen_deps(&["citizen1", "citizen2"]);
+        setup(deps.as_mut());
+        let admin_addr = deps.api.addr_make("admin");
+        let citizen1_addr = deps.api.addr_make("citizen1");
+        let citizen2_addr = deps.api.addr_make("citizen2");
+        let update = ExecuteMsg::UpdateConfig {
+            voting_period: None,
+            quorum_bps: None,
+            threshold_bps: None,
+            timelock_period: None,
+            emergency_timelock_period: None,
+            elevated_quorum_bps: None,
+            grants_contract: None,
+            escrow_disposal: Some(EscrowDisposal::Burn),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin_addr, &[]),
+            update,
+        )
+        .unwrap();
+        for _ in 0..2 {
+            let msg = ExecuteMsg::CreateProposal {
+                title: "Test".to_string(),
+                description: "Test desc".to_string(),
+                voting_method: VotingMethod::Quadratic,
+                voting_period: Some(10),
+                actions: None,
+            };
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&citizen1_addr, &[]),
+                msg,
+            )
+            .unwrap();
+        }
+        let vote = |proposal_id| ExecuteMsg::CastVote {
+            proposal_id,
+            vote: VoteOption::Yes,
+            tokens: None,
+        };
+
+        // Weight is the exact integer square root of what was paid
+        let wrong_denom = message_info(&citizen1_addr, &coins(10, "uatom"));
+        execute(deps.as_mut(), mock_env(), wrong_denom, vote(1)).unwrap_err();
+        let paid = message_info(&citizen1_addr, &coins(99, "ucitizen"));
+        execute(deps.as_mut(), mock_env(), paid.clone(), vote(1)).unwrap();
+        execute(deps.as_mut(), mock_env(), paid, vote(2)).unwrap();
+        let paid = message_info(&citizen2_addr, &coins(100, "ucitizen"));
+        execute(deps.as_mut(), mock_env(), paid, vote(2)).unwrap();
+        let p = query_proposal(deps.as_ref(), 1).unwrap();
+        assert_eq!(p.votes_for, Uint128::new(9));
+        assert_eq!(p.escrowed, Uint128::new(99));
+
+        // The first is tallied and its escrow burned
+        let mut env = mock_env();
+        env.block.height += 20;
+        let tally = ExecuteMsg::TallyProposal { proposal_id: 1 };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&citizen1_addr, &[]),
+            tally,
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Burn {
+                amount: coins(99, "ucitizen")
+            })
+        );
+        assert_eq!(res.messages[0].reply_on, cosmwasm_std::ReplyOn::Error);
+        let claim = |proposal_id| ExecuteMsg::ClaimEscrow { proposal_id };
+        let info = message_info(&citizen1_addr, &[]);
+        execute(deps.as_mut(), env.clone(), info.clone(), claim(1)).unwrap_err();
+
+        // A failed burn leaves the escrow for its voters to claim
+        let failed = Reply {
+            id: ESCROW_REPLY_ID,
+            payload: res.messages[0].payload.clone(),
+            gas_used: 0,
+            result: cosmwasm_std::SubMsgResult::Err("burn disabled".to_string()),
+        };
+        reply(deps.as_mut(), env.clone(), failed).unwrap();
+        let p = query_proposal(deps.as_ref(), 1).unwrap();
+        assert_eq!(p.status, "Timelocked");
+        assert_eq!(p.escrow_disposal, EscrowDisposal::Refund);
+        execute(deps.as_mut(), env.clone(), info.clone(), claim(1)).unwrap();
+
+        // The second is cancelled, so its voters take their tokens back
+        execute(deps.as_mut(), env.clone(), info.clone(), claim(2)).unwrap_err();
+        let cancel = ExecuteMsg::CancelProposal { proposal_id: 2 };
+        execute(deps.as_mut(), env.clone(), info.clone(), cancel).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), info, claim(2)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: citizen1_addr.to_string(),
+                amount: coins(99, "ucitizen"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_credential_check_fails_closed() {
+        let mut deps = citizen_deps(&["citizen1"]);
+        setup(deps.as_mut());
+        let create = ExecuteMsg::CreateProposal {
+            title: "Test".to_string(),
+            description: "Test desc".to_string(),
+            voting_method: VotingMethod::OnePersonOneVote,
+            voting_period: None,
+            actions: None,
+        };
+        let outsider = deps.api.addr_make("outsider");
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&outsider, &[]),
+            create.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoVerifiedCredential));
+
+        // Without a registry to answer, even citizens are turned away
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let citizen1_addr = deps.api.addr_make("citizen1");
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&citizen1_addr, &[]),
+            create,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+    }
+
+    #[test]
+    fn test_quorum_against_population() {
+        let citizens: Vec<String> = (0..10).map(|i| format!("citizen{}", i)).collect();
+        let citizens: Vec<&str> = citizens.iter().map(String::as_str).collect();
+        let mut deps = citizen_deps(&citizens);
+        setup(deps.as_mut());
+        let proposer = deps.api.addr_make("citizen0");
+        for _ in 0..2 {
+            let msg = ExecuteMsg::CreateProposal {
+                title: "Extend library hours".to_string(),
+                description: "Open until ten".to_string(),
+                voting_method: VotingMethod::OnePersonOneVote,
+                voting_period: Some(10),
+                actions: None,
+            };
+            execute(deps.as_mut(), mock_env(), message_info(&proposer, &[]), msg).unwrap();
+        }
+        assert_eq!(
+            query_proposal(deps.as_ref(), 1).unwrap().eligible_voters,
+            Some(10)
+        );
+
+        // Two of ten citizens vote on the first, three on the second
+        for (proposal_id, voters) in [(1, 2), (2, 3)] {
+            for i in 0..voters {
+                let voter = deps.api.addr_make(&format!("citizen{}", i));
+                let msg = ExecuteMsg::CastVote {
+                    proposal_id,
+                    vote: VoteOption::Yes,
+                    tokens: None,
+                };
+                execute(deps.as_mut(), mock_env(), message_info(&voter, &[]), msg).unwrap();
+            }
+        }
+        let mut env = mock_env();
+        env.block.height += 20;
+        for proposal_id in [1, 2] {
+            let msg = ExecuteMsg::TallyProposal { proposal_id };
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&proposer, &[]),
+                msg,
+            )
+            .unwrap();
+        }
+
+        // 30% quorum: 20% turnout falls short, 30% meets it
+        let p = query_proposal(deps.as_ref(), 1).unwrap();
+        assert_eq!(p.turnout_bps, Some(2000));
+        assert_eq!(p.status, "Expired");
+        let p = query_proposal(deps.as_ref(), 2).unwrap();
+        assert_eq!(p.turnout_bps, Some(3000));
+        assert_eq!(p.status, "Timelocked");
+    }
+
+    #[test]
+    fn test_create_from_template() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let citizen1_addr = deps.api.addr_make("citizen1");
+        let info = message_info(&citizen1_addr, &[]);
+
+        // The treasury holds ucitizen
+        let treasury = deps.api.addr_make("treasury").to_string();
+        let config = TreasuryConfigResponse {
+            admin: deps.api.addr_make("admin").to_string(),
+            governance_contract: MOCK_CONTRACT_ADDR.to_string(),
+            denom: "ucitizen".to_string(),
+            total_deposited: Uint128::zero(),
+            total_spent: Uint128::zero(),
+        };
+        let registry = MockRegistry::new(deps.api.addr_make("cred_registry"))
+            .with_credential(citizen1_addr.clone(), CredentialType::Citizenship);
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if *contract_addr == treasury => {
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&config).unwrap()))
+            }
+            _ => registry.handle(query),
+        });
+        let create = |deps: DepsMut, template: ProposalTemplate| {
+            let msg = ExecuteMsg::CreateFromTemplate {
+                template,
+                voting_period: Some(10),
+            };
+            execute(deps, mock_env(), info.clone(), msg)
+        };
+
+        // Governance changes get the longer minimum period and become an action
+        let change = ProposalTemplate::ParameterChange {
+            parameter: GovernanceParameter::QuorumBps,
+            new_value: 4000,
+            rationale: "Turnout has grown".to_string(),
+        };
+        create(deps.as_mut(), change).unwrap();
+        let p = query_proposal(deps.as_ref(), 1).unwrap();
+        assert_eq!(p.title, "Set QuorumBps to 4000");
+        assert_eq!(
+            p.end_height - p.start_height,
+            citizen_common::templates::GOVERNANCE_CHANGE_MIN_PERIOD
+        );
+        assert_eq!(p.quorum_bps, 3000);
+        assert_eq!(
+            p.actions,
+            vec![ProposalAction::UpdateVotingConfig {
+                voting_period: None,
+                quorum_bps: Some(4000),
+                threshold_bps: None,
+                timelock_period: None,
+            }]
+        );
+
+        // Invalid fields and unknown categories are refused
+        let bad = ProposalTemplate::ParameterChange {
+            parameter: GovernanceParameter::ThresholdBps,
+            new_value: 20_000,
+            rationale: "More".to_string(),
+        };
+        create(deps.as_mut(), bad).unwrap_err();
+        let spend = |category: &str| ProposalTemplate::TreasurySpend {
+            recipient: citizen1_addr.to_string(),
+            amount: Uint128::new(1_000),
+            denom: "ucitizen".to_string(),
+            category: category.to_string(),
+            memo: "Microscopes".to_string(),
+        };
+        create(deps.as_mut(), spend("lab")).unwrap_err();
+        let foreign = ProposalTemplate::TreasurySpend {
+            recipient: citizen1_addr.to_string(),
+            amount: Uint128::new(1_000),
+            denom: "uatom".to_string(),
+            category: "research".to_string(),
+            memo: "Microscopes".to_string(),
+        };
+        let err = create(deps.as_mut(), foreign).unwrap_err();
+        assert!(err.to_string().contains("treasury pays out ucitizen"));
+        create(deps.as_mut(), spend("research")).unwrap();
+        let p = query_proposal(deps.as_ref(), 2).unwrap();
+        assert!(matches!(
+            &p.actions[0],
+            ProposalAction::TreasurySpend {
+                category: citizen_common::treasury::FundCategory::Research,
+                ..
+            }
+        ));
+
+        // Grant funding needs a grants contract to approve in
+        let funding = ProposalTemplate::GrantFunding {
+            grant_id: 7,
+            total_funding: Uint128::new(5_000),
+            denom: "ucitizen".to_string(),
+        };
+        create(deps.as_mut(), funding).unwrap_err();
+
+        // Emergencies get the elevated quorum and the short timelock
+        let emergency = ProposalTemplate::Emergency {
+            action: "Pause disbursements".to_string(),
+            description: "Treasury key suspected leaked".to_string(),
+        };
+        create(deps.as_mut(), emergency).unwrap();
+        let p = query_proposal(deps.as_ref(), 3).unwrap();
+        assert_eq!(p.quorum_bps, 6000);
+        assert!(p.actions.is_empty());
+        let vote = ExecuteMsg::CastVote {
+            proposal_id: 3,
+            vote: VoteOption::Yes,
+            tokens: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), vote).unwrap();
+        let mut env = mock_env();
+        env.block.height += 20;
+        let tally = ExecuteMsg::TallyProposal { proposal_id: 3 };
+        execute(deps.as_mut(), env.clone(), info.clone(), tally).unwrap();
+        let p = query_proposal(deps.as_ref(), 3).unwrap();
+        assert_eq!(p.execute_at, env.block.height + 10);
+
+        // Signals count each citizen once
+        let signal = ProposalTemplate::TextProposal {
+            summary: "Open a second clinic".to_string(),
+        };
+        create(deps.as_mut(), signal).unwrap();
+        let p = query_proposal(deps.as_ref(), 4).unwrap();
+        assert_eq!(p.voting_method, VotingMethod::OnePersonOneVote);
+        assert!(p.actions.is_empty());
     }
 }
@@ -1,7 +1,10 @@
-use citizen_common::governance::{VoteOption, VotingMethod};
+use citizen_common::governance::{ProposalAction, VoteOption, VotingMethod};
+use citizen_common::templates::ProposalTemplate;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
 
+pub use crate::state::{ActionOutcome, EscrowDisposal};
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin: String,
@@ -17,6 +20,14 @@ pub struct InstantiateMsg {
     pub threshold_bps: u64,
     /// Timelock period in blocks before a passed proposal can be executed (0 = no timelock)
     pub timelock_period: u64,
+    /// Timelock of emergency proposals; defaults to a quarter of `timelock_period`
+    pub emergency_timelock_period: Option<u64>,
+    /// Quorum of proposals whose template asks for more; defaults to twice `quorum_bps`
+    pub elevated_quorum_bps: Option<u64>,
+    /// Native denom quadratic votes are paid in; defaults to `ucitizen`
+    pub quadratic_denom: Option<String>,
+    /// What happens to quadratic vote escrow after tally; defaults to refunding it
+    pub escrow_disposal: Option<EscrowDisposal>,
 }
 
 #[cw_serde]
@@ -28,26 +39,46 @@ pub enum ExecuteMsg {
         voting_method: VotingMethod,
         /// Optional custom voting period override
         voting_period: Option<u64>,
+        /// Dispatched in order by `ExecuteProposal` once the proposal passes
+        actions: Option<Vec<ProposalAction>>,
+    },
+    /// Create a proposal from a template (requires valid credential). The title,
+    /// description, voting method and action come from the template; the voting
+    /// period is raised to the template's minimum if shorter.
+    CreateFromTemplate {
+        template: ProposalTemplate,
+        voting_period: Option<u64>,
     },
     /// Cast a vote (requires valid credential)
     CastVote {
         proposal_id: u64,
         vote: VoteOption,
-        /// For quadratic voting: number of tokens to spend on votes
+        /// For quadratic voting: tokens spent on the vote, for sqrt(tokens)
+        /// weight. They must be attached as funds; if given, this must match.
         tokens: Option<Uint128>,
     },
+    /// Take back the tokens a quadratic vote escrowed, once the proposal is
+    /// tallied with refunds or cancelled
+    ClaimEscrow { proposal_id: u64 },
     /// Tally and finalize a proposal after voting ends
     TallyProposal { proposal_id: u64 },
-    /// Execute a passed proposal
+    /// Execute a passed proposal, dispatching its actions. Each action runs on
+    /// its own; if any fails the proposal ends up `ExecutionFailed`.
     ExecuteProposal { proposal_id: u64 },
     /// Cancel a proposal (proposer or admin only)
     CancelProposal { proposal_id: u64 },
-    /// Update config (admin only)
+    /// Update config (admin, or the contract itself through a proposal action)
     UpdateConfig {
         voting_period: Option<u64>,
         quorum_bps: Option<u64>,
         threshold_bps: Option<u64>,
         timelock_period: Option<u64>,
+        emergency_timelock_period: Option<u64>,
+        elevated_quorum_bps: Option<u64>,
+        /// Grants contract that grant funding templates approve in
+        grants_contract: Option<String>,
+        /// Applies to proposals created afterwards
+        escrow_disposal: Option<EscrowDisposal>,
     },
 }
 
@@ -104,7 +135,19 @@ pub struct ProposalResponse {
     pub votes_abstain: Uint128,
     pub quorum_bps: u64,
     pub threshold_bps: u64,
+    /// Citizens eligible to vote, counted when the proposal was created
+    pub eligible_voters: Option<u64>,
+    /// Voters as a share of `eligible_voters`, in basis points
+    pub turnout_bps: Option<u64>,
+    /// Tokens paid into quadratic votes
+    pub escrowed: Uint128,
+    pub escrow_disposal: EscrowDisposal,
     pub execute_at: u64,
+    /// Template the proposal was created from, if any
+    pub template: Option<ProposalTemplate>,
+    pub actions: Vec<ProposalAction>,
+    /// One entry per action once the proposal has been executed
+    pub action_results: Vec<ActionOutcome>,
 }
 
 #[cw_serde]
@@ -118,6 +161,8 @@ pub struct VoteResponse {
     pub proposal_id: u64,
     pub vote: VoteOption,
     pub weight: Uint128,
+    /// Tokens of a quadratic vote still held by the contract
+    pub escrowed: Uint128,
 }
 
 #[cw_serde]
@@ -134,5 +179,10 @@ pub struct VotingConfigResponse {
     pub quorum_bps: u64,
     pub threshold_bps: u64,
     pub timelock_period: u64,
+    pub emergency_timelock_period: u64,
+    pub elevated_quorum_bps: u64,
+    pub grants_contract: Option<String>,
+    pub quadratic_denom: String,
+    pub escrow_disposal: EscrowDisposal,
     pub total_proposals: u64,
 }
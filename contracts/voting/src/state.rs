@@ -1,4 +1,5 @@
-use citizen_common::governance::{ProposalStatus, VoteOption, VotingMethod};
+use citizen_common::governance::{ProposalAction, ProposalStatus, VoteOption, VotingMethod};
+use citizen_common::templates::ProposalTemplate;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
@@ -10,6 +11,13 @@ pub const VOTING_PERIOD: Item<u64> = Item::new("voting_period");
 pub const QUORUM_BPS: Item<u64> = Item::new("quorum_bps");
 pub const THRESHOLD_BPS: Item<u64> = Item::new("threshold_bps");
 pub const TIMELOCK_PERIOD: Item<u64> = Item::new("timelock_period");
+pub const EMERGENCY_TIMELOCK_PERIOD: Item<u64> = Item::new("emergency_timelock");
+pub const ELEVATED_QUORUM_BPS: Item<u64> = Item::new("elevated_quorum_bps");
+/// Grants contract approved in by grant funding proposals
+pub const GRANTS_CONTRACT: Item<Addr> = Item::new("grants");
+pub const QUADRATIC_DENOM: Item<String> = Item::new("quadratic_denom");
+/// Disposal given to new proposals' quadratic vote escrow
+pub const ESCROW_DISPOSAL: Item<EscrowDisposal> = Item::new("escrow_disposal");
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("prop_count");
 
 /// Proposals by ID
@@ -18,6 +26,10 @@ pub const PROPOSALS: Map<u64, StoredProposal> = Map::new("proposals");
 /// Votes: (proposal_id, voter_addr) → vote
 pub const VOTES: Map<(u64, &Addr), StoredVote> = Map::new("votes");
 
+/// Credential lineages that voted: (proposal_id, lineage_id) → voter. A
+/// lineage survives renewal and key recovery, so a new key cannot vote twice.
+pub const LINEAGE_VOTES: Map<(u64, &str), Addr> = Map::new("lineage_votes");
+
 #[cw_serde]
 pub struct StoredProposal {
     pub id: u64,
@@ -34,8 +46,49 @@ pub struct StoredProposal {
     pub quorum_bps: u64,
     pub threshold_bps: u64,
     pub total_voters: u64,
+    /// Citizens with a valid credential when the proposal was created; unset on
+    /// proposals from before quorum was measured against it
+    #[serde(default)]
+    pub eligible_voters: Option<u64>,
+    /// Tokens paid into quadratic votes
+    #[serde(default)]
+    pub escrowed: Uint128,
+    /// Set from the config at creation; cancellation switches it to refunds
+    #[serde(default)]
+    pub escrow_disposal: EscrowDisposal,
     /// Block height at which timelock expires and proposal can be executed
     pub execute_at: u64,
+    /// Replaces the configured timelock, e.g. for emergencies
+    #[serde(default)]
+    pub timelock_period: Option<u64>,
+    #[serde(default)]
+    pub template: Option<ProposalTemplate>,
+    #[serde(default)]
+    pub actions: Vec<ProposalAction>,
+    /// Filled in when the proposal is executed, one per action
+    #[serde(default)]
+    pub action_results: Vec<ActionOutcome>,
+}
+
+/// Where the tokens escrowed by quadratic votes go once a proposal is tallied.
+/// If a burn or deposit fails, the proposal falls back to `Refund`.
+#[cw_serde]
+#[derive(Default)]
+pub enum EscrowDisposal {
+    /// Voters reclaim their tokens with `ClaimEscrow`
+    #[default]
+    Refund,
+    Burn,
+    /// Deposited into the treasury
+    Treasury,
+}
+
+/// What happened to a proposal action when it was dispatched.
+#[cw_serde]
+pub enum ActionOutcome {
+    Pending,
+    Succeeded,
+    Failed { error: String },
 }
 
 #[cw_serde]
@@ -44,4 +97,7 @@ pub struct StoredVote {
     pub proposal_id: u64,
     pub vote: VoteOption,
     pub weight: Uint128,
+    /// Tokens still held for a quadratic vote; zero once claimed
+    #[serde(default)]
+    pub escrowed: Uint128,
 }
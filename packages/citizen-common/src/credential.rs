@@ -44,23 +44,94 @@ impl std::fmt::Display for CredentialType {
     }
 }
 
-/// Simplified ZK proof placeholder.
-/// In production, this would contain the actual zero-knowledge proof data
-/// (e.g., Groth16 proof bytes, public inputs, verification key reference).
+/// A zero-knowledge proof as produced by the frontend (`snarkjs.groth16.fullProve`).
 #[cw_serde]
 pub struct ZkProof {
-    /// The proof data (hex-encoded)
+    /// The proof data (JSON-encoded `Groth16Proof`)
     pub proof_data: String,
-    /// Public inputs to the proof circuit
+    /// Public signals of the proof circuit, as decimal field elements
     pub public_inputs: Vec<String>,
     /// Reference to the verification key (stored off-chain or in another contract)
     pub vk_reference: String,
 }
 
+/// Output of `circuits/nullifier.circom`: a tag that is the same every time
+/// one identity registers in a domain, but reveals nothing about the identity.
+#[cw_serde]
+pub struct Nullifier {
+    /// Domain separator the nullifier was derived under, as a decimal field element
+    pub domain: String,
+    /// The nullifier, as a decimal field element
+    pub value: String,
+}
+
+/// Groth16 proof over BN254 in the snarkjs `proof.json` layout.
+/// Points are given as decimal coordinate strings in projective form
+/// (`[x, y, "1"]` for G1, `[[x.c0, x.c1], [y.c0, y.c1], ["1", "0"]]` for G2).
+#[cw_serde]
+pub struct Groth16Proof {
+    pub pi_a: Vec<String>,
+    pub pi_b: Vec<Vec<String>>,
+    pub pi_c: Vec<String>,
+    pub protocol: String,
+    pub curve: String,
+}
+
+/// Groth16 verification key over BN254 in the snarkjs
+/// `verification_key.json` layout.
+#[cw_serde]
+pub struct Groth16VerificationKey {
+    pub protocol: String,
+    pub curve: String,
+    /// Number of public signals the circuit exposes
+    #[serde(rename = "nPublic")]
+    pub n_public: u32,
+    pub vk_alpha_1: Vec<String>,
+    pub vk_beta_2: Vec<Vec<String>>,
+    pub vk_gamma_2: Vec<Vec<String>>,
+    pub vk_delta_2: Vec<Vec<String>>,
+    /// Precomputed e(alpha, beta); accepted for compatibility, not used
+    pub vk_alphabeta_12: Option<Vec<Vec<Vec<String>>>>,
+    /// Public input commitments (`nPublic + 1` G1 points)
+    #[serde(rename = "IC")]
+    pub ic: Vec<Vec<String>>,
+}
+
 /// Verification result from checking a ZK proof
 #[cw_serde]
 pub struct VerificationResult {
     pub valid: bool,
     pub credential_type: CredentialType,
     pub message: String,
+    /// Attribute claims the proof established
+    #[serde(default)]
+    pub claims: Vec<AttributeClaim>,
+}
+
+/// A statement about one attribute of a credential, e.g. `age gte 18`.
+#[cw_serde]
+pub struct AttributeClaim {
+    pub attribute: String,
+    pub op: PredicateOp,
+    /// Right-hand side, as a decimal field element for predicate circuits
+    pub value: String,
+}
+
+#[cw_serde]
+pub enum PredicateOp {
+    Eq,
+    Neq,
+    Gte,
+    Lte,
+}
+
+impl std::fmt::Display for PredicateOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PredicateOp::Eq => write!(f, "eq"),
+            PredicateOp::Neq => write!(f, "neq"),
+            PredicateOp::Gte => write!(f, "gte"),
+            PredicateOp::Lte => write!(f, "lte"),
+        }
+    }
 }
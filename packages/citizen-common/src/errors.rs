@@ -21,9 +21,63 @@ pub enum ContractError {
     #[error("Credential revoked")]
     CredentialRevoked,
 
+    #[error("Credential superseded by {successor}")]
+    CredentialSuperseded { successor: String },
+
     #[error("Invalid ZK proof: {reason}")]
     InvalidProof { reason: String },
 
+    #[error("Invalid presentation: {reason}")]
+    InvalidPresentation { reason: String },
+
+    #[error("Verification key not found: {reference}")]
+    VerificationKeyNotFound { reference: String },
+
+    #[error("Verification key already registered: {reference}")]
+    VerificationKeyAlreadyExists { reference: String },
+
+    #[error("Verification key deprecated: {reference}")]
+    VerificationKeyDeprecated { reference: String },
+
+    #[error("Credential schema not registered: {name}")]
+    CredentialSchemaNotFound { name: String },
+
+    #[error("Credential schema already registered: {name}")]
+    CredentialSchemaAlreadyExists { name: String },
+
+    #[error("Nullifier already used in domain {domain}")]
+    NullifierAlreadyUsed { domain: String },
+
+    #[error("Verification request not found: {request_id}")]
+    VerificationRequestNotFound { request_id: String },
+
+    #[error("Verification request expired: {request_id}")]
+    VerificationRequestExpired { request_id: String },
+
+    #[error("Verification request already closed: {request_id}")]
+    VerificationRequestClosed { request_id: String },
+
+    #[error("Verification request already exists: {request_id}")]
+    VerificationRequestAlreadyExists { request_id: String },
+
+    #[error("Verification request has not expired: {request_id}")]
+    VerificationRequestNotExpired { request_id: String },
+
+    #[error("Invalid verification request: {reason}")]
+    InvalidVerificationRequest { reason: String },
+
+    #[error("Recovery not found: {recovery_id}")]
+    RecoveryNotFound { recovery_id: String },
+
+    #[error("Relayer already attested to request {request_id}")]
+    AlreadyAttested { request_id: String },
+
+    #[error("Invalid threshold: {reason}")]
+    InvalidThreshold { reason: String },
+
+    #[error("Credential type implication {from} => {to} would create a cycle")]
+    ImplicationCycle { from: String, to: String },
+
     #[error("Issuer not authorized: {issuer}")]
     IssuerNotAuthorized { issuer: String },
 
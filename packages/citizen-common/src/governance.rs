@@ -1,5 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
+
+use crate::treasury::FundCategory;
 
 /// Proposal status lifecycle
 #[cw_serde]
@@ -14,12 +16,50 @@ pub enum ProposalStatus {
     Rejected,
     /// Passed and executed on-chain
     Executed,
+    /// Executed, but at least one of its actions failed
+    ExecutionFailed,
     /// Cancelled by proposer or governance
     Cancelled,
     /// Expired without reaching quorum
     Expired,
 }
 
+/// An on-chain effect of a proposal, dispatched when it is executed.
+#[cw_serde]
+pub enum ProposalAction {
+    /// Spend from the treasury the voting contract is configured with
+    TreasurySpend {
+        recipient: String,
+        amount: Uint128,
+        category: FundCategory,
+        memo: String,
+    },
+    /// Change the voting contract's own defaults
+    UpdateVotingConfig {
+        voting_period: Option<u64>,
+        quorum_bps: Option<u64>,
+        threshold_bps: Option<u64>,
+        timelock_period: Option<u64>,
+    },
+    /// Approve a pending grant, with the proposal as its mandate
+    ApproveGrant {
+        grants_contract: String,
+        grant_id: u64,
+    },
+    /// Execute any contract as the voting contract
+    WasmExecute {
+        contract_addr: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    },
+    /// Migrate a contract the voting contract is admin of
+    WasmMigrate {
+        contract_addr: String,
+        new_code_id: u64,
+        msg: Binary,
+    },
+}
+
 /// Types of voting supported
 #[cw_serde]
 pub enum VotingMethod {
@@ -0,0 +1,52 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, CosmosMsg, StdResult, WasmMsg};
+
+use crate::credential::CredentialType;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Credential Hooks – lifecycle notifications from the credential registry
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Credential lifecycle event sent to every contract subscribed to the registry.
+#[cw_serde]
+pub enum CredentialHookMsg {
+    Issued {
+        credential_id: String,
+        holder: String,
+        credential_type: CredentialType,
+    },
+    Revoked {
+        credential_id: String,
+        holder: String,
+        credential_type: CredentialType,
+    },
+    /// Key recovery moved all of `old_holder`'s credentials to `new_holder`
+    Migrated {
+        old_holder: String,
+        new_holder: String,
+    },
+    Expired {
+        credential_id: String,
+        holder: String,
+        credential_type: CredentialType,
+    },
+}
+
+/// Execute message subscribers receive. Their own `ExecuteMsg` needs a
+/// matching `CredentialHook(CredentialHookMsg)` variant.
+#[cw_serde]
+pub enum CredentialHookExecuteMsg {
+    CredentialHook(CredentialHookMsg),
+}
+
+impl CredentialHookMsg {
+    /// Wrap the event into a message executing `contract_addr`'s hook handler.
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: to_json_binary(&CredentialHookExecuteMsg::CredentialHook(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
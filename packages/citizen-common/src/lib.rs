@@ -1,6 +1,9 @@
 pub mod credential;
 pub mod errors;
 pub mod governance;
+pub mod hooks;
+pub mod registry;
 pub mod templates;
 pub mod treasury;
+pub mod vc;
 pub mod verification;
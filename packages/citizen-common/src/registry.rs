@@ -0,0 +1,170 @@
+use cosmwasm_schema::cw_serde;
+#[cfg(any(test, feature = "mock"))]
+use cosmwasm_std::{
+    from_json, to_json_binary, Binary, ContractResult, QuerierResult, SystemError, SystemResult,
+    WasmQuery,
+};
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+
+use crate::credential::CredentialType;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Credential Registry – typed queries for contracts gating on credentials
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The registry queries other contracts rely on. Serialises the same as the
+/// matching variants of the registry's own `QueryMsg`.
+#[cw_serde]
+pub enum RegistryQueryMsg {
+    HasValidCredential {
+        holder: String,
+        credential_type: CredentialType,
+        requester: Option<String>,
+    },
+    CountValidCredentials {
+        credential_type: CredentialType,
+    },
+}
+
+/// Issuer management messages governance sends the registry. Serialises the
+/// same as the matching variants of the registry's own `ExecuteMsg`; adding
+/// an issuer this way trusts it for every credential type.
+#[cw_serde]
+pub enum RegistryExecuteMsg {
+    AddIssuer { issuer: String },
+    RemoveIssuer { issuer: String },
+}
+
+#[cw_serde]
+pub struct HasCredentialResponse {
+    pub has_credential: bool,
+    pub credential_id: Option<String>,
+    /// Stable across renewals; key per-identity records such as votes on this
+    pub lineage_id: Option<String>,
+    /// Type of the satisfying credential; differs from the queried type when
+    /// the match came through an implication
+    pub credential_type: Option<CredentialType>,
+}
+
+#[cw_serde]
+pub struct CredentialCountResponse {
+    pub credential_type: CredentialType,
+    pub count: u64,
+}
+
+/// A deployed credential registry. Failed queries come back as errors, so a
+/// misconfigured or unreachable registry never reads as "has credential".
+#[derive(Clone, Debug, PartialEq)]
+pub struct CredentialRegistry(pub Addr);
+
+impl CredentialRegistry {
+    pub fn addr(&self) -> &Addr {
+        &self.0
+    }
+
+    /// Whether `holder` has a current credential of `credential_type`,
+    /// private ones included. Contracts pass their own address as `requester`
+    /// so holders can let them see private credential ids.
+    pub fn has_valid_credential(
+        &self,
+        querier: &QuerierWrapper,
+        holder: &Addr,
+        credential_type: CredentialType,
+        requester: Option<&Addr>,
+    ) -> StdResult<HasCredentialResponse> {
+        querier.query_wasm_smart(
+            &self.0,
+            &RegistryQueryMsg::HasValidCredential {
+                holder: holder.to_string(),
+                credential_type,
+                requester: requester.map(Addr::to_string),
+            },
+        )
+    }
+
+    pub fn count_valid_credentials(
+        &self,
+        querier: &QuerierWrapper,
+        credential_type: CredentialType,
+    ) -> StdResult<u64> {
+        let res: CredentialCountResponse = querier.query_wasm_smart(
+            &self.0,
+            &RegistryQueryMsg::CountValidCredentials { credential_type },
+        )?;
+        Ok(res.count)
+    }
+}
+
+/// Stands in for the registry in unit tests, e.g. through
+/// `MockQuerier::update_wasm(move |q| registry.handle(q))`. Every listed
+/// holder has one credential per type, whose lineage is the holder's address.
+#[cfg(any(test, feature = "mock"))]
+#[derive(Clone, Debug, Default)]
+pub struct MockRegistry {
+    pub addr: String,
+    pub credentials: Vec<(String, CredentialType)>,
+}
+
+#[cfg(any(test, feature = "mock"))]
+impl MockRegistry {
+    pub fn new(addr: impl Into<String>) -> Self {
+        MockRegistry {
+            addr: addr.into(),
+            credentials: vec![],
+        }
+    }
+
+    pub fn with_credential(mut self, holder: impl Into<String>, ty: CredentialType) -> Self {
+        self.credentials.push((holder.into(), ty));
+        self
+    }
+
+    pub fn handle(&self, query: &WasmQuery) -> QuerierResult {
+        let (contract_addr, msg) = match query {
+            WasmQuery::Smart { contract_addr, msg } => (contract_addr, msg),
+            _ => {
+                return SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: "non-smart wasm query".to_string(),
+                })
+            }
+        };
+        if *contract_addr != self.addr {
+            return SystemResult::Err(SystemError::NoSuchContract {
+                addr: contract_addr.clone(),
+            });
+        }
+        SystemResult::Ok(ContractResult::from(self.answer(msg)))
+    }
+
+    fn answer(&self, msg: &Binary) -> StdResult<Binary> {
+        match from_json(msg)? {
+            RegistryQueryMsg::HasValidCredential {
+                holder,
+                credential_type,
+                ..
+            } => {
+                let held = self
+                    .credentials
+                    .iter()
+                    .any(|(h, t)| *h == holder && *t == credential_type);
+                to_json_binary(&HasCredentialResponse {
+                    has_credential: held,
+                    credential_id: held.then(|| format!("{}-{}", holder, credential_type)),
+                    lineage_id: held.then(|| holder.clone()),
+                    credential_type: held.then_some(credential_type),
+                })
+            }
+            RegistryQueryMsg::CountValidCredentials { credential_type } => {
+                let count = self
+                    .credentials
+                    .iter()
+                    .filter(|(_, t)| *t == credential_type)
+                    .count() as u64;
+                to_json_binary(&CredentialCountResponse {
+                    credential_type,
+                    count,
+                })
+            }
+        }
+    }
+}
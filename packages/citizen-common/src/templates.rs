@@ -1,6 +1,7 @@
 use crate::governance::VotingMethod;
+use crate::treasury::FundCategory;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{StdError, StdResult, Uint128};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Governance Proposal Templates – pre-built proposal types for common actions
@@ -75,7 +76,105 @@ pub struct TemplateMetadata {
     pub elevated_quorum: bool,
 }
 
+/// Minimum voting period of templates that change how governance itself works,
+/// about a day at 6s blocks
+pub const GOVERNANCE_CHANGE_MIN_PERIOD: u64 = 14_400;
+
 impl ProposalTemplate {
+    /// Check the template's fields, short of validating addresses.
+    pub fn validate(&self) -> StdResult<()> {
+        let require = |ok: bool, what: &str| {
+            if ok {
+                Ok(())
+            } else {
+                Err(StdError::generic_err(format!(
+                    "{} template: {}",
+                    self.label(),
+                    what
+                )))
+            }
+        };
+        match self {
+            ProposalTemplate::TreasurySpend {
+                recipient,
+                amount,
+                denom,
+                category,
+                ..
+            } => {
+                require(!recipient.is_empty(), "recipient is required")?;
+                require(!amount.is_zero(), "amount must be positive")?;
+                require(!denom.is_empty(), "denom is required")?;
+                category.parse::<FundCategory>()?;
+                Ok(())
+            }
+            ProposalTemplate::ParameterChange {
+                parameter,
+                new_value,
+                rationale,
+            } => {
+                match parameter {
+                    GovernanceParameter::VotingPeriod => {
+                        require(*new_value > 0, "voting period must be positive")?
+                    }
+                    GovernanceParameter::QuorumBps | GovernanceParameter::ThresholdBps => {
+                        require(*new_value <= 10_000, "basis points cannot exceed 10000")?
+                    }
+                    GovernanceParameter::TimelockPeriod => {}
+                }
+                require(!rationale.is_empty(), "rationale is required")
+            }
+            ProposalTemplate::GrantFunding {
+                total_funding,
+                denom,
+                ..
+            } => {
+                require(!total_funding.is_zero(), "funding must be positive")?;
+                require(!denom.is_empty(), "denom is required")
+            }
+            ProposalTemplate::IssuerManagement {
+                issuer_address,
+                justification,
+                ..
+            } => {
+                require(!issuer_address.is_empty(), "issuer address is required")?;
+                require(!justification.is_empty(), "justification is required")
+            }
+            ProposalTemplate::EmissionUpdate { rationale, .. } => {
+                require(!rationale.is_empty(), "rationale is required")
+            }
+            ProposalTemplate::Emergency { action, .. } => {
+                require(!action.is_empty(), "action is required")
+            }
+            ProposalTemplate::TextProposal { summary } => {
+                require(!summary.is_empty(), "summary is required")
+            }
+        }
+    }
+
+    /// How proposals of this kind should be voted on.
+    pub fn metadata(&self) -> TemplateMetadata {
+        let (suggested_voting_method, min_voting_period, elevated_quorum) = match self {
+            ProposalTemplate::ParameterChange { .. }
+            | ProposalTemplate::IssuerManagement { .. }
+            | ProposalTemplate::EmissionUpdate { .. } => (
+                VotingMethod::OnePersonOneVote,
+                GOVERNANCE_CHANGE_MIN_PERIOD,
+                false,
+            ),
+            ProposalTemplate::Emergency { .. } => (VotingMethod::OnePersonOneVote, 0, true),
+            ProposalTemplate::TreasurySpend { .. }
+            | ProposalTemplate::GrantFunding { .. }
+            | ProposalTemplate::TextProposal { .. } => (VotingMethod::OnePersonOneVote, 0, false),
+        };
+        TemplateMetadata {
+            template: self.clone(),
+            suggested_voting_method,
+            min_voting_period,
+            elevated_quorum,
+        }
+    }
+
     /// Human-readable label for the template type.
     pub fn label(&self) -> &str {
         match self {
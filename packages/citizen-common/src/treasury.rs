@@ -1,5 +1,41 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Uint128};
+use cosmwasm_std::{Addr, Coin, StdError, Uint128};
+
+/// The treasury messages other contracts send. Serialises the same as the
+/// matching variants of the treasury's own `ExecuteMsg`.
+#[cw_serde]
+pub enum TreasuryExecuteMsg {
+    Deposit {},
+    Spend {
+        recipient: String,
+        amount: Uint128,
+        category: FundCategory,
+        memo: String,
+    },
+}
+
+/// The grants messages governance sends. Serialises the same as the matching
+/// variants of the grants contract's own `ExecuteMsg`.
+#[cw_serde]
+pub enum GrantsExecuteMsg {
+    Approve { grant_id: u64, proposal_id: u64 },
+}
+
+/// The treasury queries other contracts rely on. Serialises the same as the
+/// matching variants of the treasury's own `QueryMsg`.
+#[cw_serde]
+pub enum TreasuryQueryMsg {
+    Config {},
+}
+
+#[cw_serde]
+pub struct TreasuryConfigResponse {
+    pub admin: String,
+    pub governance_contract: String,
+    pub denom: String,
+    pub total_deposited: Uint128,
+    pub total_spent: Uint128,
+}
 
 /// Treasury fund allocation categories
 #[cw_serde]
@@ -27,6 +63,31 @@ impl std::fmt::Display for FundCategory {
     }
 }
 
+impl std::str::FromStr for FundCategory {
+    type Err = StdError;
+
+    /// Inverse of `Display`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "research" => FundCategory::Research,
+            "healthcare" => FundCategory::Healthcare,
+            "infrastructure" => FundCategory::Infrastructure,
+            "education" => FundCategory::Education,
+            "emergency" => FundCategory::Emergency,
+            "node_incentives" => FundCategory::NodeIncentives,
+            _ => match s.strip_prefix("custom:") {
+                Some(name) if !name.is_empty() => FundCategory::Custom(name.to_string()),
+                _ => {
+                    return Err(StdError::generic_err(format!(
+                        "Unknown fund category: {}",
+                        s
+                    )))
+                }
+            },
+        })
+    }
+}
+
 /// A grant application
 #[cw_serde]
 pub struct GrantApplication {
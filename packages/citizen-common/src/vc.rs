@@ -0,0 +1,250 @@
+use cosmwasm_std::{Addr, StdError, StdResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::credential::{Credential, CredentialType};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// W3C Verifiable Credentials – conversion to and from the VC data model
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Holders and issuers are identified as `did:cosmos:<bech32 address>`.
+// Credential ids become `urn:citizen-ledger:credential:<id>`, and the registry
+// type goes into `credentialSubject.credentialType` in its `Display` form.
+// Timestamps are RFC 3339 in UTC, whole seconds.
+
+pub const VC_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+pub const VC_TYPE: &str = "VerifiableCredential";
+pub const CITIZEN_CREDENTIAL_TYPE: &str = "CitizenLedgerCredential";
+pub const DID_PREFIX: &str = "did:cosmos:";
+pub const CREDENTIAL_URN_PREFIX: &str = "urn:citizen-ledger:credential:";
+
+/// A credential in the W3C VC data model. Unknown members are ignored on import.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    pub issuer: String,
+    #[serde(rename = "issuanceDate")]
+    pub issuance_date: String,
+    #[serde(
+        rename = "expirationDate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expiration_date: Option<String>,
+    #[serde(rename = "credentialSubject")]
+    pub credential_subject: CredentialSubject,
+    #[serde(
+        rename = "credentialStatus",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub credential_status: Option<CredentialStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<VcProof>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CredentialSubject {
+    /// Holder DID
+    pub id: String,
+    #[serde(rename = "credentialType")]
+    pub credential_type: String,
+    /// The credential's ZK commitment
+    pub commitment: String,
+}
+
+/// Where a verifier can check whether the credential is still valid.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CredentialStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+    #[serde(
+        rename = "statusListIndex",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_list_index: Option<String>,
+    pub revoked: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VcProof {
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    pub created: String,
+    #[serde(rename = "proofPurpose")]
+    pub proof_purpose: String,
+    #[serde(rename = "verificationMethod")]
+    pub verification_method: String,
+    #[serde(
+        rename = "proofValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub proof_value: Option<String>,
+}
+
+pub fn address_to_did(address: &Addr) -> String {
+    format!("{}{}", DID_PREFIX, address)
+}
+
+/// The bech32 address inside a `did:cosmos:` identifier.
+pub fn did_to_address(did: &str) -> StdResult<String> {
+    did.strip_prefix(DID_PREFIX)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| StdError::generic_err(format!("Unsupported DID: {}", did)))
+}
+
+/// Parse a credential type from its `Display` form.
+pub fn parse_credential_type(s: &str) -> StdResult<CredentialType> {
+    Ok(match s {
+        "citizenship" => CredentialType::Citizenship,
+        "residency" => CredentialType::Residency,
+        "age_over_18" => CredentialType::AgeOver18,
+        "healthcare_eligibility" => CredentialType::HealthcareEligibility,
+        _ => match s.strip_prefix("custom:") {
+            Some(name) if !name.is_empty() => CredentialType::Custom(name.to_string()),
+            _ => {
+                return Err(StdError::generic_err(format!(
+                    "Unknown credential type: {}",
+                    s
+                )))
+            }
+        },
+    })
+}
+
+/// Express a credential in the VC data model, without status or proof.
+pub fn to_verifiable_credential(cred: &Credential) -> VerifiableCredential {
+    VerifiableCredential {
+        context: vec![VC_CONTEXT.to_string()],
+        id: format!("{}{}", CREDENTIAL_URN_PREFIX, cred.id),
+        types: vec![VC_TYPE.to_string(), CITIZEN_CREDENTIAL_TYPE.to_string()],
+        issuer: address_to_did(&cred.issuer),
+        issuance_date: format_timestamp(cred.issued_at),
+        expiration_date: (cred.expires_at > 0).then(|| format_timestamp(cred.expires_at)),
+        credential_subject: CredentialSubject {
+            id: address_to_did(&cred.holder),
+            credential_type: cred.credential_type.to_string(),
+            commitment: cred.commitment.clone(),
+        },
+        credential_status: None,
+        proof: None,
+    }
+}
+
+/// Read a credential back from the VC data model. Addresses are taken from
+/// the DIDs as-is; callers should validate them. A `credentialStatus` marks
+/// the credential revoked if it says so.
+pub fn from_verifiable_credential(vc: &VerifiableCredential) -> StdResult<Credential> {
+    if !vc.types.iter().any(|t| t == VC_TYPE) {
+        return Err(StdError::generic_err("Not a VerifiableCredential"));
+    }
+    Ok(Credential {
+        id: vc
+            .id
+            .strip_prefix(CREDENTIAL_URN_PREFIX)
+            .unwrap_or(&vc.id)
+            .to_string(),
+        holder: Addr::unchecked(did_to_address(&vc.credential_subject.id)?),
+        credential_type: parse_credential_type(&vc.credential_subject.credential_type)?,
+        commitment: vc.credential_subject.commitment.clone(),
+        issuer: Addr::unchecked(did_to_address(&vc.issuer)?),
+        issued_at: parse_timestamp(&vc.issuance_date)?,
+        expires_at: vc
+            .expiration_date
+            .as_deref()
+            .map(parse_timestamp)
+            .transpose()?
+            .unwrap_or(0),
+        revoked: vc.credential_status.as_ref().is_some_and(|s| s.revoked),
+    })
+}
+
+/// Unix seconds as `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Parse an RFC 3339 UTC timestamp (`Z` suffix, optional fractional seconds,
+/// which are dropped) into unix seconds.
+pub fn parse_timestamp(s: &str) -> StdResult<u64> {
+    let invalid = || StdError::generic_err(format!("Invalid timestamp: {}", s));
+    let body = s.strip_suffix('Z').ok_or_else(invalid)?;
+    let body = body.split('.').next().unwrap_or(body);
+    let bytes = body.as_bytes();
+    if bytes.len() != 19
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || bytes[10] != b'T'
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return Err(invalid());
+    }
+    let field = |range: std::ops::Range<usize>| -> StdResult<i64> {
+        body[range]
+            .parse::<u32>()
+            .map(i64::from)
+            .map_err(|_| invalid())
+    };
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid());
+    }
+    let days = days_from_civil(year, month, day);
+    if days < 0 || civil_from_days(days) != (year, month, day) {
+        return Err(invalid());
+    }
+    Ok(days as u64 * 86_400 + (hour * 3600 + minute * 60 + second) as u64)
+}
+
+// Proleptic Gregorian calendar conversions, after Howard Hinnant's
+// `days_from_civil` / `civil_from_days`.
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -57,7 +57,8 @@ pub enum VerificationStatus {
 pub struct VerificationConfig {
     /// Base URL for the identity verification API
     pub api_endpoint: String,
-    /// Authorized relayer addresses that can submit verification results on-chain
+    /// Authorized relayer addresses that can submit verification results on-chain.
+    /// Reported by the registry; relayers are added with `AddRelayer`, not here.
     pub authorized_relayers: Vec<String>,
     /// Minimum number of social vouches required (for SocialVouching type)
     pub min_social_vouches: u32,
@@ -3,28 +3,21 @@
 // ─────────────────────────────────────────────────────────────────────────────
 #![allow(dead_code)]
 
-use cosmwasm_std::{Addr, Coin, Uint128};
-use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+use sha2::{Digest, Sha256};
 
 use citizen_common::treasury::FundCategory;
 
 // ── Contract wrappers ───────────────────────────────────────────────────────
 
-pub fn credential_registry_contract() -> Box<
-    ContractWrapper<
-        credential_registry::msg::ExecuteMsg,
-        credential_registry::msg::InstantiateMsg,
-        credential_registry::msg::QueryMsg,
-        citizen_common::errors::ContractError,
-        citizen_common::errors::ContractError,
-        cosmwasm_std::StdError,
-    >,
-> {
+pub fn credential_registry_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         credential_registry::contract::execute,
         credential_registry::contract::instantiate,
         credential_registry::contract::query,
-    );
+    )
+    .with_reply(credential_registry::contract::reply);
     Box::new(contract)
 }
 
@@ -46,21 +39,13 @@ pub fn treasury_contract() -> Box<
     Box::new(contract)
 }
 
-pub fn voting_contract() -> Box<
-    ContractWrapper<
-        voting::msg::ExecuteMsg,
-        voting::msg::InstantiateMsg,
-        voting::msg::QueryMsg,
-        citizen_common::errors::ContractError,
-        citizen_common::errors::ContractError,
-        cosmwasm_std::StdError,
-    >,
-> {
+pub fn voting_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         voting::contract::execute,
         voting::contract::instantiate,
         voting::contract::query,
-    );
+    )
+    .with_reply(voting::contract::reply);
     Box::new(contract)
 }
 
@@ -173,6 +158,7 @@ pub fn deploy_full_system() -> (App, DeployedSystem) {
             &credential_registry::msg::InstantiateMsg {
                 admin: admin.to_string(),
                 issuers: vec![admin.to_string()],
+                verification_config: None,
             },
             &[],
             "credential-registry",
@@ -216,6 +202,10 @@ pub fn deploy_full_system() -> (App, DeployedSystem) {
                 quorum_bps: 1000,    // 10%
                 threshold_bps: 5000, // 50%
                 timelock_period: 0,  // no timelock for integration tests
+                emergency_timelock_period: None,
+                elevated_quorum_bps: None,
+                quadratic_denom: None,
+                escrow_disposal: None,
             },
             &[],
             "voting",
@@ -250,6 +240,24 @@ pub fn deploy_full_system() -> (App, DeployedSystem) {
         )
         .unwrap();
 
+    // ── 5b. Point grant funding proposals at the grants contract ────────
+    app.execute_contract(
+        admin.clone(),
+        voting_addr.clone(),
+        &voting::msg::ExecuteMsg::UpdateConfig {
+            voting_period: None,
+            quorum_bps: None,
+            threshold_bps: None,
+            timelock_period: None,
+            emergency_timelock_period: None,
+            elevated_quorum_bps: None,
+            grants_contract: Some(grants_addr.to_string()),
+            escrow_disposal: None,
+        },
+        &[],
+    )
+    .unwrap();
+
     // ── 6. Staking Emissions (points to treasury) ───────────────────────
     let staking_addr = app
         .instantiate_contract(
@@ -269,6 +277,7 @@ pub fn deploy_full_system() -> (App, DeployedSystem) {
                 treasury: treasury_addr.to_string(),
                 treasury_share_bps: 2000, // 20%
                 slash_penalty_bps: 1000,  // 10%
+                difficulty_config: None,
             },
             &[],
             "staking-emissions",
@@ -302,6 +311,8 @@ pub fn issue_citizenship(app: &mut App, system: &DeployedSystem, holder: &Addr)
                 credential_type: citizen_common::credential::CredentialType::Citizenship,
                 commitment: "test_commitment_hash_for_integration".to_string(),
                 expires_at: 0, // no expiry
+                nullifier: test_nullifier(holder),
+                attributes_root: None,
             },
             &[],
         )
@@ -316,6 +327,17 @@ pub fn issue_citizenship(app: &mut App, system: &DeployedSystem, holder: &Addr)
         .unwrap_or_default()
 }
 
+/// Derive a distinct, stable identity nullifier for a test holder
+pub fn test_nullifier(holder: &Addr) -> citizen_common::credential::Nullifier {
+    let digest = Sha256::digest(holder.as_bytes());
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&digest[..8]);
+    citizen_common::credential::Nullifier {
+        domain: "1".to_string(),
+        value: u64::from_be_bytes(value).to_string(),
+    }
+}
+
 /// Fund the treasury with ucitizen from admin
 pub fn fund_treasury(app: &mut App, system: &DeployedSystem, amount: u128) {
     app.execute_contract(
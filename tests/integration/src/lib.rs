@@ -1,5 +1,7 @@
 mod helpers;
+mod test_credential_hooks;
 mod test_credential_to_voting;
 mod test_full_flow;
 mod test_grants_lifecycle;
+mod test_proposal_execution;
 mod test_treasury_governance;
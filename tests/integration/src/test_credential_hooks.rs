@@ -0,0 +1,213 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Integration: Credential Registry hooks → Grants (revocation cancels applications)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::*;
+    use citizen_common::treasury::FundCategory;
+    use cosmwasm_std::Uint128;
+    use cw_multi_test::{App, Executor};
+
+    fn add_hook(app: &mut App, system: &DeployedSystem, contract: &cosmwasm_std::Addr) {
+        app.execute_contract(
+            system.admin.clone(),
+            system.credential_registry.clone(),
+            &credential_registry::msg::ExecuteMsg::AddHook {
+                contract: contract.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn apply_for_grant(app: &mut App, system: &DeployedSystem) {
+        app.execute_contract(
+            system.citizen1.clone(),
+            system.grants.clone(),
+            &grants::msg::ExecuteMsg::Apply {
+                title: "Community library".to_string(),
+                description: "Books for district 2".to_string(),
+                category: FundCategory::Education,
+                milestones: vec![grants::msg::MilestoneInput {
+                    description: "Purchase books".to_string(),
+                    amount: Uint128::new(100_000),
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn grant_status(app: &App, system: &DeployedSystem) -> String {
+        let grant: grants::msg::GrantResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.grants.clone(),
+                &grants::msg::QueryMsg::GetGrant { grant_id: 1 },
+            )
+            .unwrap();
+        grant.status
+    }
+
+    #[test]
+    fn revocation_cancels_pending_grant_applications() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        let cred_id = issue_citizenship(&mut app, &system, &c1);
+
+        app.execute_contract(
+            system.admin.clone(),
+            system.grants.clone(),
+            &grants::msg::ExecuteMsg::SetCredentialRegistry {
+                registry: system.credential_registry.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        add_hook(&mut app, &system, &system.grants);
+        apply_for_grant(&mut app, &system);
+
+        // Only the registry may deliver hooks
+        let err = app
+            .execute_contract(
+                c1.clone(),
+                system.grants.clone(),
+                &grants::msg::ExecuteMsg::CredentialHook(
+                    citizen_common::hooks::CredentialHookMsg::Revoked {
+                        credential_id: cred_id.clone(),
+                        holder: c1.to_string(),
+                        credential_type: citizen_common::credential::CredentialType::Citizenship,
+                    },
+                ),
+                &[],
+            )
+            .unwrap_err();
+        assert!(err.root_cause().to_string().contains("Unauthorized"));
+        assert_eq!(grant_status(&app, &system), "Pending");
+
+        app.execute_contract(
+            system.admin.clone(),
+            system.credential_registry.clone(),
+            &credential_registry::msg::ExecuteMsg::RevokeCredential {
+                credential_id: cred_id,
+                reason: "Fraudulent documents".to_string(),
+                fraud: false,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(grant_status(&app, &system), "Cancelled");
+    }
+
+    #[test]
+    fn failing_hook_does_not_block_revocation() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        let cred_id = issue_citizenship(&mut app, &system, &c1);
+
+        // The treasury has no hook handler, so every notification to it fails
+        add_hook(&mut app, &system, &system.treasury);
+
+        let res = app
+            .execute_contract(
+                system.admin.clone(),
+                system.credential_registry.clone(),
+                &credential_registry::msg::ExecuteMsg::RevokeCredential {
+                    credential_id: cred_id.clone(),
+                    reason: "Fraudulent documents".to_string(),
+                    fraud: false,
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .flat_map(|e| e.attributes.iter())
+            .any(|a| a.key == "action" && a.value == "credential_hook_failed"));
+
+        let cred: credential_registry::msg::CredentialResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.credential_registry.clone(),
+                &credential_registry::msg::QueryMsg::GetCredential {
+                    credential_id: cred_id,
+                    view_token: None,
+                },
+            )
+            .unwrap();
+        assert!(cred.revoked);
+    }
+
+    #[test]
+    fn private_citizenship_keeps_votes_and_grants() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        let cred_id = issue_citizenship(&mut app, &system, &c1);
+        app.execute_contract(
+            system.admin.clone(),
+            system.grants.clone(),
+            &grants::msg::ExecuteMsg::SetCredentialRegistry {
+                registry: system.credential_registry.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        add_hook(&mut app, &system, &system.grants);
+
+        // Hiding the credential from list queries leaves it counting everywhere
+        app.execute_contract(
+            c1.clone(),
+            system.credential_registry.clone(),
+            &credential_registry::msg::ExecuteMsg::SetCredentialPrivacy {
+                credential_type: citizen_common::credential::CredentialType::Citizenship,
+                private: true,
+            },
+            &[],
+        )
+        .unwrap();
+        apply_for_grant(&mut app, &system);
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CreateProposal {
+                title: "Repave main street".to_string(),
+                description: "From the station to the market".to_string(),
+                voting_method: citizen_common::governance::VotingMethod::OnePersonOneVote,
+                voting_period: Some(50),
+                actions: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CastVote {
+                proposal_id: 1,
+                vote: citizen_common::governance::VoteOption::Yes,
+                tokens: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // A revocation notice while the holder is still a citizen leaves the grant pending
+        app.execute_contract(
+            system.credential_registry.clone(),
+            system.grants.clone(),
+            &grants::msg::ExecuteMsg::CredentialHook(
+                citizen_common::hooks::CredentialHookMsg::Revoked {
+                    credential_id: format!("{}-old", cred_id),
+                    holder: c1.to_string(),
+                    credential_type: citizen_common::credential::CredentialType::Citizenship,
+                },
+            ),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(grant_status(&app, &system), "Pending");
+    }
+}
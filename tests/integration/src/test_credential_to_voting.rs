@@ -5,8 +5,9 @@
 #[cfg(test)]
 mod tests {
     use crate::helpers::*;
+    use citizen_common::errors::ContractError;
     use citizen_common::governance::{VoteOption, VotingMethod};
-    use cosmwasm_std::Uint128;
+    use cosmwasm_std::{coins, Uint128};
     use cw_multi_test::Executor;
 
     #[test]
@@ -25,6 +26,7 @@ mod tests {
                 description: "Allocate 1M ucitizen for new community gardens".to_string(),
                 voting_method: VotingMethod::OnePersonOneVote,
                 voting_period: None,
+                actions: None,
             },
             &[],
         );
@@ -66,6 +68,7 @@ mod tests {
                 description: "Construct a school in district 5".to_string(),
                 voting_method: VotingMethod::OnePersonOneVote,
                 voting_period: Some(50), // 50 blocks
+                actions: None,
             },
             &[],
         )
@@ -108,6 +111,8 @@ mod tests {
             .unwrap();
         assert_eq!(proposal.status, "Passed");
         assert_eq!(proposal.votes_for, Uint128::new(3));
+        assert_eq!(proposal.eligible_voters, Some(3));
+        assert_eq!(proposal.turnout_bps, Some(10000));
     }
 
     #[test]
@@ -130,6 +135,7 @@ mod tests {
                 description: "Waste money".to_string(),
                 voting_method: VotingMethod::OnePersonOneVote,
                 voting_period: Some(50),
+                actions: None,
             },
             &[],
         )
@@ -200,11 +206,31 @@ mod tests {
                 description: "Testing quadratic voting weights".to_string(),
                 voting_method: VotingMethod::Quadratic,
                 voting_period: Some(50),
+                actions: None,
             },
             &[],
         )
         .unwrap();
 
+        // Claiming tokens without paying them is refused
+        let err = app
+            .execute_contract(
+                c1.clone(),
+                system.voting.clone(),
+                &voting::msg::ExecuteMsg::CastVote {
+                    proposal_id: 1,
+                    vote: VoteOption::Yes,
+                    tokens: Some(Uint128::new(1_000_000)),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InsufficientFunds { .. }
+        ));
+        let before = app.wrap().query_balance(&c1, "ucitizen").unwrap().amount;
+
         // citizen1 votes Yes with 100 tokens = sqrt(100) = 10 weight
         app.execute_contract(
             c1.clone(),
@@ -214,20 +240,20 @@ mod tests {
                 vote: VoteOption::Yes,
                 tokens: Some(Uint128::new(100)),
             },
-            &[],
+            &coins(100, "ucitizen"),
         )
         .unwrap();
 
-        // citizen2 votes No with 9 tokens = sqrt(9) = 3 weight
+        // citizen2 votes No with 15 tokens = floor(sqrt(15)) = 3 weight
         app.execute_contract(
             c2.clone(),
             system.voting.clone(),
             &voting::msg::ExecuteMsg::CastVote {
                 proposal_id: 1,
                 vote: VoteOption::No,
-                tokens: Some(Uint128::new(9)),
+                tokens: None,
             },
-            &[],
+            &coins(15, "ucitizen"),
         )
         .unwrap();
 
@@ -253,5 +279,302 @@ mod tests {
         assert_eq!(proposal.status, "Passed");
         assert_eq!(proposal.votes_for, Uint128::new(10));
         assert_eq!(proposal.votes_against, Uint128::new(3));
+        assert_eq!(proposal.escrowed, Uint128::new(115));
+
+        // Escrow is refunded by default, once
+        let claim = voting::msg::ExecuteMsg::ClaimEscrow { proposal_id: 1 };
+        app.execute_contract(c1.clone(), system.voting.clone(), &claim, &[])
+            .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(&c1, "ucitizen").unwrap().amount,
+            before
+        );
+        app.execute_contract(c1.clone(), system.voting.clone(), &claim, &[])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn quadratic_escrow_goes_to_treasury_when_configured() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        issue_citizenship(&mut app, &system, &c1);
+        app.execute_contract(
+            system.admin.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::UpdateConfig {
+                voting_period: None,
+                quorum_bps: None,
+                threshold_bps: None,
+                timelock_period: None,
+                emergency_timelock_period: None,
+                elevated_quorum_bps: None,
+                grants_contract: None,
+                escrow_disposal: Some(voting::msg::EscrowDisposal::Treasury),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CreateProposal {
+                title: "Quadratic test".to_string(),
+                description: "Escrow to treasury".to_string(),
+                voting_method: VotingMethod::Quadratic,
+                voting_period: Some(50),
+                actions: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CastVote {
+                proposal_id: 1,
+                vote: VoteOption::Yes,
+                tokens: None,
+            },
+            &coins(400, "ucitizen"),
+        )
+        .unwrap();
+        app.update_block(|b| b.height += 51);
+        app.execute_contract(
+            system.admin.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::TallyProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        let balance: treasury::msg::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.treasury.clone(),
+                &treasury::msg::QueryMsg::Balance {},
+            )
+            .unwrap();
+        assert_eq!(balance.total, Uint128::new(400));
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::ClaimEscrow { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn non_citizen_cannot_propose_or_vote() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        issue_citizenship(&mut app, &system, &c1);
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CreateProposal {
+                title: "Plant trees".to_string(),
+                description: "Along the river".to_string(),
+                voting_method: VotingMethod::OnePersonOneVote,
+                voting_period: Some(50),
+                actions: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // citizen2 holds no credential
+        let err = app
+            .execute_contract(
+                system.citizen2.clone(),
+                system.voting.clone(),
+                &voting::msg::ExecuteMsg::CastVote {
+                    proposal_id: 1,
+                    vote: VoteOption::Yes,
+                    tokens: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            ContractError::NoVerifiedCredential.to_string()
+        );
+        let err = app
+            .execute_contract(
+                system.citizen2.clone(),
+                system.voting.clone(),
+                &voting::msg::ExecuteMsg::CreateProposal {
+                    title: "Cut trees".to_string(),
+                    description: "Along the river".to_string(),
+                    voting_method: VotingMethod::OnePersonOneVote,
+                    voting_period: Some(50),
+                    actions: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            ContractError::NoVerifiedCredential.to_string()
+        );
+    }
+
+    #[test]
+    fn wrong_registry_address_blocks_all_votes() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        issue_citizenship(&mut app, &system, &c1);
+
+        // A voting contract pointed at the treasury instead of the registry
+        let code_id = app.store_code(voting_contract());
+        let misconfigured = app
+            .instantiate_contract(
+                code_id,
+                system.admin.clone(),
+                &voting::msg::InstantiateMsg {
+                    admin: system.admin.to_string(),
+                    credential_registry: system.treasury.to_string(),
+                    treasury_contract: system.treasury.to_string(),
+                    voting_period: 100,
+                    quorum_bps: 1000,
+                    threshold_bps: 5000,
+                    timelock_period: 0,
+                    emergency_timelock_period: None,
+                    elevated_quorum_bps: None,
+                    quadratic_denom: None,
+                    escrow_disposal: None,
+                },
+                &[],
+                "voting-misconfigured",
+                None,
+            )
+            .unwrap();
+
+        // Even a real citizen is turned away, rather than everyone let in
+        let create = app.execute_contract(
+            c1.clone(),
+            misconfigured.clone(),
+            &voting::msg::ExecuteMsg::CreateProposal {
+                title: "Plant trees".to_string(),
+                description: "Along the river".to_string(),
+                voting_method: VotingMethod::OnePersonOneVote,
+                voting_period: Some(50),
+                actions: None,
+            },
+            &[],
+        );
+        assert!(create.is_err());
+        for voter in [&c1, &system.citizen2] {
+            let err = app
+                .execute_contract(
+                    voter.clone(),
+                    misconfigured.clone(),
+                    &voting::msg::ExecuteMsg::CastVote {
+                        proposal_id: 1,
+                        vote: VoteOption::Yes,
+                        tokens: None,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            // Refused at the credential check, before the proposal is looked up
+            assert_ne!(
+                err.root_cause().to_string(),
+                ContractError::ProposalNotFound { id: 1 }.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn recovered_key_cannot_vote_twice() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        let c2 = system.citizen2.clone();
+        let new_wallet = app.api().addr_make("new_wallet");
+        issue_citizenship(&mut app, &system, &c1);
+        issue_citizenship(&mut app, &system, &c2);
+
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CreateProposal {
+                title: "Repave main street".to_string(),
+                description: "From the station to the market".to_string(),
+                voting_method: VotingMethod::OnePersonOneVote,
+                // Open past the recovery timelock
+                voting_period: Some(20_000),
+                actions: None,
+            },
+            &[],
+        )
+        .unwrap();
+        let vote = voting::msg::ExecuteMsg::CastVote {
+            proposal_id: 1,
+            vote: VoteOption::Yes,
+            tokens: None,
+        };
+        app.execute_contract(c1.clone(), system.voting.clone(), &vote, &[])
+            .unwrap();
+
+        // citizen1's credential moves to a new key through guardian recovery
+        app.execute_contract(
+            c1.clone(),
+            system.credential_registry.clone(),
+            &credential_registry::msg::ExecuteMsg::SetGuardians {
+                guardians: vec![c2.to_string()],
+                threshold: 1,
+            },
+            &[],
+        )
+        .unwrap();
+        let res = app
+            .execute_contract(
+                new_wallet.clone(),
+                system.credential_registry.clone(),
+                &credential_registry::msg::ExecuteMsg::RequestKeyRecovery {
+                    old_address: c1.to_string(),
+                    reverification_id: None,
+                },
+                &[],
+            )
+            .unwrap();
+        let recovery_id = res
+            .events
+            .iter()
+            .flat_map(|e| &e.attributes)
+            .find(|a| a.key == "recovery_id")
+            .unwrap()
+            .value
+            .clone();
+        app.execute_contract(
+            system.admin.clone(),
+            system.credential_registry.clone(),
+            &credential_registry::msg::ExecuteMsg::ResolveRecovery {
+                recovery_id: recovery_id.clone(),
+                approve: true,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|b| b.height += 10_080);
+        app.execute_contract(
+            new_wallet.clone(),
+            system.credential_registry.clone(),
+            &credential_registry::msg::ExecuteMsg::ExecuteRecovery { recovery_id },
+            &[],
+        )
+        .unwrap();
+
+        // The new key holds the same credential lineage, which already voted
+        let err = app
+            .execute_contract(new_wallet, system.voting.clone(), &vote, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            ContractError::AlreadyVoted { id: 1 }.to_string()
+        );
+        app.execute_contract(c2, system.voting.clone(), &vote, &[])
+            .unwrap();
     }
 }
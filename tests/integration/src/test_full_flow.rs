@@ -37,6 +37,7 @@ mod tests {
                 &credential_registry::msg::QueryMsg::HasValidCredential {
                     holder: c1.to_string(),
                     credential_type: CredentialType::Citizenship,
+                    requester: None,
                 },
             )
             .unwrap();
@@ -54,6 +55,7 @@ mod tests {
                 description: "Upgrade district 3 water treatment".to_string(),
                 voting_method: VotingMethod::OnePersonOneVote,
                 voting_period: Some(100),
+                actions: None,
             },
             &[],
         )
@@ -165,6 +167,7 @@ mod tests {
                 system.credential_registry.clone(),
                 &credential_registry::msg::QueryMsg::GetCredential {
                     credential_id: cred_id.clone(),
+                    view_token: None,
                 },
             )
             .unwrap();
@@ -177,6 +180,7 @@ mod tests {
             &credential_registry::msg::ExecuteMsg::RevokeCredential {
                 credential_id: cred_id.clone(),
                 reason: "Fraudulent documentation".to_string(),
+                fraud: true,
             },
             &[],
         )
@@ -189,6 +193,7 @@ mod tests {
                 system.credential_registry.clone(),
                 &credential_registry::msg::QueryMsg::GetCredential {
                     credential_id: cred_id,
+                    view_token: None,
                 },
             )
             .unwrap();
@@ -0,0 +1,249 @@
+// ─────────────────────────────────────────────────────────────────────────────
+// Integration: Passed proposals dispatch their actions on execution
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::*;
+    use citizen_common::governance::{ProposalAction, VoteOption, VotingMethod};
+    use citizen_common::treasury::FundCategory;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::{App, Executor};
+    use voting::msg::ActionOutcome;
+
+    /// Create a proposal carrying `actions`, pass it and execute it
+    fn pass_and_execute(
+        app: &mut App,
+        system: &DeployedSystem,
+        actions: Vec<ProposalAction>,
+    ) -> voting::msg::ProposalResponse {
+        let c1 = system.citizen1.clone();
+        issue_citizenship(app, system, &c1);
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CreateProposal {
+                title: "Community works".to_string(),
+                description: "Fund and configure".to_string(),
+                voting_method: VotingMethod::OnePersonOneVote,
+                voting_period: Some(10),
+                actions: Some(actions),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CastVote {
+                proposal_id: 1,
+                vote: VoteOption::Yes,
+                tokens: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|b| b.height += 11);
+        app.execute_contract(
+            system.admin.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::TallyProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            system.admin.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+            &[],
+        )
+        .unwrap();
+
+        app.wrap()
+            .query_wasm_smart(
+                system.voting.clone(),
+                &voting::msg::QueryMsg::GetProposal { proposal_id: 1 },
+            )
+            .unwrap()
+    }
+
+    fn spend(recipient: &Addr, amount: u128) -> ProposalAction {
+        ProposalAction::TreasurySpend {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+            category: FundCategory::Infrastructure,
+            memo: "Road repair".to_string(),
+        }
+    }
+
+    #[test]
+    fn executed_proposal_spends_approves_and_reconfigures() {
+        let (mut app, system) = deploy_full_system();
+        fund_treasury(&mut app, &system, 10_000_000);
+        app.execute_contract(
+            system.citizen2.clone(),
+            system.grants.clone(),
+            &grants::msg::ExecuteMsg::Apply {
+                title: "Library".to_string(),
+                description: "Community library".to_string(),
+                category: FundCategory::Education,
+                milestones: vec![grants::msg::MilestoneInput {
+                    description: "Build it".to_string(),
+                    amount: Uint128::new(1_000_000),
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+        let before = app
+            .wrap()
+            .query_balance(&system.citizen3, "ucitizen")
+            .unwrap();
+
+        let proposal = pass_and_execute(
+            &mut app,
+            &system,
+            vec![
+                spend(&system.citizen3, 500_000),
+                ProposalAction::ApproveGrant {
+                    grants_contract: system.grants.to_string(),
+                    grant_id: 1,
+                },
+                ProposalAction::UpdateVotingConfig {
+                    voting_period: Some(300),
+                    quorum_bps: None,
+                    threshold_bps: None,
+                    timelock_period: None,
+                },
+            ],
+        );
+        assert_eq!(proposal.status, "Executed");
+        assert_eq!(proposal.action_results, vec![ActionOutcome::Succeeded; 3]);
+
+        let after = app
+            .wrap()
+            .query_balance(&system.citizen3, "ucitizen")
+            .unwrap();
+        assert_eq!(after.amount - before.amount, Uint128::new(500_000));
+
+        let grant: grants::msg::GrantResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.grants.clone(),
+                &grants::msg::QueryMsg::GetGrant { grant_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(grant.status, "Active");
+
+        let config: voting::msg::VotingConfigResponse = app
+            .wrap()
+            .query_wasm_smart(system.voting.clone(), &voting::msg::QueryMsg::Config {})
+            .unwrap();
+        assert_eq!(config.voting_period, 300);
+    }
+
+    #[test]
+    fn failing_action_marks_proposal_execution_failed() {
+        let (mut app, system) = deploy_full_system();
+        fund_treasury(&mut app, &system, 1_000_000);
+
+        // The second spend exceeds the treasury balance
+        let proposal = pass_and_execute(
+            &mut app,
+            &system,
+            vec![
+                spend(&system.citizen3, 100_000),
+                spend(&system.citizen3, 50_000_000),
+            ],
+        );
+        assert_eq!(proposal.status, "ExecutionFailed");
+        assert_eq!(proposal.action_results[0], ActionOutcome::Succeeded);
+        assert!(matches!(
+            proposal.action_results[1],
+            ActionOutcome::Failed { .. }
+        ));
+
+        let balance: treasury::msg::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.treasury.clone(),
+                &treasury::msg::QueryMsg::Balance {},
+            )
+            .unwrap();
+        assert_eq!(balance.total, Uint128::new(900_000));
+    }
+
+    #[test]
+    fn grant_funding_template_approves_grant() {
+        let (mut app, system) = deploy_full_system();
+        let c1 = system.citizen1.clone();
+        issue_citizenship(&mut app, &system, &c1);
+        app.execute_contract(
+            system.citizen2.clone(),
+            system.grants.clone(),
+            &grants::msg::ExecuteMsg::Apply {
+                title: "Library".to_string(),
+                description: "Community library".to_string(),
+                category: FundCategory::Education,
+                milestones: vec![grants::msg::MilestoneInput {
+                    description: "Build it".to_string(),
+                    amount: Uint128::new(1_000_000),
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CreateFromTemplate {
+                template: citizen_common::templates::ProposalTemplate::GrantFunding {
+                    grant_id: 1,
+                    total_funding: Uint128::new(1_000_000),
+                    denom: "ucitizen".to_string(),
+                },
+                voting_period: Some(10),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            c1.clone(),
+            system.voting.clone(),
+            &voting::msg::ExecuteMsg::CastVote {
+                proposal_id: 1,
+                vote: VoteOption::Yes,
+                tokens: None,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|b| b.height += 11);
+        for msg in [
+            voting::msg::ExecuteMsg::TallyProposal { proposal_id: 1 },
+            voting::msg::ExecuteMsg::ExecuteProposal { proposal_id: 1 },
+        ] {
+            app.execute_contract(system.admin.clone(), system.voting.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        let proposal: voting::msg::ProposalResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.voting.clone(),
+                &voting::msg::QueryMsg::GetProposal { proposal_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(proposal.status, "Executed");
+        assert!(proposal.template.is_some());
+        let grant: grants::msg::GrantResponse = app
+            .wrap()
+            .query_wasm_smart(
+                system.grants.clone(),
+                &grants::msg::QueryMsg::GetGrant { grant_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(grant.status, "Active");
+    }
+}